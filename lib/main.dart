@@ -4,16 +4,23 @@ import 'package:flutter_riverpod/flutter_riverpod.dart';
 import 'package:picacg/language/generated/l10n.dart';
 import 'package:picacg/provider/config_provider.dart';
 import 'package:picacg/rust/api/storage/config.dart';
+import 'package:picacg/rust/api/storage/database.dart';
+import 'package:picacg/rust/api/storage/root.dart';
 import 'package:picacg/rust/frb_generated.dart';
 import 'package:picacg/router/route_config.dart';
 import 'package:picacg/utils/path_util.dart';
+import 'package:picacg/utils/secret_util.dart';
 import 'package:responsive_framework/responsive_framework.dart';
 
 Future<void> main() async {
   WidgetsFlutterBinding.ensureInitialized();
   await RustLib.init();
   final configPath = await PathUtil.getConfigPath();
-  final config = picacgLoadConfig(path: configPath);
+  picacgInitStorage(path: configPath);
+  await picacgOpenDatabase();
+  // 平台密钥不可用时密钥库保持锁定，由解锁页面使用口令解锁或重置
+  await SecretUtil.unlock();
+  final config = picacgLoadConfig();
   globalConfig = config;
   runApp(ProviderScope(child: const MyApp()));
 }
@@ -7,6 +7,7 @@ import 'package:picacg/router/route_config.dart';
 import 'package:picacg/rust/api/error/custom_error.dart';
 import 'package:picacg/rust/api/reqs/user.dart';
 import 'package:picacg/rust/api/storage/user_data.dart';
+import 'package:picacg/rust/api/types/register_entity.dart';
 import 'package:picacg/utils/toast_util.dart';
 
 mixin LoginStore {
@@ -143,30 +144,32 @@ mixin LoginStore {
       final answer3 = registerSafeAnswer3Controller.text.trim();
       final birthday = registerPersonalBirthdayController.text.trim();
       final sex = registerPersonalGenderController.text.trim();
-      String gender;
+      Gender gender;
       switch (sex) {
         case var s when s == Language.of(context).man:
-          gender = "m";
+          gender = Gender.male;
           break;
         case var s when s == Language.of(context).lady:
-          gender = "f";
+          gender = Gender.female;
           break;
         default:
-          gender = "bot";
+          gender = Gender.bot;
       }
 
       await picacgUserRegister(
-        email: username,
-        password: password,
-        name: nickName,
-        birthday: birthday,
-        gender: gender,
-        answer1: answer1,
-        answer2: answer2,
-        answer3: answer3,
-        question1: question1,
-        question2: question2,
-        question3: question3,
+        request: RegisterRequest(
+          email: username,
+          password: password,
+          name: nickName,
+          birthday: DateTime.parse(birthday),
+          gender: gender,
+          question1: question1,
+          question2: question2,
+          question3: question3,
+          answer1: answer1,
+          answer2: answer2,
+          answer3: answer3,
+        ),
       );
 
       final result = await picacgUserLogin(
@@ -0,0 +1,68 @@
+import 'package:flutter/material.dart';
+import 'package:flutter_riverpod/flutter_riverpod.dart';
+import 'package:picacg/language/generated/l10n.dart';
+import 'package:picacg/pages/unlock/unlock_store.dart';
+
+class UnlockPage extends ConsumerStatefulWidget {
+  const UnlockPage({super.key});
+
+  @override
+  ConsumerState<ConsumerStatefulWidget> createState() => _UnlockPageState();
+}
+
+class _UnlockPageState extends ConsumerState<UnlockPage> with UnlockStore {
+  @override
+  void dispose() {
+    passphraseController.dispose();
+    super.dispose();
+  }
+
+  @override
+  Widget build(BuildContext context) {
+    final obscureText = ref.watch(passphraseObscureTextProvider);
+
+    return Scaffold(
+      appBar: AppBar(title: Text(Language.of(context).unlockTitle)),
+      body: Center(
+        child: SizedBox(
+          width: 340,
+          child: Form(
+            key: unlockFormKey,
+            child: Column(
+              mainAxisSize: MainAxisSize.min,
+              crossAxisAlignment: CrossAxisAlignment.stretch,
+              children: [
+                Text(Language.of(context).unlockDescription),
+                const SizedBox(height: 16),
+                TextFormField(
+                  controller: passphraseController,
+                  obscureText: obscureText,
+                  validator: getPassphraseValidator(context),
+                  onFieldSubmitted: (_) => startUnlock(ref, context),
+                  decoration: InputDecoration(
+                    labelText: Language.of(context).unlockPassphrase,
+                    suffixIcon: IconButton(
+                      icon: Icon(
+                        obscureText ? Icons.visibility_off : Icons.visibility,
+                      ),
+                      onPressed: () => onObscureTextChanged(ref),
+                    ),
+                  ),
+                ),
+                const SizedBox(height: 16),
+                FilledButton(
+                  onPressed: () => startUnlock(ref, context),
+                  child: Text(Language.of(context).unlockTitle),
+                ),
+                TextButton(
+                  onPressed: () => startReset(ref, context),
+                  child: Text(Language.of(context).unlockReset),
+                ),
+              ],
+            ),
+          ),
+        ),
+      ),
+    );
+  }
+}
@@ -0,0 +1,85 @@
+import 'package:flutter/material.dart';
+import 'package:flutter_riverpod/flutter_riverpod.dart';
+import 'package:go_router/go_router.dart';
+import 'package:picacg/language/generated/l10n.dart';
+import 'package:picacg/provider/config_provider.dart';
+import 'package:picacg/router/route_config.dart';
+import 'package:picacg/rust/api/error/custom_error.dart';
+import 'package:picacg/rust/api/storage/config.dart';
+import 'package:picacg/utils/secret_util.dart';
+import 'package:picacg/utils/toast_util.dart';
+
+mixin UnlockStore {
+  final passphraseObscureTextProvider = StateProvider<bool>((ref) => true);
+  final passphraseController = TextEditingController();
+  final unlockFormKey = GlobalKey<FormState>();
+
+  void onObscureTextChanged(WidgetRef ref) {
+    ref.read(passphraseObscureTextProvider.notifier).state =
+        !ref.read(passphraseObscureTextProvider);
+  }
+
+  /// 解锁后重新加载配置，旧版本的明文令牌在此时迁移到密钥库。
+  void _enterApp(BuildContext context) {
+    globalConfig = picacgLoadConfig();
+    GoRouter.of(context).go(RouteConfig.main);
+  }
+
+  Future<void> startUnlock(WidgetRef ref, BuildContext context) async {
+    if (!unlockFormKey.currentState!.validate()) {
+      return;
+    }
+
+    try {
+      await SecretUtil.unlockWithPassphrase(passphraseController.text);
+      _enterApp(context);
+    } on CustomError catch (e) {
+      ToastUtil.showErrorSnackBar(message: e.errorMessage, context: context);
+    }
+  }
+
+  Future<void> startReset(WidgetRef ref, BuildContext context) async {
+    final confirmed = await showDialog<bool>(
+      context: context,
+      builder: (context) {
+        return AlertDialog(
+          title: Text(Language.of(context).unlockTitle),
+          content: Text(Language.of(context).unlockResetConfirm),
+          actions: [
+            TextButton(
+              onPressed: () => Navigator.of(context).pop(false),
+              child: Text(Language.of(context).cancel),
+            ),
+            TextButton(
+              onPressed: () => Navigator.of(context).pop(true),
+              child: Text(Language.of(context).confirm),
+            ),
+          ],
+        );
+      },
+    );
+    if (confirmed != true) {
+      return;
+    }
+
+    try {
+      await SecretUtil.reset();
+      // 平台密钥仍不可用时留在本页，用户输入的口令会创建新的密钥库
+      if (await SecretUtil.unlock()) {
+        _enterApp(context);
+      }
+    } on CustomError catch (e) {
+      ToastUtil.showErrorSnackBar(message: e.errorMessage, context: context);
+    }
+  }
+
+  String? Function(String?)? getPassphraseValidator(BuildContext context) {
+    return (value) {
+      if (value == null || value.isEmpty) {
+        return Language.of(context).unlockPassphraseEmpty;
+      }
+
+      return null;
+    };
+  }
+}
@@ -19,7 +19,7 @@ Future<List<CategoryEntity>> picacgComicCategoryApi(Ref ref) async {
 @riverpod
 Future<List<ComicEntity>> picacgComicRandomApi(Ref ref) async {
   try {
-    return await picacgComicRandom();
+    return (await picacgComicRandom()).comics;
   } on CustomError {
     rethrow;
   }
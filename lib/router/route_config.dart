@@ -1,11 +1,14 @@
 import 'package:go_router/go_router.dart';
 import 'package:picacg/pages/login/login_page.dart';
 import 'package:picacg/pages/main/main_page.dart';
+import 'package:picacg/pages/unlock/unlock_page.dart';
 import 'package:picacg/provider/config_provider.dart';
+import 'package:picacg/rust/api/storage/secret_data.dart';
 
 class RouteConfig {
   static const String main = '/';
   static const String login = '/login';
+  static const String unlock = '/unlock';
 
   static final GoRouter _router = GoRouter(
     initialLocation: main,
@@ -22,8 +25,18 @@ class RouteConfig {
           return const LoginPage();
         },
       ),
+      GoRoute(
+        path: unlock,
+        builder: (context, state) {
+          return const UnlockPage();
+        },
+      ),
     ],
     redirect: (context, state) async {
+      if (!picacgSecretsIsUnlocked()) {
+        return state.matchedLocation == unlock ? null : unlock;
+      }
+
       final isAuthenticated = globalConfig.userData.token.isEmpty;
 
       if (isAuthenticated && state.path != login) {
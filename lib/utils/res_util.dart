@@ -8,7 +8,7 @@ class ResUtil {
 
     String path = image.path;
     if (image.path.startsWith('/')) {
-      return path = image.path.substring(1);
+      path = image.path.substring(1);
     }
 
     return "$baseUrl/static/$path";
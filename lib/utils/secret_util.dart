@@ -0,0 +1,49 @@
+import 'dart:convert';
+import 'dart:math';
+
+import 'package:flutter/foundation.dart';
+import 'package:flutter_secure_storage/flutter_secure_storage.dart';
+import 'package:picacg/rust/api/storage/secret_data.dart';
+
+class SecretUtil {
+  static const String _secretKeyName = 'picacg_secret_key';
+  static const int _secretKeyLength = 32;
+
+  static const FlutterSecureStorage _storage = FlutterSecureStorage();
+
+  /// 使用平台安全存储中的密钥解锁密钥库，第一次启动时生成新的密钥。
+  ///
+  /// 平台不支持安全存储时返回 `false`，需要由用户输入口令解锁。
+  static Future<bool> unlock() async {
+    try {
+      var key = await _storage.read(key: _secretKeyName);
+      if (key == null) {
+        final random = Random.secure();
+        key = base64Encode(
+          List<int>.generate(_secretKeyLength, (_) => random.nextInt(256)),
+        );
+        await _storage.write(key: _secretKeyName, value: key);
+      }
+
+      picacgSecretsUnlockWithKey(key: base64Decode(key));
+      return true;
+    } catch (e) {
+      debugPrint("Failed to unlock secrets with platform key: $e");
+      return false;
+    }
+  }
+
+  static Future<void> unlockWithPassphrase(String passphrase) async {
+    await picacgSecretsUnlockWithPassphrase(passphrase: passphrase);
+  }
+
+  /// 忘记口令或平台密钥丢失时删除密钥库和平台密钥，之后需要重新解锁并登录。
+  static Future<void> reset() async {
+    picacgSecretsReset();
+    try {
+      await _storage.delete(key: _secretKeyName);
+    } catch (e) {
+      debugPrint("Failed to delete platform secret key: $e");
+    }
+  }
+}
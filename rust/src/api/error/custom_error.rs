@@ -10,6 +10,10 @@ pub enum CustomErrorType {
     FileWriteError,
     SerializeJsonError,
     LockError,
+    ImageProcessError,
+    ValidationError,
+    DatabaseError,
+    SecretError,
 }
 
 #[derive(Debug)]
@@ -26,3 +30,21 @@ impl From<anyhow::Error> for CustomError {
         }
     }
 }
+
+impl From<rusqlite::Error> for CustomError {
+    fn from(error: rusqlite::Error) -> Self {
+        CustomError {
+            error_code: CustomErrorType::DatabaseError,
+            error_message: error.to_string(),
+        }
+    }
+}
+
+impl From<r2d2::Error> for CustomError {
+    fn from(error: r2d2::Error) -> Self {
+        CustomError {
+            error_code: CustomErrorType::DatabaseError,
+            error_message: error.to_string(),
+        }
+    }
+}
@@ -1,6 +1,10 @@
 pub mod error;
 pub mod init;
+#[cfg(feature = "opds")]
+pub mod opds;
 pub mod reqs;
 pub mod storage;
+#[cfg(test)]
+pub mod test_support;
 pub mod types;
 pub mod utils;
@@ -0,0 +1,223 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::fmt::Write;
+
+pub(crate) const NAVIGATION_TYPE: &str =
+    "application/atom+xml;profile=opds-catalog;kind=navigation";
+pub(crate) const ACQUISITION_TYPE: &str =
+    "application/atom+xml;profile=opds-catalog;kind=acquisition";
+pub(crate) const OPENSEARCH_TYPE: &str = "application/opensearchdescription+xml";
+pub(crate) const CBZ_TYPE: &str = "application/vnd.comicbook+zip";
+
+pub(crate) const ACQUISITION_REL: &str = "http://opds-spec.org/acquisition";
+pub(crate) const IMAGE_REL: &str = "http://opds-spec.org/image";
+pub(crate) const THUMBNAIL_REL: &str = "http://opds-spec.org/image/thumbnail";
+/// OPDS Page Streaming Extension 1.2 的逐页读取链接。
+pub(crate) const PSE_STREAM_REL: &str = "http://vaemendis.net/opds-pse/stream";
+
+/// 阅读器通过 OpenSearch 描述找到搜索地址。
+pub(crate) const OPENSEARCH_DESCRIPTION: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+     <OpenSearchDescription xmlns=\"http://a9.com/-/spec/opensearch/1.1/\">\
+     <ShortName>PicACG</ShortName><Description>PicACG downloads</Description>\
+     <InputEncoding>UTF-8</InputEncoding><OutputEncoding>UTF-8</OutputEncoding>\
+     <Url type=\"application/atom+xml;profile=opds-catalog;kind=acquisition\" \
+     template=\"/opds/search?q={searchTerms}\"/></OpenSearchDescription>";
+
+pub(crate) struct Link {
+    rel: String,
+    href: String,
+    media_type: String,
+    title: Option<String>,
+    /// Page Streaming Extension 链接的页数。
+    pse_count: Option<i32>,
+}
+
+impl Link {
+    pub(crate) fn new(rel: &str, href: String, media_type: &str) -> Self {
+        Link {
+            rel: rel.to_string(),
+            href,
+            media_type: media_type.to_string(),
+            title: None,
+            pse_count: None,
+        }
+    }
+
+    pub(crate) fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    pub(crate) fn pse_count(mut self, count: i32) -> Self {
+        self.pse_count = Some(count);
+        self
+    }
+}
+
+pub(crate) struct Entry {
+    pub(crate) id: String,
+    pub(crate) title: String,
+    pub(crate) updated: DateTime<Utc>,
+    pub(crate) authors: Vec<String>,
+    pub(crate) summary: Option<String>,
+    pub(crate) categories: Vec<String>,
+    pub(crate) links: Vec<Link>,
+}
+
+pub(crate) struct Feed {
+    pub(crate) id: String,
+    pub(crate) title: String,
+    pub(crate) updated: DateTime<Utc>,
+    pub(crate) links: Vec<Link>,
+    pub(crate) entries: Vec<Entry>,
+}
+
+/// 转义 XML 文本和属性值中的特殊字符，去掉 XML 1.0 不允许的控制字符。
+pub(crate) fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn timestamp(time: &DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn write_link(xml: &mut String, link: &Link) {
+    let _ = write!(
+        xml,
+        "<link rel=\"{}\" href=\"{}\" type=\"{}\"",
+        escape(&link.rel),
+        escape(&link.href),
+        escape(&link.media_type)
+    );
+    if let Some(title) = &link.title {
+        let _ = write!(xml, " title=\"{}\"", escape(title));
+    }
+    if let Some(count) = link.pse_count {
+        let _ = write!(xml, " pse:count=\"{}\"", count);
+    }
+    xml.push_str("/>");
+}
+
+impl Feed {
+    /// 生成 OPDS 1.2 的 Atom 文档。
+    pub(crate) fn render(&self) -> String {
+        let mut xml = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+             <feed xmlns=\"http://www.w3.org/2005/Atom\" \
+             xmlns:opds=\"http://opds-spec.org/2010/catalog\" \
+             xmlns:pse=\"http://vaemendis.net/opds-pse/ns\">",
+        );
+        let _ = write!(
+            xml,
+            "<id>{}</id><title>{}</title><updated>{}</updated>",
+            escape(&self.id),
+            escape(&self.title),
+            timestamp(&self.updated)
+        );
+        for link in &self.links {
+            write_link(&mut xml, link);
+        }
+
+        for entry in &self.entries {
+            let _ = write!(
+                xml,
+                "<entry><id>{}</id><title>{}</title><updated>{}</updated>",
+                escape(&entry.id),
+                escape(&entry.title),
+                timestamp(&entry.updated)
+            );
+            for author in &entry.authors {
+                let _ = write!(xml, "<author><name>{}</name></author>", escape(author));
+            }
+            if let Some(summary) = &entry.summary {
+                let _ = write!(xml, "<summary type=\"text\">{}</summary>", escape(summary));
+            }
+            for category in &entry.categories {
+                let _ = write!(
+                    xml,
+                    "<category term=\"{0}\" label=\"{0}\"/>",
+                    escape(category)
+                );
+            }
+            for link in &entry.links {
+                write_link(&mut xml, link);
+            }
+            xml.push_str("</entry>");
+        }
+
+        xml.push_str("</feed>");
+        xml
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::opds::feed::{
+        Entry, Feed, Link, ACQUISITION_TYPE, CBZ_TYPE, NAVIGATION_TYPE, PSE_STREAM_REL,
+    };
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_render_feed() {
+        let updated = Utc.with_ymd_and_hms(2024, 5, 1, 8, 0, 0).unwrap();
+        let feed = Feed {
+            id: "urn:picacg:comics".to_string(),
+            title: "A & B".to_string(),
+            updated,
+            links: vec![Link::new(
+                "self",
+                "/opds/comics".to_string(),
+                ACQUISITION_TYPE,
+            )],
+            entries: vec![Entry {
+                id: "urn:picacg:comic:1".to_string(),
+                title: "<Title>\u{1}".to_string(),
+                updated,
+                authors: vec!["作者".to_string()],
+                summary: Some("\"quoted\"".to_string()),
+                categories: vec!["全彩".to_string()],
+                links: vec![
+                    Link::new("subsection", "/opds/comics/1".to_string(), NAVIGATION_TYPE),
+                    Link::new(
+                        PSE_STREAM_REL,
+                        "/opds/comics/1/1/pages/{pageNumber}".to_string(),
+                        "image/jpeg",
+                    )
+                    .title("第1话")
+                    .pse_count(12),
+                    Link::new(
+                        "http://opds-spec.org/acquisition",
+                        "/a?x=1&y=2".to_string(),
+                        CBZ_TYPE,
+                    ),
+                ],
+            }],
+        };
+
+        let xml = feed.render();
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?><feed "));
+        assert!(xml.contains("<title>A &amp; B</title><updated>2024-05-01T08:00:00Z</updated>"));
+        assert!(xml.contains("<title>&lt;Title&gt;</title>"));
+        assert!(xml.contains("<author><name>作者</name></author>"));
+        assert!(xml.contains("<summary type=\"text\">&quot;quoted&quot;</summary>"));
+        assert!(xml.contains("<category term=\"全彩\" label=\"全彩\"/>"));
+        assert!(xml.contains(
+            "href=\"/opds/comics/1/1/pages/{pageNumber}\" type=\"image/jpeg\" \
+             title=\"第1话\" pse:count=\"12\"/>"
+        ));
+        assert!(xml.contains("href=\"/a?x=1&amp;y=2\""));
+        assert!(xml.ends_with("</entry></feed>"));
+    }
+}
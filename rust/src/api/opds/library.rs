@@ -0,0 +1,298 @@
+use crate::api::{
+    error::custom_error::{CustomError, CustomErrorType},
+    opds::feed::escape,
+    storage::download_data::{
+        completed_download_episodes, picacg_download_comics, DownloadEpisode,
+    },
+    types::comic_info_entity::ComicInfoEntity,
+    utils::chinese::normalize_chinese,
+};
+use chrono::{DateTime, Utc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    io::{Cursor, Write},
+    path::PathBuf,
+};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+/// 至少有一个章节下载完成的漫画。
+pub(crate) struct LibraryComic {
+    pub(crate) info: ComicInfoEntity,
+    pub(crate) updated_at: DateTime<Utc>,
+    /// 下载完成的章节，按章节序号排列。
+    pub(crate) episodes: Vec<DownloadEpisode>,
+}
+
+/// 读取下载记录，按最近下载的时间从新到旧排列。
+pub(crate) async fn load_library() -> Result<Vec<LibraryComic>, CustomError> {
+    let mut episodes = HashMap::<String, Vec<DownloadEpisode>>::new();
+    for episode in completed_download_episodes().await? {
+        episodes
+            .entry(episode.comic_id.clone())
+            .or_default()
+            .push(episode);
+    }
+
+    Ok(picacg_download_comics()
+        .await?
+        .into_iter()
+        .filter_map(|comic| {
+            Some(LibraryComic {
+                episodes: episodes.remove(&comic.info.id)?,
+                info: comic.info,
+                updated_at: comic.updated_at,
+            })
+        })
+        .collect())
+}
+
+/// 目录的分组方式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Facet {
+    Category,
+    Tag,
+    Author,
+}
+
+impl Facet {
+    fn values(self, info: &ComicInfoEntity) -> Vec<&str> {
+        let values = match self {
+            Facet::Category => info.categories.iter().map(String::as_str).collect(),
+            Facet::Tag => info.tags.iter().map(String::as_str).collect(),
+            Facet::Author => vec![info.author.as_str()],
+        };
+        values
+            .into_iter()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .collect()
+    }
+}
+
+/// 分组的所有取值和漫画数量，数量多的在前，数量相同时按名称排列。
+pub(crate) fn facet_counts(library: &[LibraryComic], facet: Facet) -> Vec<(String, usize)> {
+    let mut counts = BTreeMap::<&str, usize>::new();
+    for comic in library {
+        for value in facet.values(&comic.info) {
+            *counts.entry(value).or_default() += 1;
+        }
+    }
+
+    let mut counts = counts
+        .into_iter()
+        .map(|(value, count)| (value.to_string(), count))
+        .collect::<Vec<_>>();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
+/// 分组取值为 `name` 的漫画。
+pub(crate) fn facet_comics<'a>(
+    library: &'a [LibraryComic],
+    facet: Facet,
+    name: &str,
+) -> Vec<&'a LibraryComic> {
+    let name = name.trim();
+    library
+        .iter()
+        .filter(|comic| facet.values(&comic.info).contains(&name))
+        .collect()
+}
+
+/// 在标题、作者、标签和简介中搜索，所有词都要出现，忽略大小写和简繁写法。
+pub(crate) fn search_comics<'a>(library: &'a [LibraryComic], query: &str) -> Vec<&'a LibraryComic> {
+    let terms = normalize_chinese(query)
+        .split_whitespace()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    if terms.is_empty() {
+        return Vec::new();
+    }
+
+    library
+        .iter()
+        .filter(|comic| {
+            let info = &comic.info;
+            let text = normalize_chinese(&format!(
+                "{}\n{}\n{}\n{}",
+                info.title,
+                info.author,
+                info.tags.join("\n"),
+                info.description
+            ));
+            terms.iter().all(|term| text.contains(term.as_str()))
+        })
+        .collect()
+}
+
+/// 图片文件的 MIME 类型。
+pub(crate) fn image_type(path: &std::path::Path) -> &'static str {
+    match path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_lowercase)
+        .as_deref()
+    {
+        Some("png") => "image/png",
+        Some("webp") => "image/webp",
+        Some("gif") => "image/gif",
+        _ => "image/jpeg",
+    }
+}
+
+/// ComicRack 格式的元数据，阅读器据此显示标题、作者和标签。
+fn comic_info_xml(info: &ComicInfoEntity, episode: &DownloadEpisode, page_count: usize) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<ComicInfo>\
+         <Title>{}</Title><Series>{}</Series><Number>{}</Number><Summary>{}</Summary>\
+         <Writer>{}</Writer><Translator>{}</Translator><Genre>{}</Genre><Tags>{}</Tags>\
+         <PageCount>{}</PageCount></ComicInfo>\n",
+        escape(&episode.ep_title),
+        escape(&info.title),
+        episode.ep_order,
+        escape(&info.description),
+        escape(&info.author),
+        escape(&info.chinese_team),
+        escape(&info.categories.join(", ")),
+        escape(&info.tags.join(", ")),
+        page_count
+    )
+}
+
+/// 将章节的图片打包为 CBZ，图片已经压缩过，直接存储不再压缩。
+pub(crate) fn build_cbz(
+    info: &ComicInfoEntity,
+    episode: &DownloadEpisode,
+    pages: &[PathBuf],
+) -> Result<Vec<u8>, CustomError> {
+    let zip_error = |e: zip::result::ZipError| CustomError {
+        error_code: CustomErrorType::FileWriteError,
+        error_message: format!("Failed to build cbz: {}", e),
+    };
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+
+    for page in pages {
+        let name = page
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let bytes = fs::read(page).map_err(|e| CustomError {
+            error_code: CustomErrorType::FileReadError,
+            error_message: format!("Failed to read {}: {}", page.display(), e),
+        })?;
+        zip.start_file(name, options).map_err(zip_error)?;
+        zip.write_all(&bytes)
+            .map_err(|e| zip_error(zip::result::ZipError::Io(e)))?;
+    }
+
+    zip.start_file("ComicInfo.xml", options)
+        .map_err(zip_error)?;
+    zip.write_all(comic_info_xml(info, episode, pages.len()).as_bytes())
+        .map_err(|e| zip_error(zip::result::ZipError::Io(e)))?;
+
+    Ok(zip.finish().map_err(zip_error)?.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::{
+        opds::library::{
+            build_cbz, facet_comics, facet_counts, search_comics, Facet, LibraryComic,
+        },
+        storage::download_data::{DownloadEpisode, DownloadStatus},
+        test_support::comic_info_fixture,
+    };
+    use chrono::Utc;
+    use std::io::{Cursor, Read};
+
+    fn episode(comic_id: &str, ep_order: i32) -> DownloadEpisode {
+        DownloadEpisode {
+            comic_id: comic_id.to_string(),
+            ep_order,
+            ep_title: format!("第{}话", ep_order),
+            status: DownloadStatus::Completed,
+            downloaded_pages: 2,
+            total_pages: 2,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn library_comic(id: &str, author: &str, tags: &[&str]) -> LibraryComic {
+        let mut info = comic_info_fixture(id);
+        info.author = author.to_string();
+        info.tags = tags.iter().map(|tag| tag.to_string()).collect();
+        LibraryComic {
+            info,
+            updated_at: Utc::now(),
+            episodes: vec![episode(id, 1)],
+        }
+    }
+
+    #[test]
+    fn test_library_facets() {
+        let library = vec![
+            library_comic("1", "作者A", &["全彩", "長篇"]),
+            library_comic("2", "作者B", &["全彩"]),
+            library_comic("3", " ", &["短篇"]),
+        ];
+
+        assert_eq!(
+            facet_counts(&library, Facet::Tag),
+            vec![
+                ("全彩".to_string(), 2),
+                ("短篇".to_string(), 1),
+                ("長篇".to_string(), 1)
+            ]
+        );
+        assert_eq!(facet_counts(&library, Facet::Author).len(), 2);
+        assert_eq!(facet_comics(&library, Facet::Tag, "全彩").len(), 2);
+        assert_eq!(
+            facet_comics(&library, Facet::Author, "作者B")[0].info.id,
+            "2"
+        );
+
+        let found = search_comics(&library, "长篇 title");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].info.id, "1");
+        assert!(search_comics(&library, " ").is_empty());
+    }
+
+    #[test]
+    fn test_build_cbz() {
+        let dir = std::env::temp_dir().join(format!("picacg_test_cbz_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let pages = vec![dir.join("0001.jpg"), dir.join("0002.png")];
+        std::fs::write(&pages[0], b"first").unwrap();
+        std::fs::write(&pages[1], b"second").unwrap();
+
+        let comic = library_comic("cbz", "作者 & 汉化", &["全彩"]);
+        let cbz = build_cbz(&comic.info, &comic.episodes[0], &pages).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let mut archive = zip::ZipArchive::new(Cursor::new(cbz)).unwrap();
+        assert_eq!(
+            archive.file_names().collect::<Vec<_>>().len(),
+            3,
+            "two pages and ComicInfo.xml"
+        );
+        let mut content = String::new();
+        archive
+            .by_name("0002.png")
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, "second");
+
+        let mut metadata = String::new();
+        archive
+            .by_name("ComicInfo.xml")
+            .unwrap()
+            .read_to_string(&mut metadata)
+            .unwrap();
+        assert!(metadata.contains("<Writer>作者 &amp; 汉化</Writer>"));
+        assert!(metadata.contains("<PageCount>2</PageCount>"));
+    }
+}
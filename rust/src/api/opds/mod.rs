@@ -0,0 +1,881 @@
+//! 以 OPDS 1.2 目录的形式在局域网内提供已下载的漫画，供 KOReader、Panels、Chunky 等阅读器浏览和下载。
+//!
+//! 章节按需打包为 CBZ，并支持 OPDS Page Streaming Extension 逐页读取。
+//!
+//! 目前只供命令行工具的 `opds` 子命令使用，不导出到 Dart，应用构建也不启用 `opds` 特性。
+
+mod feed;
+mod library;
+
+use crate::api::{
+    error::custom_error::{CustomError, CustomErrorType},
+    opds::{
+        feed::{
+            Entry, Feed, Link, ACQUISITION_REL, ACQUISITION_TYPE, CBZ_TYPE, IMAGE_REL,
+            NAVIGATION_TYPE, OPENSEARCH_DESCRIPTION, OPENSEARCH_TYPE, PSE_STREAM_REL,
+            THUMBNAIL_REL,
+        },
+        library::{
+            build_cbz, facet_comics, facet_counts, image_type, load_library, search_comics, Facet,
+            LibraryComic,
+        },
+    },
+    storage::download_data::{
+        download_episode_pages, picacg_download_comic, picacg_download_episodes, DownloadEpisode,
+        DownloadStatus,
+    },
+    types::comic_info_entity::ComicInfoEntity,
+};
+use axum::{
+    extract::{Path, Query, Request, State},
+    http::{header, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::Utc;
+use flutter_rust_bridge::frb;
+use serde::{Deserialize, Serialize};
+use std::{net::IpAddr, sync::Mutex};
+use tokio::task::AbortHandle;
+
+const CATALOG_PATH: &str = "/opds";
+/// 每页漫画数量。
+const PAGE_SIZE: usize = 50;
+/// 同一章节的图片格式可能不同，逐页读取链接只声明为图片。
+const ANY_IMAGE_TYPE: &str = "image/*";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[frb(ignore)]
+pub struct OpdsSettings {
+    /// 监听地址，默认 `127.0.0.1` 只允许本机访问；监听其他地址时必须设置用户名和密码。
+    pub host: String,
+    /// 监听端口，为 0 时由系统分配。
+    pub port: u16,
+    /// 用户名和密码同时设置时启用 Basic 认证。
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+impl Default for OpdsSettings {
+    fn default() -> Self {
+        OpdsSettings {
+            host: "127.0.0.1".to_string(),
+            port: 8080,
+            username: None,
+            password: None,
+        }
+    }
+}
+
+impl OpdsSettings {
+    /// 是否只监听本机回环地址。
+    fn is_loopback(&self) -> bool {
+        self.host.eq_ignore_ascii_case("localhost")
+            || self.host.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+    }
+
+    /// 检查认证设置，返回客户端应当发送的 `Authorization` 请求头，不需要认证时为 `None`。
+    fn authorization(&self) -> Result<Option<String>, CustomError> {
+        let username = self.username.as_deref().filter(|u| !u.is_empty());
+        let password = self.password.as_deref().filter(|p| !p.is_empty());
+        match (username, password) {
+            (None, None) if self.is_loopback() => Ok(None),
+            (None, None) => Err(CustomError {
+                error_code: CustomErrorType::ParameterError,
+                error_message: format!(
+                    "Username and password are required when listening on {}",
+                    self.host
+                ),
+            }),
+            (Some(username), Some(password)) if !username.contains(':') => Ok(Some(format!(
+                "Basic {}",
+                STANDARD.encode(format!("{}:{}", username, password))
+            ))),
+            (Some(_), Some(_)) => Err(CustomError {
+                error_code: CustomErrorType::ParameterError,
+                error_message: "Username must not contain ':'".to_string(),
+            }),
+            _ => Err(CustomError {
+                error_code: CustomErrorType::ParameterError,
+                error_message: "Username and password must be set together".to_string(),
+            }),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[frb(ignore)]
+pub struct OpdsServerInfo {
+    /// 实际监听的地址和端口。
+    pub address: String,
+    /// 目录入口的路径，阅读器中填写 `http://<本机 IP>:<端口><catalog_path>`。
+    pub catalog_path: String,
+    /// 是否启用了 Basic 认证。
+    pub auth: bool,
+}
+
+struct OpdsServer {
+    info: OpdsServerInfo,
+    handle: AbortHandle,
+}
+
+static OPDS_SERVER: Mutex<Option<OpdsServer>> = Mutex::new(None);
+
+#[derive(Clone)]
+struct ServerState {
+    authorization: Option<String>,
+}
+
+/// 处理请求时的错误，参数错误返回 400，其余返回 500。
+enum OpdsError {
+    NotFound,
+    Custom(CustomError),
+}
+
+impl From<CustomError> for OpdsError {
+    fn from(e: CustomError) -> Self {
+        OpdsError::Custom(e)
+    }
+}
+
+impl IntoResponse for OpdsError {
+    fn into_response(self) -> Response {
+        match self {
+            OpdsError::NotFound => StatusCode::NOT_FOUND.into_response(),
+            OpdsError::Custom(e) => {
+                let status = match e.error_code {
+                    CustomErrorType::ParameterError => StatusCode::BAD_REQUEST,
+                    _ => StatusCode::INTERNAL_SERVER_ERROR,
+                };
+                (status, e.error_message).into_response()
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct PageQuery {
+    page: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct FacetQuery {
+    name: String,
+    page: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct SearchQuery {
+    q: String,
+    page: Option<usize>,
+}
+
+/// 拼接路径和查询参数。
+fn href(path: &str, params: &[(&str, &str)]) -> String {
+    if params.is_empty() {
+        return path.to_string();
+    }
+
+    let query = form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(params)
+        .finish();
+    format!("{}?{}", path, query)
+}
+
+/// 比较时间与内容无关，避免通过响应时间猜测密码。
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+async fn basic_auth(State(state): State<ServerState>, request: Request, next: Next) -> Response {
+    if let Some(expected) = &state.authorization {
+        let given = request
+            .headers()
+            .get(header::AUTHORIZATION)
+            .map(|value| value.as_bytes())
+            .unwrap_or_default();
+        if !constant_time_eq(given, expected.as_bytes()) {
+            return (
+                StatusCode::UNAUTHORIZED,
+                [(header::WWW_AUTHENTICATE, "Basic realm=\"PicACG\"")],
+            )
+                .into_response();
+        }
+    }
+
+    next.run(request).await
+}
+
+fn feed_response(feed: Feed, media_type: &'static str) -> Response {
+    ([(header::CONTENT_TYPE, media_type)], feed.render()).into_response()
+}
+
+/// 所有目录共有的链接：起始页和搜索。
+fn feed_links(self_href: String, media_type: &str) -> Vec<Link> {
+    vec![
+        Link::new("self", self_href, media_type),
+        Link::new("start", CATALOG_PATH.to_string(), NAVIGATION_TYPE),
+        Link::new(
+            "search",
+            format!("{}/opensearch.xml", CATALOG_PATH),
+            OPENSEARCH_TYPE,
+        ),
+    ]
+}
+
+fn navigation_entry(id: &str, title: &str, summary: String, href: String, kind: &str) -> Entry {
+    Entry {
+        id: format!("urn:picacg:{}", id),
+        title: title.to_string(),
+        updated: Utc::now(),
+        authors: Vec::new(),
+        summary: Some(summary),
+        categories: Vec::new(),
+        links: vec![Link::new("subsection", href, kind)],
+    }
+}
+
+/// 封面链接，封面是第一个章节的第一页。
+fn cover_links(first_episode: &DownloadEpisode) -> Vec<Link> {
+    let href = format!("{}/comics/{}/cover", CATALOG_PATH, first_episode.comic_id);
+    let media_type = download_episode_pages(&first_episode.comic_id, first_episode.ep_order)
+        .ok()
+        .and_then(|pages| pages.first().map(|page| image_type(page)))
+        .unwrap_or(ANY_IMAGE_TYPE);
+    vec![
+        Link::new(IMAGE_REL, href.clone(), media_type),
+        Link::new(THUMBNAIL_REL, href, media_type),
+    ]
+}
+
+/// 章节的 CBZ 下载链接和逐页读取链接。
+fn episode_links(episode: &DownloadEpisode) -> Vec<Link> {
+    let base = format!(
+        "{}/comics/{}/{}",
+        CATALOG_PATH, episode.comic_id, episode.ep_order
+    );
+    vec![
+        Link::new(ACQUISITION_REL, format!("{}/cbz", base), CBZ_TYPE).title(&episode.ep_title),
+        Link::new(
+            PSE_STREAM_REL,
+            format!("{}/pages/{{pageNumber}}", base),
+            ANY_IMAGE_TYPE,
+        )
+        .title(&episode.ep_title)
+        .pse_count(episode.total_pages),
+    ]
+}
+
+fn comic_metadata(info: &ComicInfoEntity) -> (Vec<String>, Option<String>, Vec<String>) {
+    let authors = Some(info.author.trim())
+        .filter(|author| !author.is_empty())
+        .map(str::to_string)
+        .into_iter()
+        .collect();
+    let summary = Some(info.description.trim())
+        .filter(|description| !description.is_empty())
+        .map(str::to_string);
+    let categories = info.categories.iter().chain(&info.tags).cloned().collect();
+    (authors, summary, categories)
+}
+
+/// 只有一个章节的漫画直接提供下载，多个章节时链接到章节列表。
+fn comic_entry(comic: &LibraryComic) -> Entry {
+    let info = &comic.info;
+    let (authors, summary, categories) = comic_metadata(info);
+    let mut links = cover_links(&comic.episodes[0]);
+    match comic.episodes.as_slice() {
+        [episode] => links.extend(episode_links(episode)),
+        _ => links.push(
+            Link::new(
+                "subsection",
+                format!("{}/comics/{}", CATALOG_PATH, info.id),
+                ACQUISITION_TYPE,
+            )
+            .title(&format!("{} episodes", comic.episodes.len())),
+        ),
+    }
+
+    Entry {
+        id: format!("urn:picacg:comic:{}", info.id),
+        title: info.title.clone(),
+        updated: comic.updated_at,
+        authors,
+        summary,
+        categories,
+        links,
+    }
+}
+
+/// 分页的漫画列表，`page_href` 生成指定页的地址。
+fn comics_feed(
+    id: &str,
+    title: &str,
+    comics: &[&LibraryComic],
+    page: Option<usize>,
+    page_href: impl Fn(usize) -> String,
+) -> Response {
+    let page = page.unwrap_or(1).max(1);
+    let pages = comics.len().div_ceil(PAGE_SIZE).max(1);
+
+    let mut links = feed_links(page_href(page), ACQUISITION_TYPE);
+    links.push(Link::new("up", CATALOG_PATH.to_string(), NAVIGATION_TYPE));
+    if page > 1 {
+        links.push(Link::new(
+            "previous",
+            page_href((page - 1).min(pages)),
+            ACQUISITION_TYPE,
+        ));
+    }
+    if page < pages {
+        links.push(Link::new("next", page_href(page + 1), ACQUISITION_TYPE));
+    }
+
+    let feed = Feed {
+        id: format!("urn:picacg:{}", id),
+        title: title.to_string(),
+        updated: comics
+            .iter()
+            .map(|comic| comic.updated_at)
+            .max()
+            .unwrap_or_else(Utc::now),
+        links,
+        entries: comics
+            .iter()
+            .skip((page - 1).saturating_mul(PAGE_SIZE))
+            .take(PAGE_SIZE)
+            .map(|comic| comic_entry(comic))
+            .collect(),
+    };
+    feed_response(feed, ACQUISITION_TYPE)
+}
+
+/// 分组对应的列表路径、单个取值的路径和标题。
+fn facet_paths(facet: Facet) -> (&'static str, &'static str, &'static str) {
+    match facet {
+        Facet::Category => ("categories", "category", "Categories"),
+        Facet::Tag => ("tags", "tag", "Tags"),
+        Facet::Author => ("authors", "author", "Authors"),
+    }
+}
+
+async fn root_feed() -> Response {
+    let feed = Feed {
+        id: "urn:picacg:root".to_string(),
+        title: "PicACG".to_string(),
+        updated: Utc::now(),
+        links: feed_links(CATALOG_PATH.to_string(), NAVIGATION_TYPE),
+        entries: vec![
+            navigation_entry(
+                "comics",
+                "All comics",
+                "Recently downloaded first".to_string(),
+                format!("{}/comics", CATALOG_PATH),
+                ACQUISITION_TYPE,
+            ),
+            navigation_entry(
+                "categories",
+                "Categories",
+                "Browse by category".to_string(),
+                format!("{}/categories", CATALOG_PATH),
+                NAVIGATION_TYPE,
+            ),
+            navigation_entry(
+                "tags",
+                "Tags",
+                "Browse by tag".to_string(),
+                format!("{}/tags", CATALOG_PATH),
+                NAVIGATION_TYPE,
+            ),
+            navigation_entry(
+                "authors",
+                "Authors",
+                "Browse by author".to_string(),
+                format!("{}/authors", CATALOG_PATH),
+                NAVIGATION_TYPE,
+            ),
+        ],
+    };
+    feed_response(feed, NAVIGATION_TYPE)
+}
+
+async fn all_comics(Query(query): Query<PageQuery>) -> Result<Response, OpdsError> {
+    let library = load_library().await?;
+    let comics = library.iter().collect::<Vec<_>>();
+    let path = format!("{}/comics", CATALOG_PATH);
+    Ok(comics_feed(
+        "comics",
+        "All comics",
+        &comics,
+        query.page,
+        |page| href(&path, &[("page", &page.to_string())]),
+    ))
+}
+
+async fn facet_feed(facet: Facet) -> Result<Response, OpdsError> {
+    let (list_path, item_path, title) = facet_paths(facet);
+    let library = load_library().await?;
+    let entries = facet_counts(&library, facet)
+        .into_iter()
+        .map(|(name, count)| {
+            navigation_entry(
+                &format!("{}:{}", item_path, name),
+                &name,
+                format!("{} comics", count),
+                href(
+                    &format!("{}/{}", CATALOG_PATH, item_path),
+                    &[("name", &name)],
+                ),
+                ACQUISITION_TYPE,
+            )
+        })
+        .collect();
+
+    let mut links = feed_links(format!("{}/{}", CATALOG_PATH, list_path), NAVIGATION_TYPE);
+    links.push(Link::new("up", CATALOG_PATH.to_string(), NAVIGATION_TYPE));
+    let feed = Feed {
+        id: format!("urn:picacg:{}", list_path),
+        title: title.to_string(),
+        updated: library
+            .iter()
+            .map(|comic| comic.updated_at)
+            .max()
+            .unwrap_or_else(Utc::now),
+        links,
+        entries,
+    };
+    Ok(feed_response(feed, NAVIGATION_TYPE))
+}
+
+async fn facet_comics_feed(facet: Facet, query: FacetQuery) -> Result<Response, OpdsError> {
+    let (_, item_path, _) = facet_paths(facet);
+    let library = load_library().await?;
+    let comics = facet_comics(&library, facet, &query.name);
+    let path = format!("{}/{}", CATALOG_PATH, item_path);
+    Ok(comics_feed(
+        &format!("{}:{}", item_path, query.name),
+        &query.name,
+        &comics,
+        query.page,
+        |page| href(&path, &[("name", &query.name), ("page", &page.to_string())]),
+    ))
+}
+
+async fn search_feed(Query(query): Query<SearchQuery>) -> Result<Response, OpdsError> {
+    let library = load_library().await?;
+    let comics = search_comics(&library, &query.q);
+    let path = format!("{}/search", CATALOG_PATH);
+    Ok(comics_feed(
+        &format!("search:{}", query.q),
+        &query.q,
+        &comics,
+        query.page,
+        |page| href(&path, &[("q", &query.q), ("page", &page.to_string())]),
+    ))
+}
+
+async fn opensearch() -> Response {
+    (
+        [(header::CONTENT_TYPE, OPENSEARCH_TYPE)],
+        OPENSEARCH_DESCRIPTION,
+    )
+        .into_response()
+}
+
+/// 已下载的漫画及其下载完成的章节。
+async fn find_comic(comic_id: &str) -> Result<(ComicInfoEntity, Vec<DownloadEpisode>), OpdsError> {
+    let comic = picacg_download_comic(comic_id.to_string())
+        .await?
+        .ok_or(OpdsError::NotFound)?;
+    let episodes = picacg_download_episodes(comic_id.to_string())
+        .await?
+        .into_iter()
+        .filter(|episode| episode.status == DownloadStatus::Completed)
+        .collect::<Vec<_>>();
+    if episodes.is_empty() {
+        return Err(OpdsError::NotFound);
+    }
+
+    Ok((comic.info, episodes))
+}
+
+async fn find_episode(
+    comic_id: &str,
+    ep_order: i32,
+) -> Result<(ComicInfoEntity, DownloadEpisode), OpdsError> {
+    let (info, episodes) = find_comic(comic_id).await?;
+    let episode = episodes
+        .into_iter()
+        .find(|episode| episode.ep_order == ep_order)
+        .ok_or(OpdsError::NotFound)?;
+    Ok((info, episode))
+}
+
+async fn episodes_feed(Path(comic_id): Path<String>) -> Result<Response, OpdsError> {
+    let (info, episodes) = find_comic(&comic_id).await?;
+    let (authors, summary, categories) = comic_metadata(&info);
+    let self_href = format!("{}/comics/{}", CATALOG_PATH, comic_id);
+
+    let mut links = feed_links(self_href, ACQUISITION_TYPE);
+    links.push(Link::new(
+        "up",
+        format!("{}/comics", CATALOG_PATH),
+        ACQUISITION_TYPE,
+    ));
+    let feed = Feed {
+        id: format!("urn:picacg:comic:{}:episodes", comic_id),
+        title: info.title.clone(),
+        updated: episodes
+            .iter()
+            .map(|episode| episode.updated_at)
+            .max()
+            .unwrap_or_else(Utc::now),
+        links,
+        entries: episodes
+            .iter()
+            .map(|episode| {
+                let mut links = cover_links(&episodes[0]);
+                links.extend(episode_links(episode));
+                Entry {
+                    id: format!("urn:picacg:comic:{}:{}", comic_id, episode.ep_order),
+                    title: format!("{} - {}", info.title, episode.ep_title),
+                    updated: episode.updated_at,
+                    authors: authors.clone(),
+                    summary: summary.clone(),
+                    categories: categories.clone(),
+                    links,
+                }
+            })
+            .collect(),
+    };
+    Ok(feed_response(feed, ACQUISITION_TYPE))
+}
+
+async fn page_response(comic_id: &str, ep_order: i32, page: usize) -> Result<Response, OpdsError> {
+    let path = download_episode_pages(comic_id, ep_order)?
+        .into_iter()
+        .nth(page)
+        .ok_or(OpdsError::NotFound)?;
+    let bytes = tokio::fs::read(&path).await.map_err(|e| CustomError {
+        error_code: CustomErrorType::FileReadError,
+        error_message: format!("Failed to read {}: {}", path.display(), e),
+    })?;
+    Ok(([(header::CONTENT_TYPE, image_type(&path))], bytes).into_response())
+}
+
+async fn cover(Path(comic_id): Path<String>) -> Result<Response, OpdsError> {
+    let (_, episodes) = find_comic(&comic_id).await?;
+    page_response(&comic_id, episodes[0].ep_order, 0).await
+}
+
+/// Page Streaming Extension 的页码从 0 开始。
+async fn page(
+    Path((comic_id, ep_order, page)): Path<(String, i32, usize)>,
+) -> Result<Response, OpdsError> {
+    find_episode(&comic_id, ep_order).await?;
+    page_response(&comic_id, ep_order, page).await
+}
+
+async fn cbz(Path((comic_id, ep_order)): Path<(String, i32)>) -> Result<Response, OpdsError> {
+    let (info, episode) = find_episode(&comic_id, ep_order).await?;
+    let pages = download_episode_pages(&comic_id, ep_order)?;
+    if pages.is_empty() {
+        return Err(OpdsError::NotFound);
+    }
+
+    let bytes = tokio::task::spawn_blocking(move || build_cbz(&info, &episode, &pages))
+        .await
+        .map_err(|e| CustomError {
+            error_code: CustomErrorType::UnKnownError,
+            error_message: format!("Failed to build cbz: {}", e),
+        })??;
+    Ok((
+        [
+            (header::CONTENT_TYPE, CBZ_TYPE.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}-{}.cbz\"", comic_id, ep_order),
+            ),
+        ],
+        bytes,
+    )
+        .into_response())
+}
+
+fn router(state: ServerState) -> Router {
+    let facet_routes = [Facet::Category, Facet::Tag, Facet::Author]
+        .into_iter()
+        .fold(Router::new(), |router, facet| {
+            let (list_path, item_path, _) = facet_paths(facet);
+            router
+                .route(
+                    &format!("{}/{}", CATALOG_PATH, list_path),
+                    get(move || facet_feed(facet)),
+                )
+                .route(
+                    &format!("{}/{}", CATALOG_PATH, item_path),
+                    get(move |Query(query): Query<FacetQuery>| facet_comics_feed(facet, query)),
+                )
+        });
+
+    Router::new()
+        .route(CATALOG_PATH, get(root_feed))
+        .route(&format!("{}/opensearch.xml", CATALOG_PATH), get(opensearch))
+        .route(&format!("{}/search", CATALOG_PATH), get(search_feed))
+        .route(&format!("{}/comics", CATALOG_PATH), get(all_comics))
+        .route(
+            &format!("{}/comics/{{comic_id}}", CATALOG_PATH),
+            get(episodes_feed),
+        )
+        .route(
+            &format!("{}/comics/{{comic_id}}/cover", CATALOG_PATH),
+            get(cover),
+        )
+        .route(
+            &format!("{}/comics/{{comic_id}}/{{ep_order}}/cbz", CATALOG_PATH),
+            get(cbz),
+        )
+        .route(
+            &format!(
+                "{}/comics/{{comic_id}}/{{ep_order}}/pages/{{page}}",
+                CATALOG_PATH
+            ),
+            get(page),
+        )
+        .merge(facet_routes)
+        .layer(middleware::from_fn_with_state(state, basic_auth))
+}
+
+fn stop_server() -> Result<bool, CustomError> {
+    match OPDS_SERVER
+        .lock()
+        .map_err(|_| CustomError {
+            error_code: CustomErrorType::LockError,
+            error_message: "Failed to acquire lock on OPDS_SERVER".to_string(),
+        })?
+        .take()
+    {
+        Some(server) => {
+            server.handle.abort();
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// 启动 OPDS 目录服务，已在运行时先停止旧的服务。
+///
+/// # 返回
+/// - `Ok(OpdsServerInfo)`：实际监听的地址和目录路径。
+///
+/// # 错误
+/// - 当只设置了用户名或密码之一、监听非本机地址却没有设置认证，或端口无法监听时，返回参数错误。
+#[frb(ignore)]
+pub async fn picacg_opds_start(settings: OpdsSettings) -> Result<OpdsServerInfo, CustomError> {
+    let authorization = settings.authorization()?;
+    stop_server()?;
+
+    let listener = tokio::net::TcpListener::bind((settings.host.as_str(), settings.port))
+        .await
+        .map_err(|e| CustomError {
+            error_code: CustomErrorType::ParameterError,
+            error_message: format!("Failed to bind {}:{}: {}", settings.host, settings.port, e),
+        })?;
+    let address = listener.local_addr().map_err(|e| CustomError {
+        error_code: CustomErrorType::UnKnownError,
+        error_message: format!("Failed to get local address: {}", e),
+    })?;
+
+    let info = OpdsServerInfo {
+        address: address.to_string(),
+        catalog_path: CATALOG_PATH.to_string(),
+        auth: authorization.is_some(),
+    };
+    let router = router(ServerState { authorization });
+    let task = tokio::spawn(async move {
+        let _ = axum::serve(listener, router).await;
+    });
+
+    *OPDS_SERVER.lock().map_err(|_| CustomError {
+        error_code: CustomErrorType::LockError,
+        error_message: "Failed to acquire lock on OPDS_SERVER".to_string(),
+    })? = Some(OpdsServer {
+        info: info.clone(),
+        handle: task.abort_handle(),
+    });
+
+    Ok(info)
+}
+
+/// 停止 OPDS 目录服务。
+///
+/// # 返回
+/// - `Ok(true)`：已停止正在运行的服务。
+/// - `Ok(false)`：服务没有运行。
+#[frb(ignore)]
+pub fn picacg_opds_stop() -> Result<bool, CustomError> {
+    stop_server()
+}
+
+/// 正在运行的 OPDS 目录服务，没有运行时为 `None`。
+#[frb(ignore)]
+pub fn picacg_opds_status() -> Result<Option<OpdsServerInfo>, CustomError> {
+    Ok(OPDS_SERVER
+        .lock()
+        .map_err(|_| CustomError {
+            error_code: CustomErrorType::LockError,
+            error_message: "Failed to acquire lock on OPDS_SERVER".to_string(),
+        })?
+        .as_ref()
+        .map(|server| server.info.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::{
+        opds::{picacg_opds_start, picacg_opds_status, picacg_opds_stop, OpdsSettings},
+        storage::{
+            database::open_test_database,
+            download_data::{
+                download_episode_dir, picacg_download_delete, picacg_download_save_comic,
+                picacg_download_save_episode, DownloadStatus,
+            },
+        },
+        test_support::comic_info_fixture,
+    };
+
+    #[test]
+    fn test_opds_settings() {
+        let settings = |username: Option<&str>, password: Option<&str>| OpdsSettings {
+            username: username.map(str::to_string),
+            password: password.map(str::to_string),
+            ..OpdsSettings::default()
+        };
+
+        assert_eq!(settings(None, None).authorization().unwrap(), None);
+        assert_eq!(settings(Some(""), Some("")).authorization().unwrap(), None);
+        assert_eq!(
+            settings(Some("user"), Some("pass"))
+                .authorization()
+                .unwrap(),
+            Some("Basic dXNlcjpwYXNz".to_string())
+        );
+        assert!(settings(Some("user"), None).authorization().is_err());
+        assert!(settings(Some("a:b"), Some("pass")).authorization().is_err());
+
+        let public = |username: Option<&str>, password: Option<&str>| OpdsSettings {
+            host: "0.0.0.0".to_string(),
+            ..settings(username, password)
+        };
+        assert!(public(None, None).authorization().is_err());
+        assert!(public(Some(""), Some("")).authorization().is_err());
+        assert!(public(Some("user"), Some("pass"))
+            .authorization()
+            .unwrap()
+            .is_some());
+        for host in ["localhost", "::1", "127.0.0.2"] {
+            let local = OpdsSettings {
+                host: host.to_string(),
+                ..settings(None, None)
+            };
+            assert_eq!(local.authorization().unwrap(), None);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_opds_server() {
+        open_test_database().await;
+        let mut info = comic_info_fixture("opds");
+        info.tags = vec!["全彩".to_string()];
+        picacg_download_save_comic(info).await.unwrap();
+        for ep_order in [1, 2] {
+            picacg_download_save_episode(
+                "opds".to_string(),
+                ep_order,
+                format!("第{}话", ep_order),
+                DownloadStatus::Completed,
+                2,
+                2,
+            )
+            .await
+            .unwrap();
+            let dir = download_episode_dir("opds", ep_order).unwrap();
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("0001.png"), b"first").unwrap();
+            std::fs::write(dir.join("0002.jpg"), b"second").unwrap();
+        }
+
+        let server = picacg_opds_start(OpdsSettings {
+            host: "127.0.0.1".to_string(),
+            port: 0,
+            username: Some("user".to_string()),
+            password: Some("pass".to_string()),
+        })
+        .await
+        .unwrap();
+        assert!(server.auth);
+        assert_eq!(picacg_opds_status().unwrap(), Some(server.clone()));
+
+        let base = format!("http://{}{}", server.address, server.catalog_path);
+        let client = reqwest::Client::builder().no_proxy().build().unwrap();
+        let get = |path: &str| {
+            client
+                .get(format!("{}{}", base, path))
+                .basic_auth("user", Some("pass"))
+                .send()
+        };
+
+        let response = client.get(&base).send().await.unwrap();
+        assert_eq!(response.status(), 401);
+        assert!(response.headers().contains_key("www-authenticate"));
+
+        let root = get("").await.unwrap().text().await.unwrap();
+        assert!(root.contains("href=\"/opds/tags\""));
+
+        let tags = get("/tags").await.unwrap().text().await.unwrap();
+        assert!(tags.contains("href=\"/opds/tag?name=%E5%85%A8%E5%BD%A9\""));
+
+        let tagged = get("/tag?name=全彩").await.unwrap().text().await.unwrap();
+        assert!(tagged.contains("<id>urn:picacg:comic:opds</id>"));
+        assert!(tagged.contains("href=\"/opds/comics/opds\""));
+
+        let episodes = get("/comics/opds").await.unwrap().text().await.unwrap();
+        assert!(episodes.contains("href=\"/opds/comics/opds/2/cbz\""));
+        assert!(episodes.contains("href=\"/opds/comics/opds/1/pages/{pageNumber}\""));
+        assert!(episodes.contains("pse:count=\"2\""));
+        assert!(episodes.contains("href=\"/opds/comics/opds/cover\" type=\"image/png\""));
+        assert!(episodes.contains("type=\"image/*\" title=\"第1话\" pse:count=\"2\""));
+
+        let page = get("/comics/opds/1/pages/1").await.unwrap();
+        assert_eq!(page.headers()["content-type"], "image/jpeg");
+        assert_eq!(page.bytes().await.unwrap().as_ref(), b"second");
+        assert_eq!(get("/comics/opds/1/pages/2").await.unwrap().status(), 404);
+        assert_eq!(get("/comics/opds/3/cbz").await.unwrap().status(), 404);
+
+        let cover = get("/comics/opds/cover").await.unwrap();
+        assert_eq!(cover.headers()["content-type"], "image/png");
+
+        let cbz = get("/comics/opds/1/cbz").await.unwrap();
+        assert_eq!(cbz.status(), 200);
+        assert_eq!(
+            cbz.headers()["content-type"],
+            "application/vnd.comicbook+zip"
+        );
+        assert!(cbz.bytes().await.unwrap().starts_with(b"PK"));
+
+        let search = get("/search?q=title").await.unwrap().text().await.unwrap();
+        assert!(search.contains("<id>urn:picacg:comic:opds</id>"));
+
+        assert!(picacg_opds_stop().unwrap());
+        assert!(!picacg_opds_stop().unwrap());
+        assert_eq!(picacg_opds_status().unwrap(), None);
+        picacg_download_delete("opds".to_string()).await.unwrap();
+        std::fs::remove_dir_all(download_episode_dir("opds", 1).unwrap().parent().unwrap())
+            .unwrap();
+    }
+}
@@ -1,5 +1,11 @@
 use crate::api::{
     error::custom_error::{CustomError, CustomErrorType},
+    reqs::search::record_search_in_background,
+    storage::{
+        blocklist_data::{blocklist_matcher, Blockable, BlocklistMatcher},
+        comic_index_data::index_in_background,
+        search_data::{record_search_terms, replace_search_terms, SearchTermKind},
+    },
     types::{
         action_entity::ActionEntity,
         category_entity::CategoryEntity,
@@ -8,6 +14,8 @@ use crate::api::{
         comic_ep_entity::ComicEpEntity,
         comic_ep_picture_entity::ComicEpPictureEntity,
         comic_info_entity::ComicInfoEntity,
+        comic_query::ComicQuery,
+        comic_random_entity::ComicRandomEntity,
         comic_search_entity::ComicSearchEntity,
         init_entity::InitEntity,
         page_data::{
@@ -17,22 +25,27 @@ use crate::api::{
         sort::Sort,
     },
     utils::{
+        chinese::chinese_variants,
         client::{picacg_request, HttpExpectBody},
+        paginator::{fetch_all, forward_pages, page_stream, MAX_CONCURRENT_PAGES},
         parse_json::parse_json_from_text,
     },
 };
+use crate::frb_generated::StreamSink;
 use flutter_rust_bridge::frb;
+use futures::future::try_join_all;
+use std::{collections::HashSet, future::Future};
 
-/// 获取随机漫画列表。
-///
-/// 该函数会向 `/comics/random` 接口发起 GET 请求，
-/// 并尝试将返回的 JSON 数据解析为 `Vec<ComicEntity>`。
-///
-/// # 返回
-/// - `Ok(Vec<ComicEntity>)`：请求成功并解析成功时返回漫画实体列表。
-/// - `Err(CustomError)`：请求失败或解析失败时返回错误信息。
-#[frb]
-pub async fn picacg_comic_random() -> Result<Vec<ComicEntity>, CustomError> {
+/// 屏蔽后数量不足时，最多额外请求的页数。
+const MAX_FILL_PAGES: i32 = 3;
+
+/// 记录列表中出现的标签，用于搜索建议。记录失败不影响列表的返回。
+async fn record_seen_tags<'a>(tags: impl Iterator<Item = &'a String>) {
+    let _ = record_search_terms(SearchTermKind::Tag, tags.cloned().collect()).await;
+}
+
+/// 请求随机漫画列表，不经过屏蔽列表过滤。
+async fn random_comics() -> Result<Vec<ComicEntity>, CustomError> {
     let response = picacg_request(
         "GET",
         "/comics/random",
@@ -56,66 +69,78 @@ pub async fn picacg_comic_random() -> Result<Vec<ComicEntity>, CustomError> {
         },
         "random api result expected text response".to_string(),
     )
+    .inspect(|comics: &Vec<ComicEntity>| index_in_background(comics))
 }
 
-/// 获取漫画分页列表。
-///
-/// 该函数会向 `/comics` 接口发起 GET 请求，根据传入的筛选参数（如分类、标签、作者、汉化组、排序方式和页码）
-/// 构建查询字符串，并尝试将返回的 JSON 数据解析为 `ComicPageData`。
+/// 获取随机漫画列表。
 ///
-/// # 参数
-/// - `category`: 可选，漫画分类
-/// - `tag`: 可选，漫画标签
-/// - `creator_id`: 可选，作者 ID
-/// - `chinese_team`: 可选，汉化组
-/// - `sort`: 排序方式（`Sort` 枚举）
-/// - `page`: 页码（从 1 开始）
+/// 该函数会向 `/comics/random` 接口发起 GET 请求，
+/// 并尝试将返回的 JSON 数据解析为 `Vec<ComicEntity>`。
+/// 结果会按屏蔽列表过滤，开启补足时会再次请求随机列表，直到数量不少于第一次请求的数量。
 ///
 /// # 返回
-/// - `Ok(ComicPageData)`：请求成功并解析成功时返回分页数据
-/// - `Err(CustomError)`：请求失败或解析失败时返回错误信息
+/// - `Ok(ComicRandomEntity)`：请求成功并解析成功时返回漫画实体列表和隐藏的数量。
+/// - `Err(CustomError)`：请求失败或解析失败时返回错误信息。
 #[frb]
-pub async fn picacg_comic_page(
-    category: Option<String>,
-    tag: Option<String>,
-    creator_id: Option<String>,
-    chinese_team: Option<String>,
-    sort: Sort,
-    page: i32,
-) -> Result<ComicPageData, CustomError> {
-    let mut query_params = Vec::new();
-    if let Some(category) = category {
-        query_params.push(("c".to_string(), category));
-    }
+pub async fn picacg_comic_random() -> Result<ComicRandomEntity, CustomError> {
+    let matcher = blocklist_matcher().await;
 
-    if let Some(tag) = tag {
-        query_params.push(("t".to_string(), tag));
-    }
+    let mut comics = random_comics().await?;
+    let limit = comics.len();
+    let mut hidden = matcher.retain(&mut comics);
 
-    if let Some(creator_id) = creator_id {
-        query_params.push(("ca".to_string(), creator_id));
+    let mut fill_pages = 0;
+    while matcher.fill_page() && comics.len() < limit && fill_pages < MAX_FILL_PAGES {
+        let mut more = random_comics().await?;
+        hidden += matcher.retain(&mut more);
+        more.retain(|comic| !comics.iter().any(|c| c.id == comic.id));
+        comics.extend(more);
+        fill_pages += 1;
     }
 
-    if let Some(chinese_team) = chinese_team {
-        query_params.push(("ct".to_string(), chinese_team));
-    }
+    Ok(ComicRandomEntity { comics, hidden })
+}
 
-    query_params.push(("s".to_string(), sort.as_str().to_string()));
-    query_params.push(("page".to_string(), page.to_string()));
+/// 按屏蔽列表过滤分页数据。
+///
+/// 开启补足时继续请求后续页面，直到数量达到 `limit`、没有更多页面或额外请求了
+/// `MAX_FILL_PAGES` 页，此时 `page` 为最后一次请求的页码。返回过滤后的分页数据和隐藏的数量。
+async fn filter_page_data<T, F, Fut>(
+    matcher: &BlocklistMatcher,
+    page: i32,
+    fetch: F,
+) -> Result<(PageData<T>, i32), CustomError>
+where
+    T: Blockable,
+    F: Fn(i32) -> Fut,
+    Fut: Future<Output = Result<PageData<T>, CustomError>>,
+{
+    let mut page_data = fetch(page).await?;
+    let mut hidden = matcher.retain(&mut page_data.docs);
 
-    let mut url = String::new();
-    url.push_str("/comics");
-    if !query_params.is_empty() {
-        url.push('?');
-        url.push_str(
-            &query_params
-                .iter()
-                .map(|(k, v)| format!("{}={}", k, v))
-                .collect::<Vec<String>>()
-                .join("&"),
-        );
+    let mut fill_pages = 0;
+    while matcher.fill_page()
+        && (page_data.docs.len() as i32) < page_data.limit
+        && page_data.page < page_data.pages
+        && fill_pages < MAX_FILL_PAGES
+    {
+        let mut next = fetch(page_data.page + 1).await?;
+        hidden += matcher.retain(&mut next.docs);
+        page_data.docs.append(&mut next.docs);
+        page_data.total = next.total;
+        page_data.page = next.page;
+        page_data.pages = next.pages;
+        fill_pages += 1;
     }
 
+    Ok((page_data, hidden))
+}
+
+/// 请求漫画分页列表，不经过屏蔽列表过滤。
+#[frb(ignore)]
+pub async fn comic_page_data(query: &ComicQuery) -> Result<PageData<ComicEntity>, CustomError> {
+    let url = query.to_path();
+
     let response = picacg_request("GET", &url, None, None, Some(HttpExpectBody::Text))
         .await
         .map_err(|e| CustomError {
@@ -126,18 +151,54 @@ pub async fn picacg_comic_page(
     parse_json_from_text(
         response.body,
         |json| {
-            let page_data: PageData<ComicEntity> =
-                serde_json::from_value(json["data"]["comics"].clone()).map_err(|e| {
-                    CustomError {
-                        error_code: CustomErrorType::ParseJsonError,
-                        error_message: format!("Failed to parse comic page data: {}", e),
-                    }
-                })?;
-
-            Ok(ComicPageData::from(page_data))
+            serde_json::from_value(json["data"]["comics"].clone()).map_err(|e| CustomError {
+                error_code: CustomErrorType::ParseJsonError,
+                error_message: format!("Failed to parse comic page data: {}", e),
+            })
         },
         "comic page api result expected text response".to_string(),
     )
+    .inspect(|page_data: &PageData<ComicEntity>| index_in_background(&page_data.docs))
+}
+
+/// 获取漫画分页列表。
+///
+/// 该函数会向 `/comics` 接口发起 GET 请求，根据传入的筛选条件（如分类、标签、作者、汉化组、排序方式和页码）
+/// 构建 URL 编码后的查询字符串，并尝试将返回的 JSON 数据解析为 `ComicPageData`。
+/// 结果会按屏蔽列表过滤，按已屏蔽的创作者筛选时所有漫画都会被隐藏。
+///
+/// # 参数
+/// - `query`: 筛选条件（`ComicQuery`）
+///
+/// # 返回
+/// - `Ok(ComicPageData)`：请求成功并解析成功时返回分页数据
+/// - `Err(CustomError)`：请求失败或解析失败时返回错误信息
+#[frb]
+pub async fn picacg_comic_page(query: ComicQuery) -> Result<ComicPageData, CustomError> {
+    let matcher = blocklist_matcher().await;
+
+    if query
+        .creator_id
+        .as_deref()
+        .is_some_and(|creator_id| matcher.is_creator_blocked(creator_id))
+    {
+        let mut page_data = ComicPageData::from(comic_page_data(&query).await?);
+        page_data.hidden = page_data.docs.len() as i32;
+        page_data.docs.clear();
+        return Ok(page_data);
+    }
+
+    let (page_data, hidden) = filter_page_data(&matcher, query.page, |page| {
+        let query = query.clone().page(page);
+        async move { comic_page_data(&query).await }
+    })
+    .await?;
+    record_seen_tags(page_data.docs.iter().flat_map(|comic| &comic.tags)).await;
+
+    Ok(ComicPageData {
+        hidden,
+        ..ComicPageData::from(page_data)
+    })
 }
 
 /// 获取指定漫画的详细信息。
@@ -176,6 +237,7 @@ pub async fn picacg_comic_info(comic_id: String) -> Result<ComicInfoEntity, Cust
         },
         "comic info api result expected text response".to_string(),
     )
+    .inspect(|info: &ComicInfoEntity| index_in_background([info]))
 }
 
 /// 获取指定漫画的章节分页列表。
@@ -220,6 +282,38 @@ pub async fn picacg_comic_eps(comic_id: String, page: i32) -> Result<ComicEpPage
     )
 }
 
+/// 逐页获取指定漫画的章节列表，每获取一页推送一次，请求失败时推送错误并结束。
+///
+/// # 参数
+/// - `comic_id`: 漫画的唯一标识符。
+/// - `sink`: 接收章节分页数据的流。
+#[frb]
+pub async fn picacg_comic_eps_stream(
+    comic_id: String,
+    sink: StreamSink<ComicEpPageData>,
+) -> Result<(), CustomError> {
+    let pages = page_stream(1, |page| picacg_comic_eps(comic_id.clone(), page));
+    forward_pages(pages, sink).await
+}
+
+/// 获取指定漫画的全部章节。
+///
+/// 先请求第一页得到总页数，再以 `MAX_CONCURRENT_PAGES` 的并发请求其余页面，结果保持接口顺序。
+///
+/// # 参数
+/// - `comic_id`: 漫画的唯一标识符。
+///
+/// # 返回
+/// - `Ok(Vec<ComicEpEntity>)`：全部章节。
+/// - `Err(CustomError)`：任一页请求失败或解析失败时返回错误信息。
+#[frb]
+pub async fn picacg_comic_eps_all(comic_id: String) -> Result<Vec<ComicEpEntity>, CustomError> {
+    fetch_all(MAX_CONCURRENT_PAGES, |page| {
+        picacg_comic_eps(comic_id.clone(), page)
+    })
+    .await
+}
+
 /// 获取指定漫画某一章节的图片分页列表。
 ///
 /// 该函数会向 `/comics/{comic_id}/order/{ep_order}/pages?page={page}` 接口发起 GET 请求，
@@ -270,21 +364,50 @@ pub async fn picacg_comic_ep_pictures(
     )
 }
 
-/// 获取用户收藏的漫画分页列表。
+/// 逐页获取指定章节的图片列表，每获取一页推送一次，请求失败时推送错误并结束。
 ///
-/// 该函数会向 `/users/favourite` 接口发起 GET 请求，
-/// 根据传入的排序方式和页码构建查询字符串，
-/// 并尝试将返回的 JSON 数据解析为 `ComicPageData`。
+/// # 参数
+/// - `comic_id`: 漫画的唯一标识符。
+/// - `ep_order`: 章节序号。
+/// - `sink`: 接收图片分页数据的流。
+#[frb]
+pub async fn picacg_comic_ep_pictures_stream(
+    comic_id: String,
+    ep_order: i32,
+    sink: StreamSink<ComicEpPicturePageData>,
+) -> Result<(), CustomError> {
+    let pages = page_stream(1, |page| {
+        picacg_comic_ep_pictures(comic_id.clone(), ep_order, page)
+    });
+    forward_pages(pages, sink).await
+}
+
+/// 获取指定章节的全部图片，并发请求其余页面，结果保持接口顺序。
 ///
 /// # 参数
-/// - `sort`: 排序方式（`Sort` 枚举）
-/// - `page`: 页码（从 1 开始）
+/// - `comic_id`: 漫画的唯一标识符。
+/// - `ep_order`: 章节序号。
 ///
 /// # 返回
-/// - `Ok(ComicPageData)`：请求成功并解析成功时返回收藏漫画的分页数据
-/// - `Err(CustomError)`：请求失败或解析失败时返回错误信息
+/// - `Ok(Vec<ComicEpPictureEntity>)`：全部图片。
+/// - `Err(CustomError)`：任一页请求失败或解析失败时返回错误信息。
 #[frb]
-pub async fn picacg_comic_favourite(sort: Sort, page: i32) -> Result<ComicPageData, CustomError> {
+pub async fn picacg_comic_ep_pictures_all(
+    comic_id: String,
+    ep_order: i32,
+) -> Result<Vec<ComicEpPictureEntity>, CustomError> {
+    fetch_all(MAX_CONCURRENT_PAGES, |page| {
+        picacg_comic_ep_pictures(comic_id.clone(), ep_order, page)
+    })
+    .await
+}
+
+/// 请求用户收藏的漫画分页列表，不经过屏蔽列表过滤。
+#[frb(ignore)]
+pub async fn favourite_page_data(
+    sort: Sort,
+    page: i32,
+) -> Result<PageData<ComicEntity>, CustomError> {
     let response = picacg_request(
         "GET",
         &format!("/users/favourite?s={}&page={}", sort.as_str(), page),
@@ -301,18 +424,72 @@ pub async fn picacg_comic_favourite(sort: Sort, page: i32) -> Result<ComicPageDa
     parse_json_from_text(
         response.body,
         |json| {
-            let page_data: PageData<ComicEntity> =
-                serde_json::from_value(json["data"]["comics"].clone()).map_err(|e| {
-                    CustomError {
-                        error_code: CustomErrorType::ParseJsonError,
-                        error_message: format!("Failed to parse comic favourite data: {}", e),
-                    }
-                })?;
-
-            Ok(ComicPageData::from(page_data))
+            serde_json::from_value(json["data"]["comics"].clone()).map_err(|e| CustomError {
+                error_code: CustomErrorType::ParseJsonError,
+                error_message: format!("Failed to parse comic favourite data: {}", e),
+            })
         },
         "comic favourite api result expected text response".to_string(),
     )
+    .inspect(|page_data: &PageData<ComicEntity>| index_in_background(&page_data.docs))
+}
+
+/// 获取用户收藏的漫画分页列表。
+///
+/// 该函数会向 `/users/favourite` 接口发起 GET 请求，
+/// 根据传入的排序方式和页码构建查询字符串，
+/// 并尝试将返回的 JSON 数据解析为 `ComicPageData`。结果会按屏蔽列表过滤。
+///
+/// # 参数
+/// - `sort`: 排序方式（`Sort` 枚举）
+/// - `page`: 页码（从 1 开始）
+///
+/// # 返回
+/// - `Ok(ComicPageData)`：请求成功并解析成功时返回收藏漫画的分页数据
+/// - `Err(CustomError)`：请求失败或解析失败时返回错误信息
+#[frb]
+pub async fn picacg_comic_favourite(sort: Sort, page: i32) -> Result<ComicPageData, CustomError> {
+    let matcher = blocklist_matcher().await;
+    let (page_data, hidden) =
+        filter_page_data(&matcher, page, |page| favourite_page_data(sort, page)).await?;
+
+    Ok(ComicPageData {
+        hidden,
+        ..ComicPageData::from(page_data)
+    })
+}
+
+/// 逐页获取用户收藏的漫画列表，每获取一页推送一次，请求失败时推送错误并结束。
+/// 结果会按屏蔽列表过滤。
+///
+/// # 参数
+/// - `sort`: 排序方式（`Sort` 枚举）。
+/// - `sink`: 接收收藏分页数据的流。
+#[frb]
+pub async fn picacg_comic_favourite_stream(
+    sort: Sort,
+    sink: StreamSink<ComicPageData>,
+) -> Result<(), CustomError> {
+    let pages = page_stream(1, |page| picacg_comic_favourite(sort, page));
+    forward_pages(pages, sink).await
+}
+
+/// 获取用户收藏的全部漫画，并发请求其余页面，结果保持接口顺序并按屏蔽列表过滤。
+///
+/// # 参数
+/// - `sort`: 排序方式（`Sort` 枚举）。
+///
+/// # 返回
+/// - `Ok(Vec<ComicEntity>)`：全部收藏漫画。
+/// - `Err(CustomError)`：任一页请求失败或解析失败时返回错误信息。
+#[frb]
+pub async fn picacg_comic_favourite_all(sort: Sort) -> Result<Vec<ComicEntity>, CustomError> {
+    let matcher = blocklist_matcher().await;
+    let mut comics =
+        fetch_all(MAX_CONCURRENT_PAGES, |page| favourite_page_data(sort, page)).await?;
+    matcher.retain(&mut comics);
+
+    Ok(comics)
 }
 
 /// 切换指定漫画的点赞状态（Like/Unlike）。
@@ -438,6 +615,38 @@ pub async fn picacg_comic_comments(
     )
 }
 
+/// 逐页获取指定漫画的评论列表，每获取一页推送一次，请求失败时推送错误并结束。
+///
+/// # 参数
+/// - `comic_id`: 漫画的唯一标识符。
+/// - `sink`: 接收评论分页数据的流。
+#[frb]
+pub async fn picacg_comic_comments_stream(
+    comic_id: String,
+    sink: StreamSink<ComicCommentPageData>,
+) -> Result<(), CustomError> {
+    let pages = page_stream(1, |page| picacg_comic_comments(comic_id.clone(), page));
+    forward_pages(pages, sink).await
+}
+
+/// 获取指定漫画的全部评论，并发请求其余页面，结果保持接口顺序。
+///
+/// # 参数
+/// - `comic_id`: 漫画的唯一标识符。
+///
+/// # 返回
+/// - `Ok(Vec<ComicCommentEntity>)`：全部评论。
+/// - `Err(CustomError)`：任一页请求失败或解析失败时返回错误信息。
+#[frb]
+pub async fn picacg_comic_comments_all(
+    comic_id: String,
+) -> Result<Vec<ComicCommentEntity>, CustomError> {
+    fetch_all(MAX_CONCURRENT_PAGES, |page| {
+        picacg_comic_comments(comic_id.clone(), page)
+    })
+    .await
+}
+
 /// 向指定漫画发布评论。
 ///
 /// 该函数会向 `/comics/{comic_id}/comments` 接口发起 POST 请求，
@@ -512,28 +721,14 @@ pub async fn picacg_comic_post_child_comment(
     )
 }
 
-/// 使用高级搜索功能搜索漫画。
-///
-/// 该函数会向 `/comics/advanced-search` 接口发起 POST 请求，
-/// 根据传入的关键词、排序方式、页码和分类列表进行搜索，
-/// 并尝试将返回的 JSON 数据解析为 `ComicSearchPageData`。
-///
-/// # 参数
-/// - `content`: 搜索关键词。
-/// - `sort`: 排序方式（`Sort` 枚举）。
-/// - `page`: 页码（从 1 开始）。
-/// - `categories`: 分类列表。
-///
-/// # 返回
-/// - `Ok(ComicSearchPageData)`：请求成功并解析成功时返回搜索结果分页数据。
-/// - `Err(CustomError)`：请求失败或解析失败时返回错误信息。
-#[frb]
-pub async fn picacg_comic_search(
-    content: String,
+/// 请求高级搜索结果，不经过屏蔽列表过滤。
+#[frb(ignore)]
+pub async fn search_page_data(
+    content: &str,
     sort: Sort,
     page: i32,
-    categories: Vec<String>,
-) -> Result<ComicSearchPageData, CustomError> {
+    categories: &[String],
+) -> Result<PageData<ComicSearchEntity>, CustomError> {
     let response = picacg_request(
         "POST",
         &format!("/comics/advanced-search?page={}", page),
@@ -557,18 +752,133 @@ pub async fn picacg_comic_search(
     parse_json_from_text(
         response.body,
         |json| {
-            let page_data: PageData<ComicSearchEntity> =
-                serde_json::from_value(json["data"]["comics"].clone()).map_err(|e| {
-                    CustomError {
-                        error_code: CustomErrorType::ParseJsonError,
-                        error_message: format!("Failed to parse comic search data: {}", e),
-                    }
-                })?;
-
-            Ok(ComicSearchPageData::from(page_data))
+            serde_json::from_value(json["data"]["comics"].clone()).map_err(|e| CustomError {
+                error_code: CustomErrorType::ParseJsonError,
+                error_message: format!("Failed to parse comic search data: {}", e),
+            })
         },
         "comic search api result expected text response".to_string(),
     )
+    .inspect(|page_data: &PageData<ComicSearchEntity>| index_in_background(&page_data.docs))
+}
+
+/// 使用高级搜索功能搜索漫画。
+///
+/// 该函数会向 `/comics/advanced-search` 接口发起 POST 请求，
+/// 根据传入的关键词、排序方式、页码和分类列表进行搜索，
+/// 并尝试将返回的 JSON 数据解析为 `ComicSearchPageData`。结果会按屏蔽列表过滤。
+/// 请求第一页时会把关键词记入当前账号的搜索历史。
+///
+/// # 参数
+/// - `content`: 搜索关键词。
+/// - `sort`: 排序方式（`Sort` 枚举）。
+/// - `page`: 页码（从 1 开始）。
+/// - `categories`: 分类列表。
+///
+/// # 返回
+/// - `Ok(ComicSearchPageData)`：请求成功并解析成功时返回搜索结果分页数据。
+/// - `Err(CustomError)`：请求失败或解析失败时返回错误信息。
+#[frb]
+pub async fn picacg_comic_search(
+    content: String,
+    sort: Sort,
+    page: i32,
+    categories: Vec<String>,
+) -> Result<ComicSearchPageData, CustomError> {
+    let matcher = blocklist_matcher().await;
+    let (page_data, hidden) = filter_page_data(&matcher, page, |page| {
+        search_page_data(&content, sort, page, &categories)
+    })
+    .await?;
+    record_seen_tags(page_data.docs.iter().flat_map(|comic| &comic.tags)).await;
+    if page == 1 {
+        record_search_in_background(content);
+    }
+
+    Ok(ComicSearchPageData {
+        hidden,
+        ..ComicSearchPageData::from(page_data)
+    })
+}
+
+/// 交替合并多种写法的同一页搜索结果，按 ID 去重，保留第一次出现的漫画。
+///
+/// 各写法的结果数量不同，合并后的总数和页数取各写法中的最大值。
+fn merge_search_pages(
+    pages: Vec<PageData<ComicSearchEntity>>,
+    page: i32,
+) -> PageData<ComicSearchEntity> {
+    let mut merged = PageData {
+        total: pages.iter().map(|p| p.total).max().unwrap_or(0),
+        limit: pages.iter().map(|p| p.limit).max().unwrap_or(0),
+        page,
+        pages: pages.iter().map(|p| p.pages).max().unwrap_or(0),
+        docs: Vec::new(),
+    };
+
+    let mut seen = HashSet::new();
+    let mut iters = pages
+        .into_iter()
+        .map(|p| p.docs.into_iter())
+        .collect::<Vec<_>>();
+    loop {
+        let mut exhausted = true;
+        for comic in iters.iter_mut().filter_map(Iterator::next) {
+            exhausted = false;
+            if seen.insert(comic.id.clone()) {
+                merged.docs.push(comic);
+            }
+        }
+        if exhausted {
+            break;
+        }
+    }
+
+    merged
+}
+
+/// 同时搜索关键词的原文、简体和繁体写法，合并结果并按漫画 ID 去重。
+///
+/// PicACG 的标题混用简体字和繁体字，用一种写法搜索会漏掉另一种写法的漫画。
+/// 各写法的同一页并发请求，结果交替合并后按屏蔽列表过滤，不补足数量。
+/// 请求第一页时会把关键词记入当前账号的搜索历史。
+///
+/// # 参数
+/// - `content`: 搜索关键词。
+/// - `sort`: 排序方式（`Sort` 枚举）。
+/// - `page`: 页码（从 1 开始）。
+/// - `categories`: 分类列表。
+///
+/// # 返回
+/// - `Ok(ComicSearchPageData)`：合并后的搜索结果，一页的数量可能超过 `limit`。
+/// - `Err(CustomError)`：任一写法请求失败或解析失败时返回错误信息。
+#[frb]
+pub async fn picacg_comic_search_variants(
+    content: String,
+    sort: Sort,
+    page: i32,
+    categories: Vec<String>,
+) -> Result<ComicSearchPageData, CustomError> {
+    let matcher = blocklist_matcher().await;
+    let variants = chinese_variants(&content);
+    let pages = try_join_all(
+        variants
+            .iter()
+            .map(|variant| search_page_data(variant, sort, page, &categories)),
+    )
+    .await?;
+
+    let mut page_data = merge_search_pages(pages, page);
+    let hidden = matcher.retain(&mut page_data.docs);
+    record_seen_tags(page_data.docs.iter().flat_map(|comic| &comic.tags)).await;
+    if page == 1 {
+        record_search_in_background(content);
+    }
+
+    Ok(ComicSearchPageData {
+        hidden,
+        ..ComicSearchPageData::from(page_data)
+    })
 }
 
 /// 获取所有漫画分类。
@@ -588,7 +898,7 @@ pub async fn picacg_comic_category() -> Result<Vec<CategoryEntity>, CustomError>
             error_message: format!("Failed to make request: {}", e),
         })?;
 
-    parse_json_from_text(
+    let categories: Vec<CategoryEntity> = parse_json_from_text(
         response.body,
         |json| {
             serde_json::from_value(json["data"]["categories"].clone()).map_err(|e| CustomError {
@@ -597,7 +907,17 @@ pub async fn picacg_comic_category() -> Result<Vec<CategoryEntity>, CustomError>
             })
         },
         "category api result expected text response".to_string(),
-    )
+    )?;
+
+    // 网页分类是外部链接，不作为搜索建议
+    let titles = categories
+        .iter()
+        .filter(|category| !category.is_web)
+        .map(|category| category.title.clone())
+        .collect();
+    let _ = replace_search_terms(SearchTermKind::Category, titles).await;
+
+    Ok(categories)
 }
 
 /// 获取所有漫画分类的 ID 信息。
@@ -644,7 +964,7 @@ pub async fn picacg_comic_keywords() -> Result<Vec<String>, CustomError> {
             error_message: format!("Failed to make request: {}", e),
         })?;
 
-    parse_json_from_text(
+    let keywords: Vec<String> = parse_json_from_text(
         response.body,
         |json| {
             serde_json::from_value(json["data"]["keywords"].clone()).map_err(|e| CustomError {
@@ -653,22 +973,80 @@ pub async fn picacg_comic_keywords() -> Result<Vec<String>, CustomError> {
             })
         },
         "comic keywords api result expected text response".to_string(),
-    )
+    )?;
+
+    let _ = replace_search_terms(SearchTermKind::Keyword, keywords.clone()).await;
+    Ok(keywords)
 }
 
 #[cfg(test)]
 mod tests {
     use crate::api::{
         reqs::comic::{
-            picacg_comic_category, picacg_comic_comments, picacg_comic_ep_pictures,
-            picacg_comic_eps, picacg_comic_favourite, picacg_comic_info, picacg_comic_init,
-            picacg_comic_keywords, picacg_comic_page, picacg_comic_post_child_comment,
-            picacg_comic_post_comment, picacg_comic_random, picacg_comic_search,
-            picacg_comic_switch_favourite, picacg_comic_switch_like,
+            merge_search_pages, picacg_comic_category, picacg_comic_comments,
+            picacg_comic_ep_pictures, picacg_comic_eps, picacg_comic_eps_all,
+            picacg_comic_favourite, picacg_comic_favourite_all, picacg_comic_info,
+            picacg_comic_init, picacg_comic_keywords, picacg_comic_page,
+            picacg_comic_post_child_comment, picacg_comic_post_comment, picacg_comic_random,
+            picacg_comic_search, picacg_comic_search_variants, picacg_comic_switch_favourite,
+            picacg_comic_switch_like,
         },
-        types::sort::Sort,
+        test_support::comic_search_fixture,
+        types::{comic_query::ComicQuery, page_data::PageData, sort::Sort},
     };
 
+    #[tokio::test]
+    async fn test_filter_page_data() {
+        use crate::api::{
+            reqs::comic::filter_page_data,
+            storage::blocklist_data::{Blocklist, BlocklistMatcher},
+            test_support::comic_fixture,
+            types::page_data::PageData,
+        };
+
+        // 每页 4 部漫画，共 3 页，编号为奇数的漫画被屏蔽
+        let fetch = |page: i32| async move {
+            let docs = (0..4)
+                .map(|i| {
+                    let mut comic = comic_fixture(&format!("{}-{}", page, i));
+                    if i % 2 == 1 {
+                        comic.tags = vec!["blocked".to_string()];
+                    }
+                    comic
+                })
+                .collect();
+            Ok(PageData {
+                total: 12,
+                limit: 4,
+                page,
+                pages: 3,
+                docs,
+            })
+        };
+
+        let mut blocklist = Blocklist {
+            tags: vec!["blocked".to_string()],
+            ..Default::default()
+        };
+        let matcher = BlocklistMatcher::new(&blocklist).unwrap();
+        let (page_data, hidden) = filter_page_data(&matcher, 1, fetch).await.unwrap();
+        assert_eq!(page_data.docs.len(), 2);
+        assert_eq!(page_data.page, 1);
+        assert_eq!(hidden, 2);
+
+        blocklist.fill_page = true;
+        let matcher = BlocklistMatcher::new(&blocklist).unwrap();
+        let (page_data, hidden) = filter_page_data(&matcher, 1, fetch).await.unwrap();
+        assert_eq!(page_data.docs.len(), 4);
+        assert_eq!(page_data.page, 2);
+        assert_eq!(hidden, 4);
+
+        let (page_data, hidden) = filter_page_data(&matcher, 3, fetch).await.unwrap();
+        assert_eq!(page_data.docs.len(), 2);
+        assert_eq!(page_data.page, 3);
+        assert_eq!(hidden, 2);
+    }
+
     #[tokio::test]
     async fn test_picacg_comic_random() {
         let result = picacg_comic_random().await;
@@ -677,7 +1055,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_picacg_comic_page() {
-        let result = picacg_comic_page(None, None, None, None, Sort::SORT_DEFAULT, 1).await;
+        let result = picacg_comic_page(ComicQuery::new()).await;
         assert!(result.is_err());
     }
 
@@ -693,6 +1071,12 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_picacg_comic_eps_all() {
+        let result = picacg_comic_eps_all("5b6bdf4558ed442d899486b7".to_string()).await;
+        assert!(result.is_err());
+    }
+
     #[tokio::test]
     async fn test_picacg_comic_ep_pictures() {
         let result = picacg_comic_ep_pictures("5b6bdf4558ed442d899486b7".to_string(), 1, 1).await;
@@ -701,7 +1085,13 @@ mod tests {
 
     #[tokio::test]
     async fn test_picacg_comic_favourite() {
-        let result = picacg_comic_favourite(Sort::SORT_DEFAULT, 1).await;
+        let result = picacg_comic_favourite(Sort::Default, 1).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_picacg_comic_favourite_all() {
+        let result = picacg_comic_favourite_all(Sort::Default).await;
         assert!(result.is_err());
     }
 
@@ -743,10 +1133,46 @@ mod tests {
 
     #[tokio::test]
     async fn test_picacg_comic_search() {
-        let result = picacg_comic_search("test".to_string(), Sort::SORT_DEFAULT, 1, vec![]).await;
+        let result = picacg_comic_search("test".to_string(), Sort::Default, 1, vec![]).await;
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_picacg_comic_search_variants() {
+        let result =
+            picacg_comic_search_variants("進擊".to_string(), Sort::Default, 1, vec![]).await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_merge_search_pages() {
+        let page = |total: i32, pages: i32, ids: &[&str]| PageData {
+            total,
+            limit: 20,
+            page: 1,
+            pages,
+            docs: ids
+                .iter()
+                .map(|id| comic_search_fixture(id))
+                .collect::<Vec<_>>(),
+        };
+
+        let merged = merge_search_pages(
+            vec![page(3, 1, &["a", "b", "c"]), page(40, 2, &["b", "d"])],
+            1,
+        );
+        assert_eq!(
+            merged
+                .docs
+                .iter()
+                .map(|c| c.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a", "b", "d", "c"]
+        );
+        assert_eq!(merged.total, 40);
+        assert_eq!(merged.pages, 2);
+    }
+
     #[tokio::test]
     async fn test_picacg_comic_category() {
         let result = picacg_comic_category().await;
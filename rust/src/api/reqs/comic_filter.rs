@@ -0,0 +1,241 @@
+use crate::api::{
+    error::custom_error::CustomError,
+    reqs::comic::{picacg_comic_page, picacg_comic_search},
+    types::{
+        comic_filter::{ComicFilter, ComicFilterPageData, ComicSearchFilterPageData, Filterable},
+        comic_query::ComicQuery,
+        sort::Sort,
+    },
+};
+use flutter_rust_bridge::frb;
+use std::future::Future;
+
+/// 单次调用最多扫描的页数。
+const MAX_SCAN_PAGES: i32 = 20;
+
+/// 一次请求返回的数据，`page` 为实际请求到的最后一页（屏蔽后补足时可能大于请求的页码）。
+struct ScanPage<T> {
+    docs: Vec<T>,
+    page: i32,
+    pages: i32,
+    hidden: i32,
+}
+
+struct ScanResult<T> {
+    docs: Vec<T>,
+    scanned_pages: i32,
+    scanned: i32,
+    hidden: i32,
+    next_page: Option<i32>,
+}
+
+/// 从 `page` 开始逐页请求并筛选，直到匹配数量达到 `count`、扫描了 `scan_limit` 页或没有更多页面。
+///
+/// 已扫描页面中的匹配结果全部返回，所以数量可能多于 `count`。
+/// `filter` 使用了这一类来源不提供的条件时，不发起请求直接返回 `ValidationError`。
+async fn scan_pages<T, F, Fut>(
+    filter: &ComicFilter,
+    page: i32,
+    count: i32,
+    scan_limit: i32,
+    fetch: F,
+) -> Result<ScanResult<T>, CustomError>
+where
+    T: Filterable,
+    F: Fn(i32) -> Fut,
+    Fut: Future<Output = Result<ScanPage<T>, CustomError>>,
+{
+    filter.validate_for::<T>()?;
+
+    let count = count.max(1) as usize;
+    let scan_limit = scan_limit.clamp(1, MAX_SCAN_PAGES);
+
+    let mut result = ScanResult {
+        docs: Vec::new(),
+        scanned_pages: 0,
+        scanned: 0,
+        hidden: 0,
+        next_page: None,
+    };
+    let mut page = page.max(1);
+
+    loop {
+        let scan_page = fetch(page).await?;
+        result.scanned_pages += scan_page.page.max(page) - page + 1;
+        result.scanned += scan_page.docs.len() as i32;
+        result.hidden += scan_page.hidden;
+        result.docs.extend(
+            scan_page
+                .docs
+                .into_iter()
+                .filter(|comic| filter.matches(comic)),
+        );
+        result.next_page =
+            (scan_page.page < scan_page.pages).then_some(scan_page.page.max(page) + 1);
+
+        match result.next_page {
+            Some(next_page) if result.docs.len() < count && result.scanned_pages < scan_limit => {
+                page = next_page;
+            }
+            _ => return Ok(result),
+        }
+    }
+}
+
+/// 按客户端筛选条件获取漫画列表。
+///
+/// 从 `query.page` 开始自动翻页，直到找到 `count` 部满足 `filter` 的漫画、
+/// 扫描了 `scan_limit` 页（最多 `MAX_SCAN_PAGES` 页）或没有更多页面。
+/// 结果同样会按屏蔽列表过滤。
+///
+/// # 参数
+/// - `query`: 服务器筛选条件，`page` 为开始扫描的页码。
+/// - `filter`: 客户端筛选条件，漫画列表不能使用 `updated_after`。
+/// - `count`: 期望的匹配数量。
+/// - `scan_limit`: 最多扫描的页数。
+///
+/// # 返回
+/// - `Ok(ComicFilterPageData)`：匹配的漫画和下一次继续扫描的页码。
+/// - `Err(CustomError)`：筛选条件不支持时返回 `ValidationError`，请求失败或解析失败时返回错误信息。
+#[frb]
+pub async fn picacg_comic_page_filter(
+    query: ComicQuery,
+    filter: ComicFilter,
+    count: i32,
+    scan_limit: i32,
+) -> Result<ComicFilterPageData, CustomError> {
+    let result = scan_pages(&filter, query.page, count, scan_limit, |page| {
+        let query = query.clone().page(page);
+        async move {
+            let page_data = picacg_comic_page(query).await?;
+            Ok(ScanPage {
+                docs: page_data.docs,
+                page: page_data.page,
+                pages: page_data.pages,
+                hidden: page_data.hidden,
+            })
+        }
+    })
+    .await?;
+
+    Ok(ComicFilterPageData {
+        docs: result.docs,
+        scanned_pages: result.scanned_pages,
+        scanned: result.scanned,
+        hidden: result.hidden,
+        next_page: result.next_page,
+    })
+}
+
+/// 按客户端筛选条件获取搜索结果。
+///
+/// 从 `page` 开始自动翻页，直到找到 `count` 部满足 `filter` 的漫画、
+/// 扫描了 `scan_limit` 页（最多 `MAX_SCAN_PAGES` 页）或没有更多页面。
+/// 结果同样会按屏蔽列表过滤。
+///
+/// # 参数
+/// - `content`: 搜索关键词。
+/// - `sort`: 排序方式（`Sort` 枚举）。
+/// - `page`: 开始扫描的页码（从 1 开始）。
+/// - `categories`: 分类列表。
+/// - `filter`: 客户端筛选条件，搜索结果不能使用页数和章节数。
+/// - `count`: 期望的匹配数量。
+/// - `scan_limit`: 最多扫描的页数。
+///
+/// # 返回
+/// - `Ok(ComicSearchFilterPageData)`：匹配的漫画和下一次继续扫描的页码。
+/// - `Err(CustomError)`：筛选条件不支持时返回 `ValidationError`，请求失败或解析失败时返回错误信息。
+#[frb]
+pub async fn picacg_comic_search_filter(
+    content: String,
+    sort: Sort,
+    page: i32,
+    categories: Vec<String>,
+    filter: ComicFilter,
+    count: i32,
+    scan_limit: i32,
+) -> Result<ComicSearchFilterPageData, CustomError> {
+    let result = scan_pages(&filter, page, count, scan_limit, |page| {
+        let content = content.clone();
+        let categories = categories.clone();
+        async move {
+            let page_data = picacg_comic_search(content, sort, page, categories).await?;
+            Ok(ScanPage {
+                docs: page_data.docs,
+                page: page_data.page,
+                pages: page_data.pages,
+                hidden: page_data.hidden,
+            })
+        }
+    })
+    .await?;
+
+    Ok(ComicSearchFilterPageData {
+        docs: result.docs,
+        scanned_pages: result.scanned_pages,
+        scanned: result.scanned,
+        hidden: result.hidden,
+        next_page: result.next_page,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::{
+        reqs::comic_filter::{picacg_comic_page_filter, scan_pages, ScanPage},
+        test_support::comic_fixture,
+        types::{comic_filter::ComicFilter, comic_query::ComicQuery},
+    };
+
+    #[tokio::test]
+    async fn test_scan_pages() {
+        // 共 5 页，每页 4 部漫画，只有每页的第一部已完结
+        let fetch = |page: i32| async move {
+            let docs = (0..4)
+                .map(|i| {
+                    let mut comic = comic_fixture(&format!("{}-{}", page, i));
+                    comic.finished = i == 0;
+                    comic
+                })
+                .collect();
+            Ok(ScanPage {
+                docs,
+                page,
+                pages: 5,
+                hidden: 1,
+            })
+        };
+        let filter = ComicFilter::new().finished(true);
+
+        let result = scan_pages(&filter, 1, 3, 10, fetch).await.unwrap();
+        assert_eq!(
+            result
+                .docs
+                .iter()
+                .map(|c| c.id.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["1-0", "2-0", "3-0"]
+        );
+        assert_eq!(result.scanned_pages, 3);
+        assert_eq!(result.scanned, 12);
+        assert_eq!(result.hidden, 3);
+        assert_eq!(result.next_page, Some(4));
+
+        let result = scan_pages(&filter, 4, 3, 10, fetch).await.unwrap();
+        assert_eq!(result.docs.len(), 2);
+        assert_eq!(result.next_page, None);
+
+        let result = scan_pages(&filter, 1, 10, 2, fetch).await.unwrap();
+        assert_eq!(result.docs.len(), 2);
+        assert_eq!(result.next_page, Some(3));
+
+        let filter = ComicFilter::new().updated_after(chrono::Utc::now());
+        assert!(scan_pages(&filter, 1, 3, 10, fetch).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_picacg_comic_page_filter() {
+        let result = picacg_comic_page_filter(ComicQuery::new(), ComicFilter::new(), 10, 1).await;
+        assert!(result.is_err());
+    }
+}
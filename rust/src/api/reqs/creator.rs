@@ -0,0 +1,318 @@
+use crate::api::{
+    error::custom_error::{CustomError, CustomErrorType},
+    reqs::comic::{comic_page_data, picacg_comic_info, picacg_comic_page},
+    storage::{
+        blocklist_data::blocklist_matcher,
+        follow_data::{
+            picacg_followed_creators, save_follow_progress, save_followed_creator, FollowedCreator,
+        },
+    },
+    types::{
+        comic_entity::ComicEntity,
+        comic_info_entity::ComicInfoEntity,
+        comic_query::ComicQuery,
+        creator_entity::CreatorEntity,
+        creator_page_entity::{
+            CreatorFailureEntity, CreatorPageEntity, CreatorUpdateCheckEntity, CreatorUpdateEntity,
+        },
+        sort::Sort,
+    },
+};
+use chrono::{DateTime, Utc};
+use flutter_rust_bridge::frb;
+use std::{
+    collections::HashMap,
+    sync::{LazyLock, RwLock},
+};
+
+/// 检查关注更新时，每位创作者最多向前翻阅的页数。
+const MAX_UPDATE_PAGES: i32 = 5;
+
+static CREATORS: LazyLock<RwLock<HashMap<String, CreatorEntity>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// 获取创作者主页。
+///
+/// 该函数会以 `creator_id` 为筛选条件请求 `/comics` 接口获取创作者上传的漫画分页列表，
+/// 并通过其中一部漫画的详情获取创作者资料（等级、头衔、签名、头像等）。
+/// 创作者资料会缓存在内存中，翻页时不会重复请求。
+///
+/// # 参数
+/// - `creator_id`: 创作者 ID。
+/// - `sort`: 排序方式（`Sort` 枚举）。
+/// - `page`: 页码（从 1 开始）。
+///
+/// # 返回
+/// - `Ok(CreatorPageEntity)`：请求成功时返回创作者资料、上传总数及漫画分页数据。
+/// - `Err(CustomError)`：请求失败或解析失败时返回错误信息。
+#[frb]
+pub async fn picacg_creator_page(
+    creator_id: String,
+    sort: Sort,
+    page: i32,
+) -> Result<CreatorPageEntity, CustomError> {
+    if creator_id.is_empty() {
+        return Err(CustomError {
+            error_code: CustomErrorType::ParameterError,
+            error_message: "Creator id cannot be empty".to_string(),
+        });
+    }
+
+    let comics = picacg_comic_page(
+        ComicQuery::new()
+            .creator_id(creator_id.clone())
+            .sort(sort)
+            .page(page),
+    )
+    .await?;
+
+    let cached = CREATORS
+        .read()
+        .map_err(|_| CustomError {
+            error_code: CustomErrorType::LockError,
+            error_message: "Failed to acquire read lock on CREATORS".to_string(),
+        })?
+        .get(&creator_id)
+        .cloned();
+
+    let creator = match (cached, comics.docs.first()) {
+        (Some(creator), _) => Some(creator),
+        (None, Some(comic)) => {
+            let creator = picacg_comic_info(comic.id.clone()).await?.creator;
+            if creator.id == creator_id {
+                CREATORS
+                    .write()
+                    .map_err(|_| CustomError {
+                        error_code: CustomErrorType::LockError,
+                        error_message: "Failed to acquire write lock on CREATORS".to_string(),
+                    })?
+                    .insert(creator_id, creator.clone());
+                Some(creator)
+            } else {
+                None
+            }
+        }
+        (None, None) => None,
+    };
+
+    Ok(CreatorPageEntity {
+        creator,
+        total: comics.total,
+        comics,
+    })
+}
+
+/// 关注创作者。
+///
+/// 关注时会记录创作者当前最新上传的漫画及其上传时间，之后的更新检查只会返回在此之后的新上传。
+/// 已关注的创作者再次关注时会刷新其资料和检查进度。
+///
+/// # 参数
+/// - `creator_id`: 创作者 ID。
+///
+/// # 返回
+/// - `Ok(FollowedCreator)`：关注成功时返回关注记录。
+/// - `Err(CustomError)`：创作者不存在、请求失败或写入失败时返回错误信息。
+#[frb]
+pub async fn picacg_creator_follow(creator_id: String) -> Result<FollowedCreator, CustomError> {
+    let page = picacg_creator_page(creator_id, Sort::TimeNewest, 1).await?;
+    let creator = page.creator.ok_or_else(|| CustomError {
+        error_code: CustomErrorType::ParameterError,
+        error_message: "Creator not found".to_string(),
+    })?;
+
+    let latest = match page.comics.docs.first() {
+        Some(comic) => Some(picacg_comic_info(comic.id.clone()).await?),
+        None => None,
+    };
+
+    let now = Utc::now();
+    let followed = FollowedCreator {
+        id: creator.id,
+        name: creator.name,
+        avatar: creator.avatar,
+        followed_at: now,
+        last_checked_at: Some(now),
+        last_comic_id: latest
+            .as_ref()
+            .map_or_else(String::new, |info| info.id.clone()),
+        last_uploaded_at: latest.as_ref().map(ComicInfoEntity::uploaded_at),
+    };
+
+    save_followed_creator(followed.clone()).await?;
+
+    Ok(followed)
+}
+
+/// 一位创作者的检查结果。
+struct CreatorUploads {
+    /// 新上传，按上传时间从新到旧排列。
+    comics: Vec<ComicEntity>,
+    /// 当前最新上传的漫画 ID 和上传时间，与检查进度相同时为 `None`。
+    latest: Option<(String, DateTime<Utc>)>,
+}
+
+/// 上传时间晚于上次检查时的最新上传时视为新上传，没有记录上传时间时都视为新上传。
+fn is_new_upload(creator: &FollowedCreator, uploaded_at: DateTime<Utc>) -> bool {
+    creator
+        .last_uploaded_at
+        .is_none_or(|last_uploaded_at| uploaded_at > last_uploaded_at)
+}
+
+async fn comic_uploaded_at(comic_id: &str) -> Result<DateTime<Utc>, CustomError> {
+    Ok(picacg_comic_info(comic_id.to_string()).await?.uploaded_at())
+}
+
+/// 获取创作者在上次检查之后的新上传。
+///
+/// 遇到 `last_comic_id` 时直接停止；该漫画被删除时逐部获取上传时间，遇到不晚于
+/// `last_uploaded_at` 的漫画时停止，避免把旧的上传当作新上传。
+async fn creator_new_uploads(creator: &FollowedCreator) -> Result<CreatorUploads, CustomError> {
+    let mut uploads = CreatorUploads {
+        comics: Vec::new(),
+        latest: None,
+    };
+    let mut page = 1;
+
+    loop {
+        // 不经过屏蔽列表过滤，避免上次检查时的最新漫画被屏蔽后无法定位检查进度
+        let page_data = comic_page_data(
+            &ComicQuery::new()
+                .creator_id(creator.id.clone())
+                .sort(Sort::TimeNewest)
+                .page(page),
+        )
+        .await?;
+
+        for comic in page_data.docs {
+            if comic.id == creator.last_comic_id {
+                // 旧版本的关注记录没有上传时间，补充记录
+                if creator.last_uploaded_at.is_none() && uploads.latest.is_none() {
+                    uploads.latest = Some((comic.id.clone(), comic_uploaded_at(&comic.id).await?));
+                }
+                return Ok(uploads);
+            }
+
+            if creator.last_uploaded_at.is_some() || uploads.latest.is_none() {
+                let uploaded_at = comic_uploaded_at(&comic.id).await?;
+                if uploads.latest.is_none() {
+                    uploads.latest = Some((comic.id.clone(), uploaded_at));
+                }
+                if !is_new_upload(creator, uploaded_at) {
+                    return Ok(uploads);
+                }
+            }
+            uploads.comics.push(comic);
+        }
+
+        if page >= page_data.pages || page >= MAX_UPDATE_PAGES {
+            return Ok(uploads);
+        }
+        page += 1;
+    }
+}
+
+/// 检查已关注创作者的新上传。
+///
+/// 对每位已关注的创作者按上传时间从新到旧翻阅其漫画列表，直到遇到上次检查时的最新漫画，
+/// 或上传时间不晚于该漫画的漫画。每位创作者最多翻阅 `MAX_UPDATE_PAGES` 页。
+/// 单位创作者检查失败不会中断其他创作者的检查，也不会更新其检查进度；
+/// 检查成功的创作者在全部检查完成后一起保存检查进度。
+///
+/// # 返回
+/// - `Ok(CreatorUpdateCheckEntity)`：有新上传的创作者及检查失败的创作者。
+/// - `Err(CustomError)`：读取关注列表或保存检查进度失败时返回错误信息。
+#[frb]
+pub async fn picacg_creator_follow_updates() -> Result<CreatorUpdateCheckEntity, CustomError> {
+    let matcher = blocklist_matcher().await;
+    let creators = picacg_followed_creators().await?;
+    let mut checked = Vec::new();
+    let mut updates = Vec::new();
+    let mut failures = Vec::new();
+
+    for mut creator in creators.clone() {
+        let uploads = match creator_new_uploads(&creator).await {
+            Ok(uploads) => uploads,
+            Err(e) => {
+                failures.push(CreatorFailureEntity {
+                    creator_id: creator.id,
+                    name: creator.name,
+                    error_message: e.error_message,
+                });
+                continue;
+            }
+        };
+
+        creator.last_checked_at = Some(Utc::now());
+        if let Some((comic_id, uploaded_at)) = uploads.latest {
+            creator.last_comic_id = comic_id;
+            creator.last_uploaded_at = Some(uploaded_at);
+        }
+        checked.push(creator.clone());
+
+        let mut comics = uploads.comics;
+        matcher.retain(&mut comics);
+        if !comics.is_empty() && !matcher.is_creator_blocked(&creator.id) {
+            updates.push(CreatorUpdateEntity { creator, comics });
+        }
+    }
+
+    save_follow_progress(checked).await?;
+
+    Ok(CreatorUpdateCheckEntity {
+        updates,
+        failures,
+        checked: creators.len() as i32,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::{
+        error::custom_error::CustomErrorType,
+        reqs::creator::{is_new_upload, picacg_creator_follow, picacg_creator_page},
+        storage::follow_data::FollowedCreator,
+        types::{image_entity::avatar_default, sort::Sort},
+    };
+    use chrono::{Duration, Utc};
+
+    #[test]
+    fn test_is_new_upload() {
+        let now = Utc::now();
+        let mut creator = FollowedCreator {
+            id: "creator".to_string(),
+            name: String::new(),
+            avatar: avatar_default(),
+            followed_at: now,
+            last_checked_at: None,
+            last_comic_id: "deleted".to_string(),
+            last_uploaded_at: Some(now),
+        };
+
+        assert!(is_new_upload(&creator, now + Duration::minutes(1)));
+        assert!(!is_new_upload(&creator, now));
+        assert!(!is_new_upload(&creator, now - Duration::days(1)));
+
+        creator.last_uploaded_at = None;
+        assert!(is_new_upload(&creator, now - Duration::days(1)));
+    }
+
+    #[tokio::test]
+    async fn test_picacg_creator_page() {
+        let result = picacg_creator_page(String::new(), Sort::Default, 1).await;
+        assert!(matches!(
+            result.map_err(|e| e.error_code),
+            Err(CustomErrorType::ParameterError)
+        ));
+
+        let result =
+            picacg_creator_page("58fd7d2d8e1b0b4c0c6e7c5f".to_string(), Sort::Default, 1).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_picacg_creator_follow() {
+        let result = picacg_creator_follow("58fd7d2d8e1b0b4c0c6e7c5f".to_string()).await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,233 @@
+use crate::api::{
+    error::custom_error::{CustomError, CustomErrorType},
+    reqs::comic::{picacg_comic_ep_pictures_all, picacg_comic_eps_all, picacg_comic_info},
+    storage::{
+        config::picacg_config_net_data,
+        download_data::{
+            download_episode_dir, picacg_download_save_comic, picacg_download_save_episode,
+            DownloadEpisode, DownloadStatus,
+        },
+        image_cache::{download_image, image_url},
+    },
+    types::image_entity::ImageEntity,
+    utils::file::write_file_atomic,
+};
+use crate::frb_generated::StreamSink;
+use flutter_rust_bridge::frb;
+use futures::{stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// 同时下载的图片数。
+const MAX_CONCURRENT_DOWNLOADS: usize = 4;
+
+/// 章节的下载进度，每下载完一页推送一次。
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct DownloadProgress {
+    pub comic_id: String,
+    pub ep_order: i32,
+    pub ep_title: String,
+    pub status: DownloadStatus,
+    pub downloaded_pages: i32,
+    pub total_pages: i32,
+}
+
+/// 图片的文件名：从 1 开始的四位页码加原文件的扩展名，没有扩展名时使用 `jpg`。
+fn page_file_name(index: usize, media: &ImageEntity) -> String {
+    let extension = [&media.original_name, &media.path]
+        .into_iter()
+        .filter_map(|name| Path::new(name).extension()?.to_str())
+        .find(|extension| extension.chars().all(|c| c.is_ascii_alphanumeric()))
+        .map(str::to_lowercase)
+        .unwrap_or_else(|| "jpg".to_string());
+    format!("{:04}.{}", index + 1, extension)
+}
+
+/// 下载一页图片，文件已存在时跳过，中断后重新下载可以继续。
+async fn download_page(dir: PathBuf, file_name: String, url: String) -> Result<(), CustomError> {
+    let path = dir.join(&file_name);
+    if tokio::fs::try_exists(&path).await.unwrap_or(false) {
+        return Ok(());
+    }
+
+    let bytes = download_image(&url).await?;
+    tokio::task::spawn_blocking(move || {
+        write_file_atomic(&path, &dir.join(format!("{}.tmp", file_name)), &bytes)
+    })
+    .await
+    .map_err(|e| CustomError {
+        error_code: CustomErrorType::UnKnownError,
+        error_message: format!("Download task failed: {}", e),
+    })?
+}
+
+/// 下载一个章节的所有图片，并保存章节的下载进度。
+async fn download_episode<F>(
+    comic_id: &str,
+    ep_order: i32,
+    ep_title: &str,
+    on_progress: &F,
+) -> Result<DownloadEpisode, CustomError>
+where
+    F: Fn(DownloadProgress),
+{
+    let pictures = picacg_comic_ep_pictures_all(comic_id.to_string(), ep_order).await?;
+    let total_pages = pictures.len() as i32;
+    let dir = download_episode_dir(comic_id, ep_order)?;
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .map_err(|e| CustomError {
+            error_code: CustomErrorType::FileWriteError,
+            error_message: format!("Failed to create download directory: {}", e),
+        })?;
+
+    let save = |status: DownloadStatus, downloaded_pages: i32| {
+        on_progress(DownloadProgress {
+            comic_id: comic_id.to_string(),
+            ep_order,
+            ep_title: ep_title.to_string(),
+            status,
+            downloaded_pages,
+            total_pages,
+        });
+        picacg_download_save_episode(
+            comic_id.to_string(),
+            ep_order,
+            ep_title.to_string(),
+            status,
+            downloaded_pages,
+            total_pages,
+        )
+    };
+
+    let image_server = picacg_config_net_data()?.image_server;
+    save(DownloadStatus::Downloading, 0).await?;
+    let mut downloads = stream::iter(pictures.iter().enumerate().map(|(index, picture)| {
+        download_page(
+            dir.clone(),
+            page_file_name(index, &picture.media),
+            image_url(&image_server, &picture.media),
+        )
+    }))
+    .buffer_unordered(MAX_CONCURRENT_DOWNLOADS);
+
+    let mut downloaded_pages = 0;
+    while let Some(result) = downloads.next().await {
+        if let Err(e) = result {
+            drop(downloads);
+            save(DownloadStatus::Failed, downloaded_pages).await?;
+            return Err(e);
+        }
+        downloaded_pages += 1;
+        if downloaded_pages < total_pages {
+            on_progress(DownloadProgress {
+                comic_id: comic_id.to_string(),
+                ep_order,
+                ep_title: ep_title.to_string(),
+                status: DownloadStatus::Downloading,
+                downloaded_pages,
+                total_pages,
+            });
+        }
+    }
+
+    save(DownloadStatus::Completed, total_pages).await
+}
+
+/// 下载漫画的章节到存储目录的 `downloads` 下，并保存漫画详情和各章节的下载进度。
+///
+/// 章节按序号依次下载，每个章节同时下载 `MAX_CONCURRENT_DOWNLOADS` 张图片，
+/// 已下载的图片会跳过，失败后重新调用即可继续。`ep_orders` 为空时下载全部章节。
+#[frb(ignore)]
+pub async fn download_comic<F>(
+    comic_id: String,
+    ep_orders: Vec<i32>,
+    on_progress: F,
+) -> Result<Vec<DownloadEpisode>, CustomError>
+where
+    F: Fn(DownloadProgress),
+{
+    let info = picacg_comic_info(comic_id.clone()).await?;
+    picacg_download_save_comic(info).await?;
+
+    let mut eps = picacg_comic_eps_all(comic_id.clone()).await?;
+    if !ep_orders.is_empty() {
+        if let Some(missing) = ep_orders
+            .iter()
+            .find(|order| !eps.iter().any(|ep| ep.order == **order))
+        {
+            return Err(CustomError {
+                error_code: CustomErrorType::ParameterError,
+                error_message: format!("Episode not found: {}", missing),
+            });
+        }
+        eps.retain(|ep| ep_orders.contains(&ep.order));
+    }
+    eps.sort_by_key(|ep| ep.order);
+
+    let mut episodes = Vec::new();
+    for ep in eps {
+        episodes.push(download_episode(&comic_id, ep.order, &ep.title, &on_progress).await?);
+    }
+    Ok(episodes)
+}
+
+/// 下载漫画的章节，下载进度推送给 `sink`。
+///
+/// 图片保存在存储目录的 `downloads/{comic_id}/{ep_order}` 下，漫画详情和章节进度同时写入下载记录。
+/// 已下载的图片会跳过，失败后重新调用即可继续。
+///
+/// # 参数
+/// - `comic_id`: 漫画 ID。
+/// - `ep_orders`: 要下载的章节序号，为空时下载全部章节。
+/// - `sink`: 接收下载进度的流。
+///
+/// # 返回
+/// - `Ok(Vec<DownloadEpisode>)`：所有章节的下载记录。
+/// - `Err(CustomError)`：章节不存在、请求失败或写入失败时返回错误信息，已下载的部分会保留。
+#[frb]
+pub async fn picacg_download_start(
+    comic_id: String,
+    ep_orders: Vec<i32>,
+    sink: StreamSink<DownloadProgress>,
+) -> Result<Vec<DownloadEpisode>, CustomError> {
+    download_comic(comic_id, ep_orders, |progress| {
+        let _ = sink.add(progress);
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::{
+        reqs::download::{download_comic, page_file_name},
+        types::image_entity::ImageEntity,
+    };
+
+    #[test]
+    fn test_page_file_name() {
+        let image = |original_name: &str, path: &str| ImageEntity {
+            file_server: String::new(),
+            original_name: original_name.to_string(),
+            path: path.to_string(),
+        };
+
+        assert_eq!(
+            page_file_name(0, &image("01.PNG", "tobeimg/x.png")),
+            "0001.png"
+        );
+        assert_eq!(
+            page_file_name(11, &image("cover", "tobeimg/x.webp")),
+            "0012.webp"
+        );
+        assert_eq!(page_file_name(2, &image("", "x")), "0003.jpg");
+    }
+
+    #[tokio::test]
+    async fn test_download_comic() {
+        let result = download_comic("download_comic".to_string(), vec![], |_| {}).await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,130 @@
+use crate::api::{
+    error::custom_error::{CustomError, CustomErrorType},
+    reqs::comic::{favourite_page_data, picacg_comic_info, picacg_comic_switch_favourite},
+    storage::{
+        favourite_data::{
+            picacg_favourite_snapshot, sync_favourite_snapshots, FavouriteSyncResult,
+        },
+        folder_data::{add_folder_comic, picacg_folder_sync_favourite, FolderComic},
+    },
+    types::{comic_entity::ComicEntity, comic_info_entity::ComicInfoEntity, sort::Sort},
+    utils::paginator::{fetch_all, MAX_CONCURRENT_PAGES},
+};
+use flutter_rust_bridge::frb;
+
+/// 同步收藏到本地镜像。
+///
+/// 该函数会按服务器的默认顺序获取全部收藏（不经过屏蔽列表过滤），替换本地镜像，
+/// 并返回与上次同步相比新收藏、取消收藏以及章节数或页数发生变化的漫画。
+/// 同步后可以通过 `picacg_favourite_search` 在本地搜索和排序。
+///
+/// # 返回
+/// - `Ok(FavouriteSyncResult)`：同步成功时返回收藏的变化。
+/// - `Err(CustomError)`：请求失败、解析失败或写入失败时返回错误信息。
+#[frb]
+pub async fn picacg_favourite_sync() -> Result<FavouriteSyncResult, CustomError> {
+    let comics = fetch_all(MAX_CONCURRENT_PAGES, |page| {
+        favourite_page_data(Sort::Default, page)
+    })
+    .await?;
+
+    sync_favourite_snapshots(comics).await
+}
+
+/// 确保漫画已在服务器上收藏。
+///
+/// `is_favourite` 为空时先查询本地收藏镜像，不在镜像中再请求漫画详情确认。
+/// 服务器的收藏接口是切换操作，切换后变为取消收藏时会再切换一次。
+async fn ensure_server_favourite(
+    comic_id: &str,
+    is_favourite: Option<bool>,
+) -> Result<(), CustomError> {
+    let is_favourite = match is_favourite {
+        Some(is_favourite) => is_favourite,
+        None => {
+            picacg_favourite_snapshot(comic_id.to_string())
+                .await?
+                .is_some()
+                || picacg_comic_info(comic_id.to_string()).await?.is_favourite
+        }
+    };
+    if is_favourite {
+        return Ok(());
+    }
+
+    for _ in 0..2 {
+        let action = picacg_comic_switch_favourite(comic_id.to_string()).await?;
+        if action.action == "favourite" {
+            return Ok(());
+        }
+    }
+
+    Err(CustomError {
+        error_code: CustomErrorType::BadRequest,
+        error_message: format!("Failed to favourite comic {}", comic_id),
+    })
+}
+
+/// 将漫画加入本地收藏夹，并保存漫画列表数据作为离线显示的快照。
+///
+/// 开启 `picacg_folder_set_sync_favourite` 后，漫画还没有在服务器上收藏时会同时收藏。
+/// 服务器收藏失败时漫画仍会留在收藏夹中，并返回错误信息。
+///
+/// # 参数
+/// - `folder_id`: 收藏夹 ID。
+/// - `comic`: 漫画列表数据。
+#[frb]
+pub async fn picacg_folder_add_comic(
+    folder_id: i64,
+    comic: ComicEntity,
+) -> Result<FolderComic, CustomError> {
+    let folder_comic = add_folder_comic(folder_id, comic.id.clone(), Some(&comic), None).await?;
+    if picacg_folder_sync_favourite().await? {
+        ensure_server_favourite(&comic.id, None).await?;
+    }
+
+    Ok(folder_comic)
+}
+
+/// 将漫画加入本地收藏夹，并保存漫画详情作为离线显示的快照。
+///
+/// 开启 `picacg_folder_set_sync_favourite` 后，漫画还没有在服务器上收藏时会同时收藏。
+/// 服务器收藏失败时漫画仍会留在收藏夹中，并返回错误信息。
+///
+/// # 参数
+/// - `folder_id`: 收藏夹 ID。
+/// - `info`: 漫画详情。
+#[frb]
+pub async fn picacg_folder_add_comic_info(
+    folder_id: i64,
+    info: ComicInfoEntity,
+) -> Result<FolderComic, CustomError> {
+    let folder_comic = add_folder_comic(folder_id, info.id.clone(), None, Some(&info)).await?;
+    if picacg_folder_sync_favourite().await? {
+        ensure_server_favourite(&info.id, Some(info.is_favourite)).await?;
+    }
+
+    Ok(folder_comic)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::{
+        reqs::favourite::{picacg_favourite_sync, picacg_folder_add_comic},
+        storage::database::open_test_database,
+        test_support::comic_fixture,
+    };
+
+    #[tokio::test]
+    async fn test_picacg_favourite_sync() {
+        let result = picacg_favourite_sync().await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_picacg_folder_add_comic() {
+        open_test_database().await;
+        let result = picacg_folder_add_comic(-1, comic_fixture("a")).await;
+        assert!(result.is_err());
+    }
+}
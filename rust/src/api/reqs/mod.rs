@@ -1,3 +1,13 @@
 pub mod comic;
+pub mod comic_filter;
+pub mod creator;
+pub mod download;
+pub mod favourite;
 pub mod notice;
+pub mod outbox;
+pub mod punch_in;
+pub mod reader;
+pub mod search;
+pub mod search_query;
+pub mod subscription;
 pub mod user;
@@ -7,9 +7,11 @@ use crate::api::{
     },
     utils::{
         client::{picacg_request, HttpExpectBody},
+        paginator::{fetch_all, forward_pages, page_stream, MAX_CONCURRENT_PAGES},
         parse_json::parse_json_from_text,
     },
 };
+use crate::frb_generated::StreamSink;
 use flutter_rust_bridge::frb;
 
 #[frb]
@@ -67,6 +69,20 @@ pub async fn picacg_notice_announcements(page: i32) -> Result<AnnouncementPageDa
     )
 }
 
+/// 逐页获取公告，每获取一页推送一次，请求失败时推送错误并结束。
+#[frb]
+pub async fn picacg_notice_announcements_stream(
+    sink: StreamSink<AnnouncementPageData>,
+) -> Result<(), CustomError> {
+    forward_pages(page_stream(1, picacg_notice_announcements), sink).await
+}
+
+/// 获取全部公告，并发请求其余页面，结果保持接口顺序。
+#[frb]
+pub async fn picacg_notice_announcements_all() -> Result<Vec<AnnouncementEntity>, CustomError> {
+    fetch_all(MAX_CONCURRENT_PAGES, picacg_notice_announcements).await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,367 @@
+use crate::api::{
+    error::custom_error::{CustomError, CustomErrorType},
+    storage::outbox_data::{
+        complete_action, enqueue_comment, enqueue_toggle, fail_action, next_pending_action,
+        OutboxAction, OutboxKind,
+    },
+    utils::client::{picacg_request, without_retry, HttpExpectBody, HttpResponseBody},
+};
+use crate::frb_generated::StreamSink;
+use flutter_rust_bridge::{for_generated::anyhow, frb};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Mutex;
+
+/// 同一时间只进行一次发送，保证操作按记录顺序到达服务器。
+static OUTBOX_REPLAY: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+static OUTBOX_LISTENERS: Mutex<Vec<StreamSink<OutboxEvent>>> = Mutex::new(Vec::new());
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutboxEventKind {
+    /// 发送成功并移出队列
+    Completed,
+    /// 网络不可用或服务器暂时无法处理，保留在队列中等待下次发送
+    Retrying,
+    /// 服务器拒绝，或评论发送途中断开无法确定是否已发表，不再自动重试
+    Failed,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct OutboxEvent {
+    pub kind: OutboxEventKind,
+    pub action: OutboxAction,
+}
+
+fn notify_outbox_listeners(event: OutboxEvent) {
+    if let Ok(mut listeners) = OUTBOX_LISTENERS.lock() {
+        listeners.retain(|sink| sink.add(event.clone()).is_ok());
+    }
+}
+
+/// 发送失败的原因。
+struct SendError {
+    /// 请求可能没有被服务器处理，保留在队列中等待下次发送。
+    retry: bool,
+    error: CustomError,
+}
+
+/// 网络中断、令牌过期、限流或服务器故障时的状态码，这些请求稍后重试可能成功。
+fn is_retryable_status(status_code: u16) -> bool {
+    matches!(status_code, 401 | 408 | 429) || status_code >= 500
+}
+
+/// 按状态码和响应内容判断请求结果。
+///
+/// 哔咔的响应中 `code` 与状态码一致，两者都是 200 时才算成功。无法解析的响应
+/// （例如代理或网关返回的 HTML 页面）说明请求可能没有到达服务器，同样需要重试。
+fn check_response(status_code: u16, text: &str) -> Result<Value, SendError> {
+    let json = serde_json::from_str::<Value>(text).map_err(|e| SendError {
+        retry: true,
+        error: CustomError {
+            error_code: CustomErrorType::ParseJsonError,
+            error_message: format!(
+                "Failed to parse JSON response with status {}: {}",
+                status_code, e
+            ),
+        },
+    })?;
+    let code = json["code"]
+        .as_u64()
+        .and_then(|code| u16::try_from(code).ok())
+        .unwrap_or(status_code);
+    if status_code == 200 && code == 200 {
+        return Ok(json);
+    }
+
+    Err(SendError {
+        retry: is_retryable_status(status_code) || is_retryable_status(code),
+        error: CustomError {
+            error_code: CustomErrorType::BadRequest,
+            error_message: format!(
+                "Server returned {}: {}",
+                code,
+                json["message"].as_str().unwrap_or_default()
+            ),
+        },
+    })
+}
+
+/// 请求是否在建立连接时失败，此时服务器一定没有收到请求。
+fn is_connect_error(error: &anyhow::Error) -> bool {
+    match error.downcast_ref::<reqwest_middleware::Error>() {
+        Some(reqwest_middleware::Error::Reqwest(e)) => e.is_connect(),
+        _ => false,
+    }
+}
+
+/// 发送 POST 请求。
+///
+/// `idempotent` 为 `false` 的请求重复发送会产生副作用，只发送一次；发送途中断开时无法确定
+/// 服务器是否已经处理，不再重试，只有连接失败时才保留在队列中。
+async fn post(path: &str, payload: String, idempotent: bool) -> Result<Value, SendError> {
+    let request = picacg_request(
+        "POST",
+        path,
+        Some(payload),
+        None,
+        Some(HttpExpectBody::Text),
+    );
+    let response = match idempotent {
+        true => request.await,
+        false => without_retry(request).await,
+    }
+    .map_err(|e| SendError {
+        retry: idempotent || is_connect_error(&e),
+        error: CustomError {
+            error_code: CustomErrorType::BadRequest,
+            error_message: format!("Failed to make request: {}", e),
+        },
+    })?;
+
+    let text = match response.body {
+        HttpResponseBody::Text(text) => text,
+        HttpResponseBody::Bytes(bytes) => String::from_utf8_lossy(&bytes).to_string(),
+    };
+    check_response(response.status_code, &text)
+}
+
+/// 切换点赞或收藏，直到服务器返回的状态与目标状态一致。
+///
+/// 服务器的接口是切换操作，服务器上已经是目标状态时第一次切换会得到相反的结果，需要再切换一次。
+async fn send_toggle(kind: OutboxKind, comic_id: &str, desired: bool) -> Result<(), SendError> {
+    let (path, on, off) = match kind {
+        OutboxKind::Like => ("like", "like", "unlike"),
+        _ => ("favourite", "favourite", "un_favourite"),
+    };
+    let expected = if desired { on } else { off };
+
+    for _ in 0..2 {
+        let json = post(
+            &format!("/comics/{}/{}", comic_id, path),
+            "{}".to_string(),
+            true,
+        )
+        .await?;
+        if json["data"]["action"].as_str() == Some(expected) {
+            return Ok(());
+        }
+    }
+
+    Err(SendError {
+        retry: false,
+        error: CustomError {
+            error_code: CustomErrorType::ValidationError,
+            error_message: format!("Server did not switch {} to {}", comic_id, expected),
+        },
+    })
+}
+
+async fn send_action(action: &OutboxAction) -> Result<(), SendError> {
+    match (action.kind, action.desired, &action.content) {
+        (OutboxKind::Comment, _, Some(content)) => post(
+            &format!("/comics/{}/comments", action.comic_id),
+            serde_json::json!({ "content": content }).to_string(),
+            false,
+        )
+        .await
+        .map(|_| ()),
+        (kind, Some(desired), _) => send_toggle(kind, &action.comic_id, desired).await,
+        _ => Err(SendError {
+            retry: false,
+            error: CustomError {
+                error_code: CustomErrorType::ParameterError,
+                error_message: format!("Outbox action {} is incomplete", action.id),
+            },
+        }),
+    }
+}
+
+/// 在后台发送队列中的操作。
+fn spawn_replay() {
+    tokio::spawn(async {
+        let _ = picacg_outbox_replay().await;
+    });
+}
+
+/// 按记录顺序发送队列中等待发送的操作。
+///
+/// 网络不可用、令牌过期或服务器故障时停止发送，保留剩余操作等待下次调用，应在网络恢复
+/// 或重新登录后调用。服务器明确拒绝的操作会标记为失败并继续发送后续操作；评论在发送途中断开时
+/// 可能已经发表，为避免重复发表同样标记为失败。每个操作的结果都会通过
+/// `picacg_outbox_subscribe` 推送给 Dart。
+///
+/// # 返回
+/// - `Ok(i32)`：发送成功的操作数量。
+/// - `Err(CustomError)`：读写队列失败时返回错误信息。
+#[frb]
+pub async fn picacg_outbox_replay() -> Result<i32, CustomError> {
+    let _replay = OUTBOX_REPLAY.lock().await;
+    let mut completed = 0;
+
+    while let Some(action) = next_pending_action().await? {
+        match send_action(&action).await {
+            Ok(()) => {
+                if complete_action(&action).await? {
+                    completed += 1;
+                    notify_outbox_listeners(OutboxEvent {
+                        kind: OutboxEventKind::Completed,
+                        action,
+                    });
+                }
+            }
+            Err(e) => {
+                // 请求可能没有被服务器处理，等待恢复后按原顺序重试
+                let action = fail_action(&action, e.error.error_message, !e.retry).await?;
+                notify_outbox_listeners(OutboxEvent {
+                    kind: match e.retry {
+                        true => OutboxEventKind::Retrying,
+                        false => OutboxEventKind::Failed,
+                    },
+                    action,
+                });
+                if e.retry {
+                    break;
+                }
+            }
+        }
+    }
+
+    Ok(completed)
+}
+
+/// 点赞或取消点赞。操作会先记录到队列中并立即尝试发送，网络不可用时等待下次发送。
+///
+/// # 参数
+/// - `comic_id`: 漫画的唯一标识符。
+/// - `liked`: 目标状态，`true` 为点赞。
+#[frb]
+pub async fn picacg_outbox_like(
+    comic_id: String,
+    liked: bool,
+) -> Result<OutboxAction, CustomError> {
+    let action = enqueue_toggle(OutboxKind::Like, comic_id, liked).await?;
+    spawn_replay();
+    Ok(action)
+}
+
+/// 收藏或取消收藏。操作会先记录到队列中并立即尝试发送，网络不可用时等待下次发送。
+///
+/// # 参数
+/// - `comic_id`: 漫画的唯一标识符。
+/// - `favourite`: 目标状态，`true` 为收藏。
+#[frb]
+pub async fn picacg_outbox_favourite(
+    comic_id: String,
+    favourite: bool,
+) -> Result<OutboxAction, CustomError> {
+    let action = enqueue_toggle(OutboxKind::Favourite, comic_id, favourite).await?;
+    spawn_replay();
+    Ok(action)
+}
+
+/// 发表评论。评论会先记录到队列中并立即尝试发送，网络不可用时等待下次发送。
+///
+/// # 参数
+/// - `comic_id`: 漫画的唯一标识符。
+/// - `content`: 评论内容，不能为空。
+#[frb]
+pub async fn picacg_outbox_comment(
+    comic_id: String,
+    content: String,
+) -> Result<OutboxAction, CustomError> {
+    if content.trim().is_empty() {
+        return Err(CustomError {
+            error_code: CustomErrorType::ParameterError,
+            error_message: "Comment content cannot be empty".to_string(),
+        });
+    }
+
+    let action = enqueue_comment(comic_id, content).await?;
+    spawn_replay();
+    Ok(action)
+}
+
+/// 订阅队列中操作的发送结果。
+#[frb(sync)]
+pub fn picacg_outbox_subscribe(sink: StreamSink<OutboxEvent>) -> Result<(), CustomError> {
+    OUTBOX_LISTENERS
+        .lock()
+        .map_err(|_| CustomError {
+            error_code: CustomErrorType::LockError,
+            error_message: "Failed to acquire lock on OUTBOX_LISTENERS".to_string(),
+        })?
+        .push(sink);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::{
+        error::custom_error::CustomErrorType,
+        reqs::outbox::{
+            check_response, is_connect_error, picacg_outbox_comment, picacg_outbox_replay,
+        },
+        storage::database::open_test_database,
+        utils::client::{send_request, without_retry},
+    };
+    use flutter_rust_bridge::for_generated::anyhow;
+
+    #[tokio::test]
+    async fn test_picacg_outbox_comment() {
+        open_test_database().await;
+        let result = picacg_outbox_comment("outbox".to_string(), " ".to_string()).await;
+        assert!(matches!(
+            result.map_err(|e| e.error_code),
+            Err(CustomErrorType::ParameterError)
+        ));
+    }
+
+    #[test]
+    fn test_check_response() {
+        let retry =
+            |status_code: u16, text: &str| check_response(status_code, text).err().map(|e| e.retry);
+
+        assert_eq!(retry(200, r#"{"code":200,"data":{}}"#), None);
+        // 令牌过期、服务器故障和无法解析的页面都保留在队列中
+        assert_eq!(
+            retry(401, r#"{"code":401,"message":"unauthorized"}"#),
+            Some(true)
+        );
+        assert_eq!(retry(502, "<html>Bad Gateway</html>"), Some(true));
+        assert_eq!(retry(200, "<html>portal</html>"), Some(true));
+        assert_eq!(retry(200, r#"{"code":503,"message":"busy"}"#), Some(true));
+        // 服务器明确拒绝的请求不再重试
+        assert_eq!(
+            retry(400, r#"{"code":400,"message":"invalid"}"#),
+            Some(false)
+        );
+        assert_eq!(retry(404, r#"{"code":404}"#), Some(false));
+    }
+
+    #[tokio::test]
+    async fn test_is_connect_error() {
+        // 本机的 1 号端口没有服务监听，连接会被拒绝
+        let error = without_retry(send_request(
+            "POST",
+            "http://127.0.0.1:1",
+            None,
+            None,
+            None,
+            None,
+        ))
+        .await
+        .err()
+        .unwrap();
+        assert!(is_connect_error(&error));
+        assert!(!is_connect_error(&anyhow::anyhow!("connection reset")));
+    }
+
+    #[tokio::test]
+    async fn test_picacg_outbox_replay() {
+        open_test_database().await;
+        assert!(picacg_outbox_replay().await.is_ok());
+    }
+}
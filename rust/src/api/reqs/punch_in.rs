@@ -0,0 +1,277 @@
+use crate::api::{
+    error::custom_error::{CustomError, CustomErrorType},
+    reqs::user::{
+        fetch_user_profile, picacg_user_cached_profile, picacg_user_login, picacg_user_profile,
+        picacg_user_punch_in,
+    },
+    storage::{
+        config::CONFIG,
+        punch_in_data::{
+            picacg_punch_in_settings, punch_in_accounts, punch_in_record, record_punch_in,
+            set_punch_in_settings, PunchInRecord, PunchInSettings, PunchInStatus,
+        },
+        secret_data::{picacg_secrets_is_unlocked, Credentials},
+    },
+    types::profile_entity::ProfileEntity,
+    utils::client::with_request_token,
+};
+use crate::frb_generated::StreamSink;
+use chrono::{DateTime, Duration, Local, TimeZone};
+use flutter_rust_bridge::frb;
+use std::sync::Mutex;
+use tokio::task::AbortHandle;
+
+/// 同一时间只进行一次签到，避免启动检查和定时任务重复签到。
+static PUNCH_IN_RUN: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+static PUNCH_IN_SCHEDULER: Mutex<Option<AbortHandle>> = Mutex::new(None);
+static PUNCH_IN_LISTENERS: Mutex<Vec<StreamSink<PunchInRecord>>> = Mutex::new(Vec::new());
+
+fn notify_punch_in_listeners(record: &PunchInRecord) {
+    if let Ok(mut listeners) = PUNCH_IN_LISTENERS.lock() {
+        listeners.retain(|sink| sink.add(record.clone()).is_ok());
+    }
+}
+
+/// `now` 之后下一次到达每天 `hour:minute` 的时间。
+///
+/// 当天的时间因夏令时不存在时顺延一天。
+fn next_run_at<Tz: TimeZone>(now: &DateTime<Tz>, hour: u32, minute: u32) -> DateTime<Tz> {
+    let mut date = now.date_naive();
+    loop {
+        let run_at = date
+            .and_hms_opt(hour, minute, 0)
+            .and_then(|time| now.timezone().from_local_datetime(&time).earliest());
+        if let Some(run_at) = run_at.filter(|run_at| run_at > now) {
+            return run_at;
+        }
+        date += Duration::days(1);
+    }
+}
+
+/// 检查账号的签到状态，未签到时签到，返回账号资料和签到结果。
+///
+/// `current` 为 `true` 时检查当前登录的账号并更新资料缓存，否则检查请求令牌对应的账号。
+/// 获取资料失败时资料为空。
+async fn punch_in_account(
+    current: bool,
+) -> (Option<ProfileEntity>, Result<PunchInStatus, CustomError>) {
+    let profile = match current {
+        true => picacg_user_profile().await,
+        false => fetch_user_profile().await,
+    };
+    let profile = match profile {
+        Ok(profile) => profile,
+        Err(e) => return (None, Err(e)),
+    };
+    if profile.is_punched {
+        return (Some(profile), Ok(PunchInStatus::AlreadyPunched));
+    }
+
+    let result = picacg_user_punch_in().await.map(|_| PunchInStatus::Punched);
+    (Some(profile), result)
+}
+
+/// 使用保存的账号和密码单独登录并签到，不影响当前登录的账号。
+async fn punch_in_saved_account(
+    credentials: Credentials,
+) -> (Option<ProfileEntity>, Result<PunchInStatus, CustomError>) {
+    match picacg_user_login(credentials.username, credentials.password).await {
+        Ok(login) => with_request_token(login.token, punch_in_account(false)).await,
+        Err(e) => (None, Err(e)),
+    }
+}
+
+/// 保存签到结果并推送给订阅者。
+async fn save_result(
+    profile: Option<ProfileEntity>,
+    account: Option<String>,
+    result: Result<PunchInStatus, CustomError>,
+) -> Result<PunchInRecord, CustomError> {
+    let (status, error_message) = match result {
+        Ok(status) => (status, None),
+        Err(e) => (PunchInStatus::Failed, Some(e.error_message)),
+    };
+    let record = record_punch_in(
+        profile.as_ref().map(|p| p.id.clone()),
+        account,
+        profile.map(|p| p.name),
+        status,
+        error_message,
+    )
+    .await?;
+
+    notify_punch_in_listeners(&record);
+    Ok(record)
+}
+
+/// 为当前登录的账号和保存的自动签到账号签到，`skip_punched` 为 `true` 时跳过今天已经签到成功的账号。
+async fn run_punch_in(skip_punched: bool) -> Result<Vec<PunchInRecord>, CustomError> {
+    let _run = PUNCH_IN_RUN.lock().await;
+    let today = Local::now().date_naive();
+    let mut records = Vec::new();
+
+    let logged_in = CONFIG
+        .read()
+        .map(|config| !config.user_data.token.is_empty())
+        .unwrap_or(false);
+    if logged_in {
+        let cached = picacg_user_cached_profile();
+        let user_id = cached.as_ref().map(|profile| profile.id.clone());
+        let punched = user_id.is_some()
+            && punch_in_record(user_id, None)
+                .await?
+                .is_some_and(|record| record.is_punched_on(today));
+        if !(skip_punched && punched) {
+            let (profile, result) = punch_in_account(true).await;
+            // 请求失败时使用当前账号之前缓存的资料，都没有时结果不会保存
+            records.push(save_result(profile.or(cached), None, result).await?);
+        }
+    }
+
+    // 密钥库未解锁时读取不到保存的账号，只为当前登录的账号签到
+    let accounts = match picacg_secrets_is_unlocked() {
+        true => punch_in_accounts()?,
+        false => Vec::new(),
+    };
+    for credentials in accounts {
+        let account = Some(credentials.username.clone());
+        if skip_punched
+            && punch_in_record(None, account.clone())
+                .await?
+                .is_some_and(|record| record.is_punched_on(today))
+        {
+            continue;
+        }
+
+        let (profile, result) = punch_in_saved_account(credentials).await;
+        // 保存的账号就是当前登录的账号时不重复记录
+        if profile.as_ref().is_some_and(|profile| {
+            records
+                .iter()
+                .any(|record| record.user_id.as_ref() == Some(&profile.id))
+        }) {
+            continue;
+        }
+        records.push(save_result(profile, account, result).await?);
+    }
+
+    Ok(records)
+}
+
+/// 立即为当前登录的账号和 `picacg_punch_in_add_account` 添加的账号检查签到状态并签到，
+/// 结果会保存并推送给订阅者。
+///
+/// 保存的账号会单独登录后签到，不影响当前登录的账号；密钥库未解锁时只为当前登录的账号签到。
+///
+/// # 返回
+/// - `Ok(Vec<PunchInRecord>)`：每个账号的签到结果，没有需要签到的账号时为空。
+/// - `Err(CustomError)`：读取保存的账号或保存签到结果失败时返回错误信息。
+#[frb]
+pub async fn picacg_punch_in_run() -> Result<Vec<PunchInRecord>, CustomError> {
+    run_punch_in(false).await
+}
+
+fn stop_scheduler() -> Result<(), CustomError> {
+    if let Some(handle) = PUNCH_IN_SCHEDULER
+        .lock()
+        .map_err(|_| CustomError {
+            error_code: CustomErrorType::LockError,
+            error_message: "Failed to acquire lock on PUNCH_IN_SCHEDULER".to_string(),
+        })?
+        .take()
+    {
+        handle.abort();
+    }
+
+    Ok(())
+}
+
+/// 按设置启动自动签到，应在应用启动时调用。
+///
+/// 启动后立即检查一次，之后每天在设置的时间检查。未开启自动签到时停止已有的定时任务。
+///
+/// # 返回
+/// - `Ok(true)`：已启动自动签到。
+/// - `Ok(false)`：未开启自动签到。
+///
+/// # 错误
+/// - 当设置的时间不合法时，返回参数错误。
+#[frb]
+pub async fn picacg_punch_in_start() -> Result<bool, CustomError> {
+    stop_scheduler()?;
+    let settings = picacg_punch_in_settings().await?;
+    if !settings.enabled {
+        return Ok(false);
+    }
+    settings.validate()?;
+
+    let task = tokio::spawn(async move {
+        let _ = run_punch_in(true).await;
+        loop {
+            let now = Local::now();
+            let run_at = next_run_at(&now, settings.hour, settings.minute);
+            tokio::time::sleep((run_at - now).to_std().unwrap_or_default()).await;
+            let _ = run_punch_in(true).await;
+        }
+    });
+
+    *PUNCH_IN_SCHEDULER.lock().map_err(|_| CustomError {
+        error_code: CustomErrorType::LockError,
+        error_message: "Failed to acquire lock on PUNCH_IN_SCHEDULER".to_string(),
+    })? = Some(task.abort_handle());
+
+    Ok(true)
+}
+
+/// 保存自动签到设置，并按新的设置重新启动或停止自动签到。
+///
+/// # 错误
+/// - 当时间不合法时，返回参数错误。
+#[frb]
+pub async fn picacg_punch_in_configure(settings: PunchInSettings) -> Result<bool, CustomError> {
+    set_punch_in_settings(&settings).await?;
+    picacg_punch_in_start().await
+}
+
+/// 停止自动签到的定时任务，不修改设置。
+#[frb(sync)]
+pub fn picacg_punch_in_stop() -> Result<(), CustomError> {
+    stop_scheduler()
+}
+
+/// 订阅签到结果。
+#[frb(sync)]
+pub fn picacg_punch_in_subscribe(sink: StreamSink<PunchInRecord>) -> Result<(), CustomError> {
+    PUNCH_IN_LISTENERS
+        .lock()
+        .map_err(|_| CustomError {
+            error_code: CustomErrorType::LockError,
+            error_message: "Failed to acquire lock on PUNCH_IN_LISTENERS".to_string(),
+        })?
+        .push(sink);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::reqs::punch_in::next_run_at;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_next_run_at() {
+        let now = Utc.with_ymd_and_hms(2024, 12, 31, 7, 30, 0).unwrap();
+        assert_eq!(
+            next_run_at(&now, 8, 0),
+            Utc.with_ymd_and_hms(2024, 12, 31, 8, 0, 0).unwrap()
+        );
+        assert_eq!(
+            next_run_at(&now, 7, 30),
+            Utc.with_ymd_and_hms(2025, 1, 1, 7, 30, 0).unwrap()
+        );
+        assert_eq!(
+            next_run_at(&now, 0, 0),
+            Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap()
+        );
+    }
+}
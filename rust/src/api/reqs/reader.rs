@@ -0,0 +1,416 @@
+use crate::api::{
+    error::custom_error::{CustomError, CustomErrorType},
+    reqs::comic::{picacg_comic_ep_pictures, picacg_comic_ep_pictures_all, picacg_comic_eps_all},
+    storage::{
+        config::picacg_config_net_data,
+        image_cache::{image_url, picacg_image_cached, picacg_image_fetch},
+    },
+    types::reader_entity::{ReaderManifest, ReaderPage, ReaderPageReady},
+};
+use crate::frb_generated::StreamSink;
+use flutter_rust_bridge::frb;
+use futures::future::join_all;
+use std::{
+    collections::HashSet,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+};
+use tokio::sync::Notify;
+
+/// 预取下一章节开头的页数。
+const NEXT_EP_PREFETCH_PAGES: usize = 3;
+/// 预取时同时下载的图片数。
+const MAX_CONCURRENT_DOWNLOADS: usize = 2;
+
+static READER: Mutex<Option<ReaderSession>> = Mutex::new(None);
+static READER_GENERATION: AtomicU64 = AtomicU64::new(0);
+static READER_LISTENERS: Mutex<Vec<StreamSink<ReaderPageReady>>> = Mutex::new(Vec::new());
+
+/// 当前打开的章节及预取进度。
+struct ReaderSession {
+    /// 每次打开章节递增，旧的预取任务发现不一致时退出。
+    generation: u64,
+    comic_id: String,
+    ep_order: i32,
+    urls: Vec<String>,
+    next_ep_order: Option<i32>,
+    /// 下一章节开头几页的地址，首次需要时才请求。
+    next_urls: Option<Vec<String>>,
+    current: usize,
+    prefetch: usize,
+    ready: HashSet<String>,
+    downloading: HashSet<String>,
+    /// 下载失败的图片，跳转页码后重试。
+    failed: HashSet<String>,
+    wake: Arc<Notify>,
+}
+
+#[derive(Debug, PartialEq)]
+enum PrefetchPage {
+    Current(usize),
+    Next(usize),
+}
+
+struct PrefetchTask {
+    ep_order: i32,
+    index: usize,
+    url: String,
+}
+
+enum PrefetchStep {
+    LoadNextEp { comic_id: String, ep_order: i32 },
+    Download(Vec<PrefetchTask>),
+    Wait(Arc<Notify>),
+}
+
+/// 按优先级排列需要预取的页面：先是当前页及之后 `prefetch` 页，
+/// 预取范围到达章节末尾时再加上下一章节开头的 `next_total` 页。
+fn prefetch_order(
+    current: usize,
+    total: usize,
+    prefetch: usize,
+    next_total: usize,
+) -> Vec<PrefetchPage> {
+    let end = current.saturating_add(prefetch).saturating_add(1);
+    let mut pages: Vec<PrefetchPage> = (current.min(total)..end.min(total))
+        .map(PrefetchPage::Current)
+        .collect();
+
+    if end >= total {
+        pages.extend((0..next_total).map(PrefetchPage::Next));
+    }
+
+    pages
+}
+
+impl ReaderSession {
+    fn reaches_end(&self) -> bool {
+        self.current.saturating_add(self.prefetch).saturating_add(1) >= self.urls.len()
+    }
+
+    fn next_step(&mut self) -> PrefetchStep {
+        if let (Some(ep_order), None, true) =
+            (self.next_ep_order, &self.next_urls, self.reaches_end())
+        {
+            return PrefetchStep::LoadNextEp {
+                comic_id: self.comic_id.clone(),
+                ep_order,
+            };
+        }
+
+        let next_urls = self.next_urls.as_deref().unwrap_or_default();
+        let mut tasks = Vec::new();
+        for page in prefetch_order(
+            self.current,
+            self.urls.len(),
+            self.prefetch,
+            next_urls.len(),
+        ) {
+            let task = match page {
+                PrefetchPage::Current(index) => PrefetchTask {
+                    ep_order: self.ep_order,
+                    index,
+                    url: self.urls[index].clone(),
+                },
+                PrefetchPage::Next(index) => PrefetchTask {
+                    ep_order: self.next_ep_order.unwrap_or_default(),
+                    index,
+                    url: next_urls[index].clone(),
+                },
+            };
+
+            if self.ready.contains(&task.url)
+                || self.downloading.contains(&task.url)
+                || self.failed.contains(&task.url)
+            {
+                continue;
+            }
+
+            self.downloading.insert(task.url.clone());
+            tasks.push(task);
+            if tasks.len() >= MAX_CONCURRENT_DOWNLOADS {
+                break;
+            }
+        }
+
+        if tasks.is_empty() {
+            PrefetchStep::Wait(self.wake.clone())
+        } else {
+            PrefetchStep::Download(tasks)
+        }
+    }
+}
+
+fn reader() -> Result<MutexGuard<'static, Option<ReaderSession>>, CustomError> {
+    READER.lock().map_err(|_| CustomError {
+        error_code: CustomErrorType::LockError,
+        error_message: "Failed to acquire lock on READER".to_string(),
+    })
+}
+
+/// 获取指定预取任务对应的会话，会话已关闭或已打开其他章节时返回 `None`。
+fn with_session<R>(generation: u64, f: impl FnOnce(&mut ReaderSession) -> R) -> Option<R> {
+    let mut reader = reader().ok()?;
+    reader
+        .as_mut()
+        .filter(|session| session.generation == generation)
+        .map(f)
+}
+
+fn notify_reader_listeners(ready: &ReaderPageReady) {
+    if let Ok(mut listeners) = READER_LISTENERS.lock() {
+        listeners.retain(|sink| sink.add(ready.clone()).is_ok());
+    }
+}
+
+/// 后台预取任务，会话关闭或打开其他章节后退出。
+async fn prefetch_loop(generation: u64) {
+    loop {
+        let Some(step) = with_session(generation, ReaderSession::next_step) else {
+            return;
+        };
+
+        match step {
+            PrefetchStep::LoadNextEp { comic_id, ep_order } => {
+                // 请求失败时视为没有可预取的页面，不再重试
+                let urls = match (
+                    picacg_config_net_data(),
+                    picacg_comic_ep_pictures(comic_id, ep_order, 1).await,
+                ) {
+                    (Ok(net_data), Ok(page_data)) => page_data
+                        .docs
+                        .iter()
+                        .take(NEXT_EP_PREFETCH_PAGES)
+                        .map(|picture| image_url(&net_data.image_server, &picture.media))
+                        .collect(),
+                    _ => Vec::new(),
+                };
+                with_session(generation, |session| session.next_urls = Some(urls));
+            }
+            PrefetchStep::Download(tasks) => {
+                let results = join_all(
+                    tasks
+                        .iter()
+                        .map(|task| picacg_image_fetch(task.url.clone())),
+                )
+                .await;
+
+                let Some(comic_id) = with_session(generation, |session| {
+                    for (task, result) in tasks.iter().zip(&results) {
+                        session.downloading.remove(&task.url);
+                        match result {
+                            Ok(_) => session.ready.insert(task.url.clone()),
+                            Err(_) => session.failed.insert(task.url.clone()),
+                        };
+                    }
+                    session.comic_id.clone()
+                }) else {
+                    return;
+                };
+
+                for (task, result) in tasks.into_iter().zip(results) {
+                    if let Ok(path) = result {
+                        notify_reader_listeners(&ReaderPageReady {
+                            comic_id: comic_id.clone(),
+                            ep_order: task.ep_order,
+                            index: task.index as i32,
+                            path,
+                        });
+                    }
+                }
+            }
+            PrefetchStep::Wait(wake) => wake.notified().await,
+        }
+    }
+}
+
+/// 关闭当前会话并唤醒其预取任务，使其退出。
+fn close_session(reader: &mut Option<ReaderSession>) {
+    if let Some(session) = reader.take() {
+        session.wake.notify_one();
+    }
+}
+
+/// 打开章节并开始后台预取。
+///
+/// 该函数会获取章节的全部图片生成阅读清单，并在后台依次下载当前页之后的 `prefetch` 页到图片缓存，
+/// 读到章节末尾附近时还会预取下一章节开头的几页。同一时间只有一个阅读会话，打开新章节会结束之前的会话。
+///
+/// # 参数
+/// - `comic_id`: 漫画的唯一标识符。
+/// - `ep_order`: 章节序号。
+/// - `prefetch`: 当前页之后预取的页数，小于 0 时视为 0。
+///
+/// # 返回
+/// - `Ok(ReaderManifest)`：按顺序排列的全部页面，已缓存的页面带有本地文件路径。
+/// - `Err(CustomError)`：请求失败或解析失败时返回错误信息。
+#[frb]
+pub async fn picacg_reader_open(
+    comic_id: String,
+    ep_order: i32,
+    prefetch: i32,
+) -> Result<ReaderManifest, CustomError> {
+    let (pictures, eps) = futures::try_join!(
+        picacg_comic_ep_pictures_all(comic_id.clone(), ep_order),
+        picacg_comic_eps_all(comic_id.clone())
+    )?;
+    let next_ep_order = eps
+        .iter()
+        .map(|ep| ep.order)
+        .filter(|order| *order > ep_order)
+        .min();
+
+    let image_server = picacg_config_net_data()?.image_server;
+    let pages = pictures
+        .into_iter()
+        .enumerate()
+        .map(|(index, picture)| {
+            let url = image_url(&image_server, &picture.media);
+            Ok(ReaderPage {
+                index: index as i32,
+                id: picture.id,
+                media: picture.media,
+                path: picacg_image_cached(url.clone())?,
+                url,
+            })
+        })
+        .collect::<Result<Vec<_>, CustomError>>()?;
+
+    let generation = READER_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let session = ReaderSession {
+        generation,
+        comic_id: comic_id.clone(),
+        ep_order,
+        urls: pages.iter().map(|page| page.url.clone()).collect(),
+        next_ep_order,
+        next_urls: None,
+        current: 0,
+        prefetch: prefetch.max(0) as usize,
+        ready: pages
+            .iter()
+            .filter(|page| page.path.is_some())
+            .map(|page| page.url.clone())
+            .collect(),
+        downloading: HashSet::new(),
+        failed: HashSet::new(),
+        wake: Arc::new(Notify::new()),
+    };
+
+    {
+        let mut reader = reader()?;
+        close_session(&mut reader);
+        *reader = Some(session);
+    }
+    tokio::spawn(prefetch_loop(generation));
+
+    Ok(ReaderManifest {
+        comic_id,
+        ep_order,
+        pages,
+        next_ep_order,
+    })
+}
+
+/// 跳转到指定页，预取会从该页开始重新排列，之前下载失败的页面会重试。
+///
+/// # 参数
+/// - `index`: 页码（从 0 开始）。
+#[frb(sync)]
+pub fn picacg_reader_jump(index: i32) -> Result<(), CustomError> {
+    let mut reader = reader()?;
+    let session = reader.as_mut().ok_or_else(|| CustomError {
+        error_code: CustomErrorType::ParameterError,
+        error_message: "Reader session is not open".to_string(),
+    })?;
+
+    if index < 0 || index as usize >= session.urls.len() {
+        return Err(CustomError {
+            error_code: CustomErrorType::ParameterError,
+            error_message: format!("Page index {} out of range", index),
+        });
+    }
+
+    session.current = index as usize;
+    session.failed.clear();
+    session.wake.notify_one();
+
+    Ok(())
+}
+
+/// 获取当前章节已缓存的页码（从 0 开始），未打开章节时返回空列表。
+#[frb(sync)]
+pub fn picacg_reader_ready_pages() -> Result<Vec<i32>, CustomError> {
+    Ok(reader()?
+        .as_ref()
+        .map(|session| {
+            session
+                .urls
+                .iter()
+                .enumerate()
+                .filter(|(_, url)| session.ready.contains(*url))
+                .map(|(index, _)| index as i32)
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// 订阅预取完成的页面，包括下一章节开头的页面。
+#[frb(sync)]
+pub fn picacg_reader_subscribe(sink: StreamSink<ReaderPageReady>) -> Result<(), CustomError> {
+    READER_LISTENERS
+        .lock()
+        .map_err(|_| CustomError {
+            error_code: CustomErrorType::LockError,
+            error_message: "Failed to acquire lock on READER_LISTENERS".to_string(),
+        })?
+        .push(sink);
+
+    Ok(())
+}
+
+/// 关闭阅读会话并停止预取。
+#[frb(sync)]
+pub fn picacg_reader_close() -> Result<(), CustomError> {
+    close_session(&mut *reader()?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::reqs::reader::{
+        picacg_reader_jump, picacg_reader_open, prefetch_order, PrefetchPage,
+    };
+
+    #[test]
+    fn test_prefetch_order() {
+        assert_eq!(
+            prefetch_order(2, 10, 3, 2),
+            vec![
+                PrefetchPage::Current(2),
+                PrefetchPage::Current(3),
+                PrefetchPage::Current(4),
+                PrefetchPage::Current(5),
+            ]
+        );
+        assert_eq!(
+            prefetch_order(7, 10, 3, 2),
+            vec![
+                PrefetchPage::Current(7),
+                PrefetchPage::Current(8),
+                PrefetchPage::Current(9),
+                PrefetchPage::Next(0),
+                PrefetchPage::Next(1),
+            ]
+        );
+        assert_eq!(prefetch_order(0, 0, 3, 0), vec![]);
+    }
+
+    #[tokio::test]
+    async fn test_picacg_reader_open() {
+        let result = picacg_reader_open("5b6bdf4558ed442d899486b7".to_string(), 1, 5).await;
+        assert!(result.is_err());
+        assert!(picacg_reader_jump(0).is_err());
+    }
+}
@@ -0,0 +1,115 @@
+use crate::api::{
+    error::custom_error::CustomError,
+    reqs::user::{picacg_user_cached_profile, picacg_user_profile},
+    storage::{
+        config::CONFIG,
+        search_data::{
+            clear_search_history, delete_search, pin_search, record_search, search_history,
+            search_suggestions, SearchHistoryEntry, SearchSuggestion,
+        },
+    },
+};
+use flutter_rust_bridge::frb;
+
+/// 当前登录账号的用户 ID。
+///
+/// 没有当前账号的资料缓存时请求一次用户资料，未登录或请求失败时返回 `None`，
+/// 此时不读写搜索历史，避免记到其他账号下。
+#[frb(ignore)]
+pub async fn current_account() -> Option<String> {
+    if let Some(profile) = picacg_user_cached_profile() {
+        return Some(profile.id);
+    }
+
+    let logged_in = CONFIG
+        .read()
+        .is_ok_and(|config| !config.user_data.token.is_empty());
+    if !logged_in {
+        return None;
+    }
+
+    picacg_user_profile().await.ok().map(|profile| profile.id)
+}
+
+/// 在后台把关键词记入当前账号的搜索历史，不等待确定账号和写入数据库。
+#[frb(ignore)]
+pub fn record_search_in_background(keyword: String) {
+    tokio::spawn(async move {
+        if let Some(account) = current_account().await {
+            let _ = record_search(account, keyword).await;
+        }
+    });
+}
+
+/// 记录一次搜索。`picacg_comic_search` 请求第一页时会自动记录，
+/// 点击标签等不经过搜索接口的搜索可以手动记录。
+///
+/// # 返回
+/// - `Ok(None)`：关键词为空或无法确定当前账号，没有记录。
+#[frb]
+pub async fn picacg_search_history_add(
+    keyword: String,
+) -> Result<Option<SearchHistoryEntry>, CustomError> {
+    match current_account().await {
+        Some(account) => record_search(account, keyword).await,
+        None => Ok(None),
+    }
+}
+
+/// 获取当前账号的搜索历史，置顶的在前，其余按最近搜索时间排列。无法确定当前账号时为空。
+#[frb]
+pub async fn picacg_search_history() -> Result<Vec<SearchHistoryEntry>, CustomError> {
+    match current_account().await {
+        Some(account) => search_history(account).await,
+        None => Ok(Vec::new()),
+    }
+}
+
+/// 置顶或取消置顶一条搜索历史。
+///
+/// # 返回
+/// - `Ok(false)`：没有该搜索历史。
+#[frb]
+pub async fn picacg_search_history_pin(keyword: String, pinned: bool) -> Result<bool, CustomError> {
+    match current_account().await {
+        Some(account) => pin_search(account, keyword, pinned).await,
+        None => Ok(false),
+    }
+}
+
+/// 删除一条搜索历史。
+#[frb]
+pub async fn picacg_search_history_delete(keyword: String) -> Result<(), CustomError> {
+    match current_account().await {
+        Some(account) => delete_search(account, keyword).await,
+        None => Ok(()),
+    }
+}
+
+/// 清空当前账号的搜索历史。
+///
+/// # 参数
+/// - `keep_pinned`: 是否保留置顶的搜索历史。
+#[frb]
+pub async fn picacg_search_history_clear(keep_pinned: bool) -> Result<(), CustomError> {
+    match current_account().await {
+        Some(account) => clear_search_history(account, keep_pinned).await,
+        None => Ok(()),
+    }
+}
+
+/// 按输入生成搜索建议。
+///
+/// 候选词来自当前账号的搜索历史（无法确定当前账号时不包含）、`picacg_comic_keywords` 返回的热门关键词、
+/// 漫画列表中出现过的标签和 `picacg_comic_category` 返回的分类标题，只读取本地数据。
+///
+/// # 参数
+/// - `input`: 正在输入的关键词，为空时返回搜索历史和热门关键词。
+/// - `limit`: 最多返回的数量。
+#[frb]
+pub async fn picacg_search_suggestions(
+    input: String,
+    limit: i32,
+) -> Result<Vec<SearchSuggestion>, CustomError> {
+    search_suggestions(current_account().await, input, limit).await
+}
@@ -0,0 +1,327 @@
+use crate::api::{
+    error::custom_error::{CustomError, CustomErrorType},
+    reqs::{comic::search_page_data, search::current_account},
+    storage::{blocklist_data::blocklist_matcher, search_data::record_search},
+    types::{
+        comic_search_entity::ComicSearchEntity,
+        page_data::{ComicSearchPageData, PageData},
+        search_query::{SearchField, SearchQuery},
+        sort::Sort,
+    },
+    utils::paginator::{fetch_remaining_pages, MAX_CONCURRENT_PAGES},
+};
+use flutter_rust_bridge::frb;
+use futures::future::try_join_all;
+use std::{
+    collections::HashSet,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// 最多同时请求服务器搜索的条件数。
+const MAX_SEARCHED_TERMS: usize = 8;
+
+/// 驱动搜索的条件组中每个条件最多请求的搜索结果页数。
+const MAX_TERM_PAGES: i32 = 20;
+
+/// 查询结果的缓存时间，翻页时复用同一次查询的结果，保证各页的顺序一致。
+const QUERY_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+
+struct QueryResult {
+    comics: Vec<ComicSearchEntity>,
+    /// 驱动搜索的条件有超过 `MAX_TERM_PAGES` 页的结果。
+    truncated: bool,
+}
+
+struct QueryCache {
+    account: Option<String>,
+    query: String,
+    sort: Sort,
+    result: QueryResult,
+    fetched_at: Instant,
+}
+
+static QUERY_CACHE: Mutex<Option<QueryCache>> = Mutex::new(None);
+
+/// 结果总数最少的条件组，`totals` 为每个条件组中各条件的结果总数。
+///
+/// 组内取并集，所以条件组的结果数量不超过各条件结果总数之和。
+fn most_selective_group(totals: &[Vec<i32>]) -> usize {
+    totals
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, totals)| totals.iter().map(|total| *total as i64).sum::<i64>())
+        .map(|(index, _)| index)
+        .unwrap_or_default()
+}
+
+/// 合并驱动搜索的条件组的结果，`results` 与第 `driver` 个可搜索的条件组中的条件一一对应。
+///
+/// 组内取并集，除普通关键词外，服务器返回的结果还要匹配条件的字段，例如 `tag:` 只保留带有
+/// 该标签的漫画。其余条件组在本地匹配，再应用本地条件。结果顺序与组内条件的顺序一致，
+/// 重复的漫画只保留第一次出现的位置。
+fn combine_results(
+    query: &SearchQuery,
+    driver: usize,
+    results: Vec<Vec<ComicSearchEntity>>,
+) -> Vec<ComicSearchEntity> {
+    let groups = query.searched_groups().collect::<Vec<_>>();
+    let Some(driver_group) = groups.get(driver) else {
+        return Vec::new();
+    };
+
+    let mut seen = HashSet::new();
+    let mut union = Vec::new();
+    for (term, comics) in driver_group.iter().zip(results) {
+        for comic in comics {
+            if (term.field == SearchField::Keyword || term.matches(&comic))
+                && seen.insert(comic.id.clone())
+            {
+                union.push(comic);
+            }
+        }
+    }
+
+    union
+        .into_iter()
+        .filter(|comic| {
+            groups
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| *index != driver)
+                .all(|(_, group)| group.iter().any(|term| term.matches(comic)))
+                && query.matches_local(comic)
+        })
+        .collect()
+}
+
+/// 先并发请求每个可搜索条件的第一页，选出结果总数最少的条件组驱动搜索。
+///
+/// 驱动组中的每个条件最多请求前 `MAX_TERM_PAGES` 页，其余条件组在本地匹配，
+/// 所以不会因为其他条件的结果太多而漏掉匹配的漫画。
+async fn run_query(query: &SearchQuery, sort: Sort) -> Result<QueryResult, CustomError> {
+    query.filter.validate_for::<ComicSearchEntity>()?;
+
+    let terms = query.searched_groups().flatten().collect::<Vec<_>>();
+    if terms.is_empty() {
+        return Err(CustomError {
+            error_code: CustomErrorType::ParameterError,
+            error_message: "Search query has no searchable terms".to_string(),
+        });
+    }
+    if terms.len() > MAX_SEARCHED_TERMS {
+        return Err(CustomError {
+            error_code: CustomErrorType::ParameterError,
+            error_message: format!(
+                "Search query has more than {} searchable terms",
+                MAX_SEARCHED_TERMS
+            ),
+        });
+    }
+
+    let mut first_pages = try_join_all(
+        terms
+            .iter()
+            .map(|term| search_page_data(&term.value, sort, 1, &[])),
+    )
+    .await?
+    .into_iter();
+    let mut groups = query
+        .searched_groups()
+        .map(|group| group.iter().zip(first_pages.by_ref()).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let totals = groups
+        .iter()
+        .map(|group| group.iter().map(|(_, page)| page.total).collect())
+        .collect::<Vec<_>>();
+    let driver = most_selective_group(&totals);
+    let driver_terms = groups.swap_remove(driver);
+    let truncated = driver_terms
+        .iter()
+        .any(|(_, page)| page.pages > MAX_TERM_PAGES);
+
+    let results = try_join_all(driver_terms.into_iter().map(|(term, first)| {
+        fetch_remaining_pages(first, MAX_CONCURRENT_PAGES, MAX_TERM_PAGES, |page| {
+            search_page_data(&term.value, sort, page, &[])
+        })
+    }))
+    .await?;
+
+    Ok(QueryResult {
+        comics: combine_results(query, driver, results),
+        truncated,
+    })
+}
+
+/// 解析搜索语句，语法见 `SearchQuery`。
+///
+/// # 返回
+/// - `Ok(SearchQuery)`：解析后的条件。
+/// - `Err(CustomError)`：筛选条件的值无效时返回 `ParameterError`。
+#[frb(sync)]
+pub fn picacg_search_query_parse(query: String) -> Result<SearchQuery, CustomError> {
+    SearchQuery::parse(&query)
+}
+
+/// 按搜索语句搜索漫画，如 `tag:全彩 -tag:NTR author:xxx "exact phrase" finished:true`。
+///
+/// 每个可搜索的条件并发请求一次 `/comics/advanced-search` 的第一页，由结果总数最少的条件组
+/// 驱动搜索（组内 `OR` 连接的条件取并集，每个条件最多前 `MAX_TERM_PAGES` 页），其余条件组、
+/// 分类、排除和筛选条件在本地匹配，最后按屏蔽列表过滤并分页。驱动组的结果超过页数上限时
+/// `truncated` 为 `true`。
+/// 请求第一页时重新搜索并记入搜索历史，之后的页码在 `QUERY_CACHE_TTL` 内复用第一次的结果，
+/// 因此各页之间不会重复或遗漏。
+///
+/// # 参数
+/// - `query`: 搜索语句。
+/// - `sort`: 排序方式（`Sort` 枚举），决定每个条件的服务器结果顺序。
+/// - `page`: 页码（从 1 开始）。
+/// - `limit`: 每页数量。
+///
+/// # 返回
+/// - `Ok(ComicSearchPageData)`：合并后的分页结果，`hidden` 为全部结果中被屏蔽的数量。
+/// - `Err(CustomError)`：语句无效、没有可搜索的条件或任一请求失败时返回错误信息。
+#[frb]
+pub async fn picacg_comic_search_query(
+    query: String,
+    sort: Sort,
+    page: i32,
+    limit: i32,
+) -> Result<ComicSearchPageData, CustomError> {
+    let parsed = SearchQuery::parse(&query)?;
+    let account = current_account().await;
+    let query = query.trim().to_string();
+
+    let cached = (page > 1)
+        .then(|| {
+            let cache = QUERY_CACHE.lock().ok()?;
+            cache
+                .as_ref()
+                .filter(|cache| {
+                    cache.account == account
+                        && cache.query == query
+                        && cache.sort == sort
+                        && cache.fetched_at.elapsed() < QUERY_CACHE_TTL
+                })
+                .map(|cache| (cache.result.comics.clone(), cache.result.truncated))
+        })
+        .flatten();
+
+    let (mut comics, truncated) = match cached {
+        Some(cached) => cached,
+        None => {
+            let result = run_query(&parsed, sort).await?;
+            let cached = (result.comics.clone(), result.truncated);
+            if let Ok(mut cache) = QUERY_CACHE.lock() {
+                *cache = Some(QueryCache {
+                    account: account.clone(),
+                    query: query.clone(),
+                    sort,
+                    result,
+                    fetched_at: Instant::now(),
+                });
+            }
+            if let Some(account) = account.filter(|_| page <= 1) {
+                let _ = record_search(account, query).await;
+            }
+            cached
+        }
+    };
+
+    let hidden = blocklist_matcher().await.retain(&mut comics);
+    Ok(ComicSearchPageData {
+        hidden,
+        truncated,
+        ..ComicSearchPageData::from(PageData::paginate(comics, page, limit))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::{
+        reqs::search_query::{combine_results, most_selective_group, picacg_comic_search_query},
+        test_support::comic_search_fixture,
+        types::{comic_search_entity::ComicSearchEntity, search_query::SearchQuery, sort::Sort},
+    };
+
+    fn comic(id: &str, tags: &[&str], finished: bool) -> ComicSearchEntity {
+        let mut comic = comic_search_fixture(id);
+        comic.tags = tags.iter().map(|tag| tag.to_string()).collect();
+        comic.finished = finished;
+        comic
+    }
+
+    fn ids(comics: &[ComicSearchEntity]) -> Vec<&str> {
+        comics.iter().map(|comic| comic.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_most_selective_group() {
+        assert_eq!(most_selective_group(&[vec![300, 200], vec![400]]), 1);
+        assert_eq!(most_selective_group(&[vec![10, 20], vec![400]]), 0);
+        assert_eq!(most_selective_group(&[]), 0);
+    }
+
+    #[test]
+    fn test_combine_results() {
+        let query = SearchQuery::parse("foo OR bar tag:全彩 -tag:NTR finished:true").unwrap();
+        let mut foo = comic("2", &["全彩"], true);
+        foo.title = "foo".to_string();
+        let mut bar = comic("5", &["全彩"], true);
+        bar.description = "BAR".to_string();
+
+        // 由 `foo OR bar` 驱动时，`tag:全彩` 在本地匹配
+        let combined = combine_results(
+            &query,
+            0,
+            vec![
+                // foo
+                vec![comic("1", &["全彩"], true), foo.clone()],
+                // bar
+                vec![
+                    comic("3", &["全彩", "NTR"], true),
+                    comic("1", &["全彩"], true),
+                    comic("4", &["全彩"], false),
+                    comic("6", &[], true),
+                    bar.clone(),
+                ],
+            ],
+        );
+        assert_eq!(ids(&combined), vec!["1", "2", "5"]);
+
+        // 由 `tag:全彩` 驱动时，服务器返回的结果中 6 没有该标签，`foo OR bar` 在本地匹配
+        let combined = combine_results(
+            &query,
+            1,
+            vec![vec![
+                bar,
+                comic("6", &[], true),
+                comic("1", &["全彩"], true),
+                foo,
+                comic("3", &["全彩", "NTR"], true),
+            ]],
+        );
+        assert_eq!(ids(&combined), vec!["5", "2"]);
+
+        let query = SearchQuery::parse("foo category:短篇").unwrap();
+        let mut short = comic("7", &[], false);
+        short.categories = vec!["短篇".to_string()];
+        let combined = combine_results(&query, 0, vec![vec![comic("8", &[], false), short]]);
+        assert_eq!(ids(&combined), vec!["7"]);
+    }
+
+    #[tokio::test]
+    async fn test_picacg_comic_search_query() {
+        assert!(
+            picacg_comic_search_query("category:短篇".to_string(), Sort::Default, 1, 20)
+                .await
+                .is_err()
+        );
+        assert!(
+            picacg_comic_search_query("tag:全彩".to_string(), Sort::Default, 1, 20)
+                .await
+                .is_err()
+        );
+    }
+}
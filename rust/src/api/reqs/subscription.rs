@@ -0,0 +1,167 @@
+use crate::api::{
+    error::custom_error::CustomError,
+    reqs::comic::{picacg_comic_eps, picacg_comic_eps_all, picacg_comic_info},
+    storage::subscription_data::{
+        picacg_subscriptions, record_subscription_check, save_subscription, Subscription,
+        SubscriptionEpisode,
+    },
+    types::{
+        comic_ep_entity::ComicEpEntity,
+        subscription_check_entity::{SubscriptionCheckEntity, SubscriptionFailureEntity},
+    },
+    utils::rate_limiter::RateLimiter,
+};
+use flutter_rust_bridge::frb;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// 检查订阅时相邻两次请求的最小间隔。
+const SUBSCRIPTION_REQUEST_INTERVAL: Duration = Duration::from_millis(1500);
+
+/// 同一时间只进行一次检查，避免前台和后台任务重复记录新章节。
+static SUBSCRIPTION_CHECK: Mutex<()> = Mutex::const_new(());
+
+/// 序号大于已知的最新章节，或更新时间晚于已知章节时视为新章节。
+fn is_new_episode(subscription: &Subscription, ep: &ComicEpEntity) -> bool {
+    ep.order > subscription.last_ep_order
+        || subscription
+            .last_ep_updated_at
+            .is_some_and(|updated_at| ep.updated_at > updated_at)
+}
+
+/// 检查一部订阅的漫画，返回检查到的新章节。
+async fn check_subscription(
+    limiter: &mut RateLimiter,
+    subscription: &Subscription,
+) -> Result<Vec<SubscriptionEpisode>, CustomError> {
+    limiter.wait().await;
+    let info = picacg_comic_info(subscription.comic_id.clone()).await?;
+    if info.updated_at == subscription.comic_updated_at {
+        return record_subscription_check(subscription.comic_id.clone(), None, &[]).await;
+    }
+
+    let mut new_eps = Vec::new();
+    let mut page = 1;
+    loop {
+        limiter.wait().await;
+        let page_data = picacg_comic_eps(subscription.comic_id.clone(), page).await?;
+        let count = page_data.docs.len();
+        let before = new_eps.len();
+        new_eps.extend(
+            page_data
+                .docs
+                .into_iter()
+                .filter(|ep| is_new_episode(subscription, ep)),
+        );
+
+        // 章节按序号从新到旧排列，一页中出现已知章节后不再翻页
+        if new_eps.len() - before < count || page >= page_data.pages {
+            break;
+        }
+        page += 1;
+    }
+
+    record_subscription_check(subscription.comic_id.clone(), Some(&info), &new_eps).await
+}
+
+/// 订阅漫画。
+///
+/// 订阅时会记录漫画当前的全部章节，之后的检查只会返回在此之后出现的章节。
+/// 已订阅的漫画再次订阅时会重置检查起点。
+///
+/// # 参数
+/// - `comic_id`: 漫画的唯一标识符。
+///
+/// # 返回
+/// - `Ok(Subscription)`：订阅成功时返回订阅记录。
+/// - `Err(CustomError)`：请求失败或写入失败时返回错误信息。
+#[frb]
+pub async fn picacg_subscription_add(comic_id: String) -> Result<Subscription, CustomError> {
+    let (info, eps) = futures::try_join!(
+        picacg_comic_info(comic_id.clone()),
+        picacg_comic_eps_all(comic_id)
+    )?;
+
+    save_subscription(&info, &eps).await
+}
+
+/// 检查所有订阅的漫画是否有新章节。
+///
+/// 对每部漫画先请求详情，只有更新时间与上次不同时才翻阅章节列表，
+/// 所有请求之间至少间隔 `SUBSCRIPTION_REQUEST_INTERVAL`。单部漫画检查失败不会中断其他漫画的检查。
+/// 检查到的新章节会保存下来，可以之后通过 `picacg_subscription_episodes` 获取，
+/// 因此也可以由后台任务调用，只需要先初始化存储目录并打开数据库。
+///
+/// # 返回
+/// - `Ok(SubscriptionCheckEntity)`：本次检查到的新章节和检查失败的订阅。
+/// - `Err(CustomError)`：读取订阅失败时返回错误信息。
+#[frb]
+pub async fn picacg_subscription_check() -> Result<SubscriptionCheckEntity, CustomError> {
+    let _check = SUBSCRIPTION_CHECK.lock().await;
+    let subscriptions = picacg_subscriptions().await?;
+    let mut limiter = RateLimiter::new(SUBSCRIPTION_REQUEST_INTERVAL);
+    let mut episodes = Vec::new();
+    let mut failures = Vec::new();
+
+    for subscription in &subscriptions {
+        match check_subscription(&mut limiter, subscription).await {
+            Ok(new_episodes) => episodes.extend(new_episodes),
+            Err(e) => failures.push(SubscriptionFailureEntity {
+                comic_id: subscription.comic_id.clone(),
+                title: subscription.title.clone(),
+                error_message: e.error_message,
+            }),
+        }
+    }
+
+    Ok(SubscriptionCheckEntity {
+        episodes,
+        failures,
+        checked: subscriptions.len() as i32,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::{
+        reqs::subscription::{is_new_episode, picacg_subscription_add},
+        storage::subscription_data::Subscription,
+        types::{comic_ep_entity::ComicEpEntity, image_entity::avatar_default},
+    };
+    use chrono::{Duration, Utc};
+
+    #[test]
+    fn test_is_new_episode() {
+        let now = Utc::now();
+        let subscription = Subscription {
+            comic_id: "a".to_string(),
+            title: String::new(),
+            thumb: avatar_default(),
+            comic_updated_at: now,
+            last_ep_order: 2,
+            last_ep_updated_at: Some(now),
+            created_at: now,
+            last_checked_at: None,
+            unseen_count: 0,
+        };
+        let ep = |order: i32, updated_at| ComicEpEntity {
+            id: String::new(),
+            title: String::new(),
+            order,
+            updated_at,
+        };
+
+        assert!(is_new_episode(&subscription, &ep(3, now)));
+        assert!(is_new_episode(
+            &subscription,
+            &ep(1, now + Duration::hours(1))
+        ));
+        assert!(!is_new_episode(&subscription, &ep(2, now)));
+    }
+
+    #[tokio::test]
+    async fn test_picacg_subscription_add() {
+        let result = picacg_subscription_add("5b6bdf4558ed442d899486b7".to_string()).await;
+        assert!(result.is_err());
+    }
+}
@@ -1,12 +1,33 @@
 use crate::api::{
     error::custom_error::{CustomError, CustomErrorType},
-    types::{login_entity::LoginEntity, profile_entity::ProfileEntity},
+    storage::config::CONFIG,
+    types::{
+        forgot_password_entity::{ForgotPasswordEntity, ForgotPasswordRequest},
+        login_entity::LoginEntity,
+        profile_entity::ProfileEntity,
+        register_entity::{RegisterEntity, RegisterField, RegisterFieldError, RegisterRequest},
+        reset_password_entity::{ResetPasswordEntity, ResetPasswordRequest},
+    },
     utils::{
         client::{picacg_request, HttpExpectBody},
+        image::encode_avatar_data_url,
         parse_json::parse_json_from_text,
+        validator::is_valid_account,
     },
 };
+use chrono::{Local, NaiveDate};
 use flutter_rust_bridge::frb;
+use std::{ops::RangeInclusive, sync::RwLock};
+
+/// 用户资料缓存和请求时使用的令牌，令牌变化后缓存失效。
+static PROFILE: RwLock<Option<(String, ProfileEntity)>> = RwLock::new(None);
+
+fn current_token() -> String {
+    CONFIG
+        .read()
+        .map(|config| config.user_data.token.clone())
+        .unwrap_or_default()
+}
 
 /// 登录到 Picacg 平台。
 ///
@@ -66,62 +87,139 @@ pub async fn picacg_user_login(
     )
 }
 
+pub const REGISTER_MIN_AGE: u32 = 18;
+pub const REGISTER_MIN_PASSWORD_LENGTH: usize = 8;
+pub const REGISTER_NAME_LENGTH: RangeInclusive<usize> = 2..=50;
+
+/// 校验注册请求，返回所有不合法的字段。
+///
+/// # 参数
+/// - `request`：注册请求。
+/// - `today`：用于计算年龄的当前日期。
+fn validate_register_request(
+    request: &RegisterRequest,
+    today: NaiveDate,
+) -> Vec<RegisterFieldError> {
+    let mut errors = Vec::new();
+    let mut reject = |field: RegisterField, message: &str| {
+        errors.push(RegisterFieldError {
+            field,
+            message: message.to_string(),
+        })
+    };
+
+    if request.email.is_empty() {
+        reject(RegisterField::Email, "Email cannot be empty");
+    } else if !is_valid_account(&request.email) {
+        reject(
+            RegisterField::Email,
+            "Email must be a valid email or phone number",
+        );
+    }
+
+    if request.password.is_empty() {
+        reject(RegisterField::Password, "Password cannot be empty");
+    } else if request.password.chars().count() < REGISTER_MIN_PASSWORD_LENGTH {
+        reject(
+            RegisterField::Password,
+            "Password must be at least 8 characters",
+        );
+    }
+
+    if request.name.trim().is_empty() {
+        reject(RegisterField::Name, "Name cannot be empty");
+    } else if !REGISTER_NAME_LENGTH.contains(&request.name.chars().count()) {
+        reject(
+            RegisterField::Name,
+            "Name must be between 2 and 50 characters",
+        );
+    }
+
+    match today.years_since(request.birthday) {
+        None => reject(RegisterField::Birthday, "Birthday cannot be in the future"),
+        Some(age) if age < REGISTER_MIN_AGE => {
+            reject(RegisterField::Birthday, "Must be at least 18 years old")
+        }
+        Some(_) => {}
+    }
+
+    for (field, value) in [
+        (RegisterField::Question1, &request.question1),
+        (RegisterField::Question2, &request.question2),
+        (RegisterField::Question3, &request.question3),
+    ] {
+        if value.trim().is_empty() {
+            reject(field, "Security question cannot be empty");
+        }
+    }
+
+    for (field, value) in [
+        (RegisterField::Answer1, &request.answer1),
+        (RegisterField::Answer2, &request.answer2),
+        (RegisterField::Answer3, &request.answer3),
+    ] {
+        if value.trim().is_empty() {
+            reject(field, "Security answer cannot be empty");
+        }
+    }
+
+    errors
+}
+
+/// 在客户端校验注册请求。
+///
+/// 与服务器的校验规则保持一致，逐字段返回所有问题，便于界面在对应输入框下提示。
+///
+/// # 参数
+/// - `request`：注册请求。
+///
+/// # 返回
+/// - 所有不合法字段的错误列表，为空表示校验通过。
+#[frb(sync)]
+pub fn picacg_user_register_validate(request: RegisterRequest) -> Vec<RegisterFieldError> {
+    validate_register_request(&request, Local::now().date_naive())
+}
+
 /// 注册到 Picacg 平台。
 ///
+/// 提交前会先进行客户端校验，校验失败时不会发起请求。
+///
 /// # 参数
-/// - `email`：用户邮箱。
-/// - `password`：用户密码。
-/// - `name`：用户名。
-/// - `birthday`：生日，格式为 yyyy-mm-dd。
-/// - `gender`：性别，可选值为 "m"、"f"、"bot"。
-/// - `answer1`：安全问题 1 的答案。
-/// - `answer2`：安全问题 2 的答案。
-/// - `answer3`：安全问题 3 的答案。
-/// - `question1`：安全问题 1。
-/// - `question2`：安全问题 2。
-/// - `question3`：安全问题 3。
+/// - `request`：注册请求，包含账号、密码、昵称、生日、性别及三组安全问题和答案。
 ///
 /// # 返回
-/// - `Ok(true)`：注册成功。
+/// - `Ok(RegisterEntity)`：注册成功，返回新建账号的 ID。
 /// - `Err(CustomError)`：注册失败，返回错误信息。
 ///
 /// # 错误
-/// - 当 API 响应非 200 状态码时，返回错误信息。
+/// - 当客户端校验失败时，返回校验错误，错误信息中逐行列出所有问题字段。
+/// - 当服务器拒绝注册（如账号已存在）时，返回校验错误及服务器给出的原因。
+/// - 当 API 请求失败时，返回请求错误。
+/// - 当响应中没有新账号的 ID 时，返回解析错误。
 /// - 当响应体不是文本格式时，返回错误。
 #[frb]
-#[allow(clippy::too_many_arguments)]
-pub async fn picacg_user_register(
-    email: String,
-    password: String,
-    name: String,
-    birthday: String,
-    gender: String,
-    answer1: String,
-    answer2: String,
-    answer3: String,
-    question1: String,
-    question2: String,
-    question3: String,
-) -> Result<bool, CustomError> {
+pub async fn picacg_user_register(request: RegisterRequest) -> Result<RegisterEntity, CustomError> {
+    let errors = validate_register_request(&request, Local::now().date_naive());
+    if !errors.is_empty() {
+        return Err(CustomError {
+            error_code: CustomErrorType::ValidationError,
+            error_message: errors
+                .iter()
+                .map(|e| format!("{:?}: {}", e.field, e.message))
+                .collect::<Vec<String>>()
+                .join("\n"),
+        });
+    }
+
+    let payload = serde_json::to_string(&request).map_err(|e| CustomError {
+        error_code: CustomErrorType::SerializeJsonError,
+        error_message: format!("Failed to serialize register request: {}", e),
+    })?;
+
     let response = picacg_request(
         "POST",
         "/auth/register",
-        Some(
-            serde_json::json!({
-                "email": email,
-                "password": password,
-                "name": name,
-                "birthday": birthday,
-                "gender": gender,
-                "answer1": answer1,
-                "answer2": answer2,
-                "answer3": answer3,
-                "question1": question1,
-                "question2": question2,
-                "question3": question3,
-            })
-            .to_string(),
-        ),
+        Some(payload),
         None,
         Some(HttpExpectBody::Text),
     )
@@ -133,11 +231,34 @@ pub async fn picacg_user_register(
 
     parse_json_from_text(
         response.body,
-        |_json| Ok(true),
+        parse_register_entity,
         "register api result expected text response".to_string(),
     )
 }
 
+/// 解析注册结果，新账号的 ID 可能在 `data.user` 或 `data` 中。
+///
+/// 响应中没有账号 ID 时视为格式错误，避免调用方把异常响应当作注册成功。
+fn parse_register_entity(json: serde_json::Value) -> Result<RegisterEntity, CustomError> {
+    let user = match &json["data"]["user"] {
+        serde_json::Value::Null => &json["data"],
+        user => user,
+    };
+
+    let entity: RegisterEntity = serde_json::from_value(user.clone()).map_err(|e| CustomError {
+        error_code: CustomErrorType::ParseJsonError,
+        error_message: format!("Failed to parse register entity: {}", e),
+    })?;
+    if entity.id.is_empty() {
+        return Err(CustomError {
+            error_code: CustomErrorType::ParseJsonError,
+            error_message: "Register response does not contain the account id".to_string(),
+        });
+    }
+
+    Ok(entity)
+}
+
 /// 获取 Picacg 平台的用户个人资料。
 ///
 /// # 返回
@@ -150,6 +271,19 @@ pub async fn picacg_user_register(
 /// - 当解析用户资料失败时，返回错误。
 #[frb]
 pub async fn picacg_user_profile() -> Result<ProfileEntity, CustomError> {
+    let token = current_token();
+    let profile = fetch_user_profile().await?;
+
+    *PROFILE.write().map_err(|_| CustomError {
+        error_code: CustomErrorType::LockError,
+        error_message: "Failed to acquire write lock on PROFILE".to_string(),
+    })? = Some((token, profile.clone()));
+
+    Ok(profile)
+}
+
+/// 请求用户个人资料，不更新缓存，用于查询当前登录以外的账号。
+pub(crate) async fn fetch_user_profile() -> Result<ProfileEntity, CustomError> {
     let response = picacg_request(
         "GET",
         "/users/profile",
@@ -175,6 +309,184 @@ pub async fn picacg_user_profile() -> Result<ProfileEntity, CustomError> {
     )
 }
 
+/// 获取最近一次成功请求到的用户个人资料缓存。
+///
+/// 缓存只对请求时登录的账号有效，登录、退出或令牌变化后返回 `None`。
+///
+/// # 返回
+/// - `Some(ProfileEntity)`：存在缓存时返回缓存的用户资料。
+/// - `None`：尚未请求过当前账号的用户资料，或缓存已失效。
+#[frb(sync)]
+pub fn picacg_user_cached_profile() -> Option<ProfileEntity> {
+    let token = current_token();
+    if token.is_empty() {
+        return None;
+    }
+
+    PROFILE
+        .read()
+        .ok()?
+        .as_ref()
+        .filter(|(cached_token, _)| *cached_token == token)
+        .map(|(_, profile)| profile.clone())
+}
+
+/// 在用户资料被修改后刷新缓存。
+///
+/// 刷新失败时清空缓存，避免继续返回过期的用户资料。
+async fn refresh_profile_cache() {
+    if picacg_user_profile().await.is_err() {
+        if let Ok(mut profile) = PROFILE.write() {
+            *profile = None;
+        }
+    }
+}
+
+/// 修改当前用户的登录密码。
+///
+/// # 参数
+/// - `old_password`：当前密码。
+/// - `new_password`：新密码，长度不少于 8 位。
+///
+/// # 返回
+/// - `Ok(true)`：修改成功。
+/// - `Err(CustomError)`：修改失败，返回错误信息。
+///
+/// # 错误
+/// - 当密码为空或新密码长度不足时，返回参数错误。
+/// - 当 API 响应非 200 状态码时，返回错误信息。
+/// - 当响应体不是文本格式时，返回错误。
+#[frb]
+pub async fn picacg_user_change_password(
+    old_password: String,
+    new_password: String,
+) -> Result<bool, CustomError> {
+    if old_password.is_empty() || new_password.is_empty() {
+        return Err(CustomError {
+            error_code: CustomErrorType::ParameterError,
+            error_message: "Old password or new password cannot be empty".to_string(),
+        });
+    }
+
+    if new_password.chars().count() < REGISTER_MIN_PASSWORD_LENGTH {
+        return Err(CustomError {
+            error_code: CustomErrorType::ParameterError,
+            error_message: format!(
+                "New password must be at least {} characters",
+                REGISTER_MIN_PASSWORD_LENGTH
+            ),
+        });
+    }
+
+    let response = picacg_request(
+        "PUT",
+        "/users/password",
+        Some(
+            serde_json::json!({
+                "old_password": old_password,
+                "new_password": new_password,
+            })
+            .to_string(),
+        ),
+        None,
+        Some(HttpExpectBody::Text),
+    )
+    .await
+    .map_err(|e| CustomError {
+        error_code: CustomErrorType::BadRequest,
+        error_message: format!("Failed to make request: {}", e),
+    })?;
+
+    parse_json_from_text(
+        response.body,
+        |_json| Ok(true),
+        "change password api result expected text response".to_string(),
+    )?;
+
+    refresh_profile_cache().await;
+    Ok(true)
+}
+
+/// 修改当前用户的个性签名。
+///
+/// # 参数
+/// - `slogan`：新的个性签名。
+///
+/// # 返回
+/// - `Ok(true)`：修改成功。
+/// - `Err(CustomError)`：修改失败，返回错误信息。
+///
+/// # 错误
+/// - 当 API 响应非 200 状态码时，返回错误信息。
+/// - 当响应体不是文本格式时，返回错误。
+#[frb]
+pub async fn picacg_user_update_slogan(slogan: String) -> Result<bool, CustomError> {
+    let response = picacg_request(
+        "PUT",
+        "/users/profile",
+        Some(serde_json::json!({ "slogan": slogan }).to_string()),
+        None,
+        Some(HttpExpectBody::Text),
+    )
+    .await
+    .map_err(|e| CustomError {
+        error_code: CustomErrorType::BadRequest,
+        error_message: format!("Failed to make request: {}", e),
+    })?;
+
+    parse_json_from_text(
+        response.body,
+        |_json| Ok(true),
+        "update slogan api result expected text response".to_string(),
+    )?;
+
+    refresh_profile_cache().await;
+    Ok(true)
+}
+
+/// 上传新的用户头像。
+///
+/// 图片会先被裁剪缩放为服务器要求的尺寸并重新编码为 JPEG，
+/// 再以 base64 data URL 的形式提交。
+///
+/// # 参数
+/// - `image`：原始图片数据。
+///
+/// # 返回
+/// - `Ok(true)`：上传成功。
+/// - `Err(CustomError)`：上传失败，返回错误信息。
+///
+/// # 错误
+/// - 当图片无法解码或编码时，返回图片处理错误。
+/// - 当 API 响应非 200 状态码时，返回错误信息。
+/// - 当响应体不是文本格式时，返回错误。
+#[frb]
+pub async fn picacg_user_update_avatar(image: Vec<u8>) -> Result<bool, CustomError> {
+    let avatar = encode_avatar_data_url(&image)?;
+
+    let response = picacg_request(
+        "PUT",
+        "/users/avatar",
+        Some(serde_json::json!({ "avatar": avatar }).to_string()),
+        None,
+        Some(HttpExpectBody::Text),
+    )
+    .await
+    .map_err(|e| CustomError {
+        error_code: CustomErrorType::BadRequest,
+        error_message: format!("Failed to make request: {}", e),
+    })?;
+
+    parse_json_from_text(
+        response.body,
+        |_json| Ok(true),
+        "update avatar api result expected text response".to_string(),
+    )?;
+
+    refresh_profile_cache().await;
+    Ok(true)
+}
+
 /// 用户每日签到（打卡）Picacg 平台。
 ///
 /// # 返回
@@ -206,11 +518,149 @@ pub async fn picacg_user_punch_in() -> Result<bool, CustomError> {
     )
 }
 
+/// 找回密码：获取指定账号注册时设置的安全问题。
+///
+/// # 参数
+/// - `request`：包含账号邮箱的请求实体。
+///
+/// # 返回
+/// - `Ok(ForgotPasswordEntity)`：获取成功，返回三个安全问题。
+/// - `Err(CustomError)`：获取失败，返回错误信息。
+///
+/// # 错误
+/// - 当账号不是合法的邮箱或手机号时，返回校验错误。
+/// - 当账号不存在等服务器校验失败时，返回校验错误。
+/// - 当 API 请求失败时，返回请求错误。
+/// - 当解析安全问题失败时，返回解析错误。
+#[frb]
+pub async fn picacg_user_forgot_password(
+    request: ForgotPasswordRequest,
+) -> Result<ForgotPasswordEntity, CustomError> {
+    if !is_valid_account(&request.email) {
+        return Err(CustomError {
+            error_code: CustomErrorType::ValidationError,
+            error_message: "Email must be a valid email or phone number".to_string(),
+        });
+    }
+
+    let payload = serde_json::to_string(&request).map_err(|e| CustomError {
+        error_code: CustomErrorType::SerializeJsonError,
+        error_message: format!("Failed to serialize forgot password request: {}", e),
+    })?;
+
+    let response = picacg_request(
+        "POST",
+        "/auth/forgot-password",
+        Some(payload),
+        None,
+        Some(HttpExpectBody::Text),
+    )
+    .await
+    .map_err(|e| CustomError {
+        error_code: CustomErrorType::BadRequest,
+        error_message: format!("Failed to make request: {}", e),
+    })?;
+
+    parse_json_from_text(
+        response.body,
+        |json| {
+            serde_json::from_value(json["data"].clone()).map_err(|e| CustomError {
+                error_code: CustomErrorType::ParseJsonError,
+                error_message: format!("Failed to parse forgot password entity: {}", e),
+            })
+        },
+        "forgot password api result expected text response".to_string(),
+    )
+}
+
+/// 找回密码：回答安全问题并重置密码。
+///
+/// # 参数
+/// - `request`：包含账号邮箱、安全问题序号及答案的请求实体。
+///
+/// # 返回
+/// - `Ok(ResetPasswordEntity)`：重置成功，返回服务器生成的新密码。
+/// - `Err(CustomError)`：重置失败，返回错误信息。
+///
+/// # 错误
+/// - 当账号不是合法的邮箱或手机号、问题序号不在 1~3 之间或答案为空时，返回校验错误。
+/// - 当答案错误等服务器校验失败时，返回校验错误。
+/// - 当 API 请求失败时，返回请求错误。
+/// - 当解析新密码失败时，返回解析错误。
+#[frb]
+pub async fn picacg_user_reset_password(
+    request: ResetPasswordRequest,
+) -> Result<ResetPasswordEntity, CustomError> {
+    if !is_valid_account(&request.email) {
+        return Err(CustomError {
+            error_code: CustomErrorType::ValidationError,
+            error_message: "Email must be a valid email or phone number".to_string(),
+        });
+    }
+
+    if !(1..=3).contains(&request.question_no) {
+        return Err(CustomError {
+            error_code: CustomErrorType::ValidationError,
+            error_message: "Question number must be between 1 and 3".to_string(),
+        });
+    }
+
+    if request.answer.trim().is_empty() {
+        return Err(CustomError {
+            error_code: CustomErrorType::ValidationError,
+            error_message: "Answer cannot be empty".to_string(),
+        });
+    }
+
+    let payload = serde_json::to_string(&request).map_err(|e| CustomError {
+        error_code: CustomErrorType::SerializeJsonError,
+        error_message: format!("Failed to serialize reset password request: {}", e),
+    })?;
+
+    let response = picacg_request(
+        "POST",
+        "/auth/reset-password",
+        Some(payload),
+        None,
+        Some(HttpExpectBody::Text),
+    )
+    .await
+    .map_err(|e| CustomError {
+        error_code: CustomErrorType::BadRequest,
+        error_message: format!("Failed to make request: {}", e),
+    })?;
+
+    parse_json_from_text(
+        response.body,
+        |json| {
+            serde_json::from_value(json["data"].clone()).map_err(|e| CustomError {
+                error_code: CustomErrorType::ParseJsonError,
+                error_message: format!("Failed to parse reset password entity: {}", e),
+            })
+        },
+        "reset password api result expected text response".to_string(),
+    )
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::api::reqs::user::{
-        picacg_user_login, picacg_user_profile, picacg_user_punch_in, picacg_user_register,
+    use crate::api::{
+        error::custom_error::CustomErrorType,
+        reqs::user::{
+            parse_register_entity, picacg_user_cached_profile, picacg_user_change_password,
+            picacg_user_forgot_password, picacg_user_login, picacg_user_profile,
+            picacg_user_punch_in, picacg_user_register, picacg_user_reset_password,
+            picacg_user_update_avatar, picacg_user_update_slogan, validate_register_request,
+            PROFILE,
+        },
+        types::{
+            forgot_password_entity::ForgotPasswordRequest,
+            profile_entity::ProfileEntity,
+            register_entity::{Gender, RegisterField, RegisterFieldError, RegisterRequest},
+            reset_password_entity::ResetPasswordRequest,
+        },
     };
+    use chrono::NaiveDate;
 
     #[tokio::test]
     async fn test_picacg_login() {
@@ -218,25 +668,111 @@ mod tests {
         assert!(result.is_err());
     }
 
+    fn register_request() -> RegisterRequest {
+        RegisterRequest {
+            email: "user@example.com".to_string(),
+            password: "password".to_string(),
+            name: "name".to_string(),
+            birthday: NaiveDate::from_ymd_opt(2000, 1, 1).unwrap(),
+            gender: Gender::Male,
+            question1: "question1".to_string(),
+            question2: "question2".to_string(),
+            question3: "question3".to_string(),
+            answer1: "answer1".to_string(),
+            answer2: "answer2".to_string(),
+            answer3: "answer3".to_string(),
+        }
+    }
+
     #[tokio::test]
     async fn test_picacg_register() {
-        let result = picacg_user_register(
-            "email".to_string(),
-            "password".to_string(),
-            "name".to_string(),
-            "2000-01-01".to_string(),
-            "m".to_string(),
-            "answer1".to_string(),
-            "answer2".to_string(),
-            "answer3".to_string(),
-            "question1".to_string(),
-            "question2".to_string(),
-            "question3".to_string(),
-        )
-        .await;
+        let result = picacg_user_register(register_request()).await;
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_parse_register_entity() {
+        let nested = serde_json::json!({"code": 200, "data": {"user": {"_id": "user-id"}}});
+        assert_eq!(parse_register_entity(nested).unwrap().id, "user-id");
+
+        let flat = serde_json::json!({"code": 200, "data": {"_id": "user-id"}});
+        assert_eq!(parse_register_entity(flat).unwrap().id, "user-id");
+
+        for json in [
+            serde_json::json!({"code": 200}),
+            serde_json::json!({"code": 200, "data": {"user": {}}}),
+            serde_json::json!({"code": 200, "data": {"_id": ""}}),
+        ] {
+            assert!(matches!(
+                parse_register_entity(json).map_err(|e| e.error_code),
+                Err(CustomErrorType::ParseJsonError)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_register_validate() {
+        let today = NaiveDate::from_ymd_opt(2024, 6, 1).unwrap();
+        assert!(validate_register_request(&register_request(), today).is_empty());
+
+        let request = RegisterRequest {
+            email: "email".to_string(),
+            password: "short".to_string(),
+            name: "n".to_string(),
+            birthday: NaiveDate::from_ymd_opt(2006, 6, 2).unwrap(),
+            question2: " ".to_string(),
+            answer3: String::new(),
+            ..register_request()
+        };
+        let fields: Vec<RegisterField> = validate_register_request(&request, today)
+            .into_iter()
+            .map(|e| e.field)
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                RegisterField::Email,
+                RegisterField::Password,
+                RegisterField::Name,
+                RegisterField::Birthday,
+                RegisterField::Question2,
+                RegisterField::Answer3,
+            ]
+        );
+
+        let request = RegisterRequest {
+            email: "+8613800138000".to_string(),
+            birthday: NaiveDate::from_ymd_opt(2006, 6, 1).unwrap(),
+            ..register_request()
+        };
+        assert!(validate_register_request(&request, today).is_empty());
+
+        let request = RegisterRequest {
+            birthday: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            ..register_request()
+        };
+        assert_eq!(
+            validate_register_request(&request, today),
+            vec![RegisterFieldError {
+                field: RegisterField::Birthday,
+                message: "Birthday cannot be in the future".to_string(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_picacg_register_invalid() {
+        let request = RegisterRequest {
+            email: "email".to_string(),
+            ..register_request()
+        };
+        let result = picacg_user_register(request).await;
+        assert!(matches!(
+            result.map_err(|e| e.error_code),
+            Err(CustomErrorType::ValidationError)
+        ));
+    }
+
     #[tokio::test]
     async fn test_picacg_user_profile() {
         let result = picacg_user_profile().await;
@@ -248,4 +784,100 @@ mod tests {
         let result = picacg_user_punch_in().await;
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_picacg_user_change_password() {
+        let result = picacg_user_change_password("password".to_string(), "short".to_string()).await;
+        assert!(result.is_err());
+
+        let result =
+            picacg_user_change_password("password".to_string(), "new_password".to_string()).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_picacg_user_update_slogan() {
+        let result = picacg_user_update_slogan("slogan".to_string()).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_picacg_user_update_avatar() {
+        let result = picacg_user_update_avatar(b"not an image".to_vec()).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_picacg_user_forgot_password() {
+        let result = picacg_user_forgot_password(ForgotPasswordRequest {
+            email: "email".to_string(),
+        })
+        .await;
+        assert!(matches!(
+            result.map_err(|e| e.error_code),
+            Err(CustomErrorType::ValidationError)
+        ));
+
+        let result = picacg_user_forgot_password(ForgotPasswordRequest {
+            email: "user@example.com".to_string(),
+        })
+        .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_picacg_user_reset_password() {
+        let result = picacg_user_reset_password(ResetPasswordRequest {
+            email: "user@example.com".to_string(),
+            question_no: 4,
+            answer: "answer".to_string(),
+        })
+        .await;
+        assert!(matches!(
+            result.map_err(|e| e.error_code),
+            Err(CustomErrorType::ValidationError)
+        ));
+
+        let result = picacg_user_reset_password(ResetPasswordRequest {
+            email: "user@example.com".to_string(),
+            question_no: 1,
+            answer: " ".to_string(),
+        })
+        .await;
+        assert!(matches!(
+            result.map_err(|e| e.error_code),
+            Err(CustomErrorType::ValidationError)
+        ));
+
+        let result = picacg_user_reset_password(ResetPasswordRequest {
+            email: "user@example.com".to_string(),
+            question_no: 1,
+            answer: "answer".to_string(),
+        })
+        .await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cached_profile_bound_to_token() {
+        let profile: ProfileEntity = serde_json::from_value(serde_json::json!({
+            "_id": "cached",
+            "gender": "bot",
+            "name": "name",
+            "title": "title",
+            "verified": false,
+            "exp": 0,
+            "level": 1,
+            "characters": [],
+            "birthday": "2000-01-01",
+            "email": "cached@example.com",
+            "created_at": "2024-01-01T00:00:00Z",
+            "isPunched": false,
+        }))
+        .unwrap();
+
+        // 其他令牌请求到的资料不会作为当前账号的资料返回
+        *PROFILE.write().unwrap() = Some(("previous_account_token".to_string(), profile));
+        assert!(picacg_user_cached_profile().is_none());
+    }
 }
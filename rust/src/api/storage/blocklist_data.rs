@@ -0,0 +1,464 @@
+use crate::api::{
+    error::custom_error::{CustomError, CustomErrorType},
+    storage::{
+        comic_index_data::IndexedComic,
+        database::database_id,
+        setting_data::{get_setting, set_setting},
+    },
+    types::{
+        comic_entity::ComicEntity, comic_info_entity::ComicInfoEntity,
+        comic_search_entity::ComicSearchEntity,
+    },
+    utils::chinese::normalize_chinese,
+};
+use flutter_rust_bridge::frb;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashSet,
+    sync::{Arc, RwLock},
+};
+
+pub const BLOCKLIST_SETTING_KEY: &str = "blocklist";
+
+/// 作者字段中多位作者之间的分隔符。
+const AUTHOR_SEPARATORS: &[char] = &[',', '，', '、', '&', '/'];
+
+/// 编译后的屏蔽列表，以及加载时数据库的编号。
+static BLOCKLIST: RwLock<Option<(u64, Arc<BlocklistMatcher>)>> = RwLock::new(None);
+
+/// 屏蔽列表。
+///
+/// 标签、分类、作者、汉化组和标题关键词不区分大小写，正则表达式同样忽略大小写。
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct Blocklist {
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub categories: Vec<String>,
+    #[serde(default)]
+    pub authors: Vec<String>,
+    #[serde(default)]
+    pub chinese_teams: Vec<String>,
+    /// 创作者 ID，只对带有创作者信息的漫画或按创作者筛选的列表生效。
+    #[serde(default)]
+    pub creators: Vec<String>,
+    /// 标题包含任意一个关键词时屏蔽。
+    #[serde(default)]
+    pub title_keywords: Vec<String>,
+    /// 标题匹配任意一个正则表达式时屏蔽。
+    #[serde(default)]
+    pub title_regexes: Vec<String>,
+    /// 屏蔽后一页的数量不足时，是否继续请求后续页面补足。
+    #[serde(default)]
+    pub fill_page: bool,
+}
+
+/// 可以被屏蔽列表过滤的漫画。
+pub trait Blockable {
+    fn block_title(&self) -> &str;
+    fn block_author(&self) -> &str;
+    fn block_categories(&self) -> &[String];
+    fn block_tags(&self) -> &[String];
+    /// 没有汉化组信息时返回空字符串。
+    fn block_chinese_team(&self) -> &str;
+    /// 没有创作者信息时返回 `None`。
+    fn block_creator_id(&self) -> Option<&str>;
+}
+
+impl Blockable for ComicEntity {
+    fn block_title(&self) -> &str {
+        &self.title
+    }
+
+    fn block_author(&self) -> &str {
+        &self.author
+    }
+
+    fn block_categories(&self) -> &[String] {
+        &self.categories
+    }
+
+    fn block_tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    fn block_chinese_team(&self) -> &str {
+        ""
+    }
+
+    fn block_creator_id(&self) -> Option<&str> {
+        None
+    }
+}
+
+impl Blockable for ComicSearchEntity {
+    fn block_title(&self) -> &str {
+        &self.title
+    }
+
+    fn block_author(&self) -> &str {
+        &self.author
+    }
+
+    fn block_categories(&self) -> &[String] {
+        &self.categories
+    }
+
+    fn block_tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    fn block_chinese_team(&self) -> &str {
+        &self.chinese_team
+    }
+
+    fn block_creator_id(&self) -> Option<&str> {
+        None
+    }
+}
+
+impl Blockable for ComicInfoEntity {
+    fn block_title(&self) -> &str {
+        &self.title
+    }
+
+    fn block_author(&self) -> &str {
+        &self.author
+    }
+
+    fn block_categories(&self) -> &[String] {
+        &self.categories
+    }
+
+    fn block_tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    fn block_chinese_team(&self) -> &str {
+        &self.chinese_team
+    }
+
+    fn block_creator_id(&self) -> Option<&str> {
+        Some(&self.creator.id)
+    }
+}
+
+impl Blockable for IndexedComic {
+    fn block_title(&self) -> &str {
+        &self.title
+    }
+
+    fn block_author(&self) -> &str {
+        &self.author
+    }
+
+    fn block_categories(&self) -> &[String] {
+        &self.categories
+    }
+
+    fn block_tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    fn block_chinese_team(&self) -> &str {
+        &self.chinese_team
+    }
+
+    fn block_creator_id(&self) -> Option<&str> {
+        (!self.creator_id.is_empty()).then_some(self.creator_id.as_str())
+    }
+}
+
+/// 忽略首尾空白、大小写和简繁写法。
+fn normalize(value: &str) -> String {
+    normalize_chinese(value.trim())
+}
+
+fn normalize_set(values: &[String]) -> HashSet<String> {
+    values
+        .iter()
+        .map(|value| normalize(value))
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
+/// 编译后的屏蔽列表，默认值不屏蔽任何漫画。
+#[derive(Default)]
+pub struct BlocklistMatcher {
+    tags: HashSet<String>,
+    categories: HashSet<String>,
+    authors: HashSet<String>,
+    chinese_teams: HashSet<String>,
+    creators: HashSet<String>,
+    title_keywords: Vec<String>,
+    title_regexes: Vec<Regex>,
+    fill_page: bool,
+}
+
+impl BlocklistMatcher {
+    /// 编译屏蔽列表，正则表达式无效时返回 `ValidationError`。
+    pub fn new(blocklist: &Blocklist) -> Result<Self, CustomError> {
+        let mut title_regexes = Vec::new();
+        let mut errors = Vec::new();
+        for pattern in blocklist.title_regexes.iter().filter(|p| !p.is_empty()) {
+            match RegexBuilder::new(pattern).case_insensitive(true).build() {
+                Ok(regex) => title_regexes.push(regex),
+                Err(e) => errors.push(format!("{}: {}", pattern, e)),
+            }
+        }
+        if !errors.is_empty() {
+            return Err(CustomError {
+                error_code: CustomErrorType::ValidationError,
+                error_message: errors.join("\n"),
+            });
+        }
+
+        Ok(BlocklistMatcher {
+            tags: normalize_set(&blocklist.tags),
+            categories: normalize_set(&blocklist.categories),
+            authors: normalize_set(&blocklist.authors),
+            chinese_teams: normalize_set(&blocklist.chinese_teams),
+            creators: blocklist
+                .creators
+                .iter()
+                .map(|creator| creator.trim().to_string())
+                .filter(|creator| !creator.is_empty())
+                .collect(),
+            title_keywords: normalize_set(&blocklist.title_keywords)
+                .into_iter()
+                .collect(),
+            title_regexes,
+            fill_page: blocklist.fill_page,
+        })
+    }
+
+    /// 屏蔽后一页的数量不足时，是否继续请求后续页面补足。
+    pub fn fill_page(&self) -> bool {
+        self.fill_page
+    }
+
+    pub fn is_creator_blocked(&self, creator_id: &str) -> bool {
+        self.creators.contains(creator_id.trim())
+    }
+
+    pub fn is_blocked<T: Blockable>(&self, comic: &T) -> bool {
+        let contains_any = |set: &HashSet<String>, values: &[String]| {
+            values.iter().any(|v| set.contains(&normalize(v)))
+        };
+
+        if contains_any(&self.tags, comic.block_tags())
+            || contains_any(&self.categories, comic.block_categories())
+        {
+            return true;
+        }
+
+        let author = normalize(comic.block_author());
+        if !author.is_empty()
+            && (self.authors.contains(&author)
+                || author
+                    .split(AUTHOR_SEPARATORS)
+                    .any(|author| self.authors.contains(author.trim())))
+        {
+            return true;
+        }
+
+        let chinese_team = normalize(comic.block_chinese_team());
+        if !chinese_team.is_empty() && self.chinese_teams.contains(&chinese_team) {
+            return true;
+        }
+
+        if comic
+            .block_creator_id()
+            .is_some_and(|creator_id| self.is_creator_blocked(creator_id))
+        {
+            return true;
+        }
+
+        let title = normalize(comic.block_title());
+        self.title_keywords
+            .iter()
+            .any(|keyword| title.contains(keyword.as_str()))
+            || self
+                .title_regexes
+                .iter()
+                .any(|regex| regex.is_match(comic.block_title()))
+    }
+
+    /// 移除被屏蔽的漫画，返回移除的数量。
+    pub fn retain<T: Blockable>(&self, comics: &mut Vec<T>) -> i32 {
+        let len = comics.len();
+        comics.retain(|comic| !self.is_blocked(comic));
+        (len - comics.len()) as i32
+    }
+}
+
+fn cache_blocklist(id: u64, matcher: Arc<BlocklistMatcher>) -> Result<(), CustomError> {
+    *BLOCKLIST.write().map_err(|_| CustomError {
+        error_code: CustomErrorType::LockError,
+        error_message: "Failed to acquire write lock on BLOCKLIST".to_string(),
+    })? = Some((id, matcher));
+    Ok(())
+}
+
+/// 获取编译后的屏蔽列表。
+///
+/// 第一次调用或重新打开数据库后从数据库加载；无法加载时（例如数据库还没有打开）
+/// 不屏蔽任何漫画，避免漫画列表因为屏蔽列表而无法加载。
+pub async fn blocklist_matcher() -> Arc<BlocklistMatcher> {
+    load_blocklist_matcher().await.unwrap_or_default()
+}
+
+async fn load_blocklist_matcher() -> Result<Arc<BlocklistMatcher>, CustomError> {
+    let id = database_id()?;
+    let cached = BLOCKLIST
+        .read()
+        .map_err(|_| CustomError {
+            error_code: CustomErrorType::LockError,
+            error_message: "Failed to acquire read lock on BLOCKLIST".to_string(),
+        })?
+        .clone();
+    if let Some((cached_id, matcher)) = cached {
+        if cached_id == id {
+            return Ok(matcher);
+        }
+    }
+
+    let blocklist = picacg_blocklist().await?;
+    let matcher = Arc::new(BlocklistMatcher::new(&blocklist)?);
+    cache_blocklist(id, matcher.clone())?;
+    Ok(matcher)
+}
+
+/// 获取屏蔽列表。
+#[frb]
+pub async fn picacg_blocklist() -> Result<Blocklist, CustomError> {
+    Ok(get_setting::<Blocklist>(BLOCKLIST_SETTING_KEY)
+        .await?
+        .unwrap_or_default())
+}
+
+/// 保存屏蔽列表，之后的漫画列表、搜索、随机和收藏结果都会按新的屏蔽列表过滤。
+///
+/// 各项会去除首尾空白、空值和重复值。
+///
+/// # 返回
+/// - `Ok(Blocklist)`：保存后的屏蔽列表。
+/// - `Err(CustomError)`：正则表达式无效时返回 `ValidationError`。
+#[frb]
+pub async fn picacg_blocklist_set(blocklist: Blocklist) -> Result<Blocklist, CustomError> {
+    let clean = |values: Vec<String>| {
+        let mut seen = HashSet::new();
+        values
+            .into_iter()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty() && seen.insert(value.clone()))
+            .collect::<Vec<String>>()
+    };
+    let blocklist = Blocklist {
+        tags: clean(blocklist.tags),
+        categories: clean(blocklist.categories),
+        authors: clean(blocklist.authors),
+        chinese_teams: clean(blocklist.chinese_teams),
+        creators: clean(blocklist.creators),
+        title_keywords: clean(blocklist.title_keywords),
+        title_regexes: clean(blocklist.title_regexes),
+        fill_page: blocklist.fill_page,
+    };
+
+    let matcher = Arc::new(BlocklistMatcher::new(&blocklist)?);
+    set_setting(BLOCKLIST_SETTING_KEY, &blocklist).await?;
+    cache_blocklist(database_id()?, matcher)?;
+
+    Ok(blocklist)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::{
+        error::custom_error::CustomErrorType,
+        storage::{
+            blocklist_data::{
+                blocklist_matcher, picacg_blocklist, picacg_blocklist_set, Blocklist,
+                BlocklistMatcher,
+            },
+            database::open_test_database,
+        },
+        test_support::{comic_fixture, comic_info_fixture},
+    };
+
+    #[test]
+    fn test_blocklist_matcher() {
+        let matcher = BlocklistMatcher::new(&Blocklist {
+            tags: vec!["NTR".to_string()],
+            categories: vec!["重口地帶".to_string()],
+            authors: vec!["Blocked".to_string()],
+            chinese_teams: vec!["team".to_string()],
+            creators: vec!["creator".to_string()],
+            title_keywords: vec!["Keyword".to_string()],
+            title_regexes: vec![r"^\[.*\]$".to_string()],
+            fill_page: false,
+        })
+        .unwrap();
+
+        let mut comics = (0..6)
+            .map(|i| comic_fixture(&i.to_string()))
+            .collect::<Vec<_>>();
+        comics[1].tags = vec!["ntr".to_string()];
+        comics[2].categories = vec!["重口地带".to_string()];
+        comics[3].author = "someone, blocked".to_string();
+        comics[4].title = "has KEYWORD inside".to_string();
+        comics[5].title = "[bracket]".to_string();
+
+        assert_eq!(matcher.retain(&mut comics), 5);
+        assert_eq!(comics.len(), 1);
+        assert_eq!(comics[0].id, "0");
+
+        let mut info = comic_info_fixture("info");
+        assert!(!matcher.is_blocked(&info));
+        info.chinese_team = "Team".to_string();
+        assert!(matcher.is_blocked(&info));
+        info.chinese_team = String::new();
+        info.creator.id = "creator".to_string();
+        assert!(matcher.is_blocked(&info));
+
+        let result = BlocklistMatcher::new(&Blocklist {
+            title_regexes: vec!["(".to_string()],
+            ..Default::default()
+        });
+        assert!(matches!(
+            result.map(|_| ()).map_err(|e| e.error_code),
+            Err(CustomErrorType::ValidationError)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_blocklist_matcher_without_database() {
+        // 数据库没有打开时不屏蔽任何漫画
+        let mut comics = vec![comic_fixture("comic")];
+        assert_eq!(blocklist_matcher().await.retain(&mut comics), 0);
+        assert_eq!(comics.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_blocklist_save_read() {
+        open_test_database().await;
+
+        let blocklist = picacg_blocklist_set(Blocklist {
+            tags: vec![" tag ".to_string(), "tag".to_string(), String::new()],
+            fill_page: true,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        assert_eq!(blocklist.tags, vec!["tag".to_string()]);
+
+        let blocklist = picacg_blocklist().await.unwrap();
+        assert_eq!(blocklist.tags, vec!["tag".to_string()]);
+        assert!(blocklist.fill_page);
+
+        picacg_blocklist_set(Blocklist::default()).await.unwrap();
+    }
+}
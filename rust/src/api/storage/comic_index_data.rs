@@ -0,0 +1,564 @@
+use crate::api::{
+    error::custom_error::CustomError,
+    storage::{
+        blocklist_data::blocklist_matcher,
+        database::{json_column, to_json, with_connection},
+    },
+    types::{
+        comic_entity::ComicEntity,
+        comic_info_entity::ComicInfoEntity,
+        comic_search_entity::ComicSearchEntity,
+        image_entity::ImageEntity,
+        page_data::{IndexedComicPageData, PageData},
+    },
+    utils::{chinese::normalize_chinese, pinyin::pinyin},
+};
+use chrono::{DateTime, Utc};
+use flutter_rust_bridge::frb;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+
+/// 拼音词条最多包含的字数，超出的部分只能通过之后开始的词条匹配。
+const MAX_PINYIN_TOKEN_CHARS: usize = 12;
+
+const INDEXED_COMIC_COLUMNS: &str = "comic_index.comic_id, comic_index.title, comic_index.author,
+    comic_index.description, comic_index.categories, comic_index.tags, comic_index.chinese_team,
+    comic_index.creator_id, comic_index.creator_name, comic_index.thumb, comic_index.created_at,
+    comic_index.updated_at";
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct IndexedComic {
+    pub comic_id: String,
+    pub title: String,
+    pub author: String,
+    /// 只有看过漫画详情或搜索结果后才有简介。
+    pub description: String,
+    pub categories: Vec<String>,
+    pub tags: Vec<String>,
+    pub chinese_team: String,
+    pub creator_id: String,
+    pub creator_name: String,
+    pub thumb: ImageEntity,
+    /// 第一次被索引的时间。
+    pub created_at: DateTime<Utc>,
+    /// 最近一次被索引的时间。
+    pub updated_at: DateTime<Utc>,
+}
+
+/// 待写入索引的漫画，`None` 表示来源中没有该字段，保留索引中已有的值。
+#[frb(ignore)]
+pub struct ComicIndexDocument {
+    comic_id: String,
+    title: String,
+    author: String,
+    description: Option<String>,
+    categories: Vec<String>,
+    tags: Vec<String>,
+    chinese_team: Option<String>,
+    creator: Option<(String, String)>,
+    thumb: ImageEntity,
+}
+
+impl From<&ComicEntity> for ComicIndexDocument {
+    fn from(comic: &ComicEntity) -> Self {
+        ComicIndexDocument {
+            comic_id: comic.id.clone(),
+            title: comic.title.clone(),
+            author: comic.author.clone(),
+            description: None,
+            categories: comic.categories.clone(),
+            tags: comic.tags.clone(),
+            chinese_team: None,
+            creator: None,
+            thumb: comic.thumb.clone(),
+        }
+    }
+}
+
+impl From<&ComicSearchEntity> for ComicIndexDocument {
+    fn from(comic: &ComicSearchEntity) -> Self {
+        ComicIndexDocument {
+            comic_id: comic.id.clone(),
+            title: comic.title.clone(),
+            author: comic.author.clone(),
+            description: Some(comic.description.clone()),
+            categories: comic.categories.clone(),
+            tags: comic.tags.clone(),
+            chinese_team: Some(comic.chinese_team.clone()),
+            creator: None,
+            thumb: comic.thumb.clone(),
+        }
+    }
+}
+
+impl From<&ComicInfoEntity> for ComicIndexDocument {
+    fn from(comic: &ComicInfoEntity) -> Self {
+        ComicIndexDocument {
+            comic_id: comic.id.clone(),
+            title: comic.title.clone(),
+            author: comic.author.clone(),
+            description: Some(comic.description.clone()),
+            categories: comic.categories.clone(),
+            tags: comic.tags.clone(),
+            chinese_team: Some(comic.chinese_team.clone()),
+            creator: Some((comic.creator.id.clone(), comic.creator.name.clone())),
+            thumb: comic.thumb.clone(),
+        }
+    }
+}
+
+fn indexed_comic_from_row(row: &Row<'_>) -> rusqlite::Result<IndexedComic> {
+    Ok(IndexedComic {
+        comic_id: row.get(0)?,
+        title: row.get(1)?,
+        author: row.get(2)?,
+        description: row.get(3)?,
+        categories: json_column(row, 4)?,
+        tags: json_column(row, 5)?,
+        chinese_team: row.get(6)?,
+        creator_id: row.get(7)?,
+        creator_name: row.get(8)?,
+        thumb: json_column(row, 9)?,
+        created_at: row.get(10)?,
+        updated_at: row.get(11)?,
+    })
+}
+
+/// 中文、日文假名和韩文按字切分，其他文字按单词切分。
+fn is_cjk(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x3040..=0x30FF | 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xAC00..=0xD7AF | 0xF900..=0xFAFF
+    )
+}
+
+enum Segment {
+    Word(String),
+    Cjk(Vec<char>),
+}
+
+/// 将文本转为简体和小写后切分为单词和连续的中日韩文字，丢弃标点和空白。
+fn segments(text: &str) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut word = String::new();
+    let mut cjk = Vec::new();
+
+    for c in normalize_chinese(text).chars() {
+        let cjk_char = is_cjk(c);
+        if (cjk_char || !c.is_alphanumeric()) && !word.is_empty() {
+            segments.push(Segment::Word(std::mem::take(&mut word)));
+        }
+        if !cjk_char && !cjk.is_empty() {
+            segments.push(Segment::Cjk(std::mem::take(&mut cjk)));
+        }
+
+        if cjk_char {
+            cjk.push(c);
+        } else if c.is_alphanumeric() {
+            word.push(c);
+        }
+    }
+    if !word.is_empty() {
+        segments.push(Segment::Word(word));
+    }
+    if !cjk.is_empty() {
+        segments.push(Segment::Cjk(cjk));
+    }
+
+    segments
+}
+
+/// 索引使用的词：单词保持不变，中日韩文字同时索引单字和相邻的两个字。
+fn index_tokens(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    for segment in segments(text) {
+        match segment {
+            Segment::Word(word) => tokens.push(word),
+            Segment::Cjk(chars) => {
+                tokens.extend(chars.iter().map(char::to_string));
+                tokens.extend(chars.windows(2).map(|pair| pair.iter().collect()));
+            }
+        }
+    }
+    tokens
+}
+
+/// 拼音词：从每个汉字开始的连续全拼和首字母，用前缀查询匹配任意位置开始的拼音。
+fn pinyin_tokens(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    for segment in segments(text) {
+        let Segment::Cjk(chars) = segment else {
+            continue;
+        };
+
+        for run in chars
+            .iter()
+            .map(|c| pinyin(*c))
+            .collect::<Vec<_>>()
+            .split(Option::is_none)
+        {
+            let syllables = run.iter().flatten().collect::<Vec<_>>();
+            for start in 0..syllables.len() {
+                let end = (start + MAX_PINYIN_TOKEN_CHARS).min(syllables.len());
+                let syllables = &syllables[start..end];
+                tokens.push(syllables.iter().map(|s| **s).collect());
+                if syllables.len() > 1 {
+                    tokens.push(syllables.iter().filter_map(|s| s.chars().next()).collect());
+                }
+            }
+        }
+    }
+    tokens
+}
+
+fn join_tokens<'a>(
+    texts: impl IntoIterator<Item = &'a str>,
+    tokenize: fn(&str) -> Vec<String>,
+) -> String {
+    texts
+        .into_iter()
+        .flat_map(tokenize)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 将搜索内容转换为 FTS5 查询，所有词都要匹配，单词按前缀匹配。
+///
+/// 没有可以搜索的词时返回 `None`。
+fn match_query(query: &str) -> Option<String> {
+    let mut terms = Vec::new();
+    for segment in segments(query) {
+        match segment {
+            Segment::Word(word) => terms.push(format!("\"{}\"*", word)),
+            Segment::Cjk(chars) if chars.len() == 1 => terms.push(format!("\"{}\"", chars[0])),
+            Segment::Cjk(chars) => terms.extend(
+                chars
+                    .windows(2)
+                    .map(|pair| format!("\"{}\"", pair.iter().collect::<String>())),
+            ),
+        }
+    }
+
+    (!terms.is_empty()).then(|| terms.join(" AND "))
+}
+
+fn existing_comic(
+    connection: &Connection,
+    comic_id: &str,
+) -> Result<Option<(i64, IndexedComic)>, CustomError> {
+    Ok(connection
+        .query_row(
+            &format!(
+                "SELECT {}, comic_index.id FROM comic_index WHERE comic_id = ?1",
+                INDEXED_COMIC_COLUMNS
+            ),
+            params![comic_id],
+            // id 放在最后，前面的列与其他查询相同
+            |row| Ok((row.get(12)?, indexed_comic_from_row(row)?)),
+        )
+        .optional()?)
+}
+
+fn write_comic(
+    connection: &Connection,
+    document: ComicIndexDocument,
+    now: DateTime<Utc>,
+) -> Result<(), CustomError> {
+    let existing = existing_comic(connection, &document.comic_id)?;
+    let (description, chinese_team, creator_id, creator_name) = match &existing {
+        Some((_, comic)) => (
+            document.description.unwrap_or(comic.description.clone()),
+            document.chinese_team.unwrap_or(comic.chinese_team.clone()),
+            document
+                .creator
+                .clone()
+                .map_or(comic.creator_id.clone(), |creator| creator.0),
+            document
+                .creator
+                .map_or(comic.creator_name.clone(), |creator| creator.1),
+        ),
+        None => {
+            let (creator_id, creator_name) = document.creator.unwrap_or_default();
+            (
+                document.description.unwrap_or_default(),
+                document.chinese_team.unwrap_or_default(),
+                creator_id,
+                creator_name,
+            )
+        }
+    };
+
+    let id = match existing {
+        Some((id, _)) => {
+            connection.execute(
+                "UPDATE comic_index SET title = ?2, author = ?3, description = ?4, categories = ?5,
+                    tags = ?6, chinese_team = ?7, creator_id = ?8, creator_name = ?9, thumb = ?10,
+                    updated_at = ?11
+                 WHERE id = ?1",
+                params![
+                    id,
+                    document.title,
+                    document.author,
+                    description,
+                    to_json(&document.categories)?,
+                    to_json(&document.tags)?,
+                    chinese_team,
+                    creator_id,
+                    creator_name,
+                    to_json(&document.thumb)?,
+                    now
+                ],
+            )?;
+            connection.execute("DELETE FROM comic_fts WHERE rowid = ?1", params![id])?;
+            id
+        }
+        None => {
+            connection.execute(
+                "INSERT INTO comic_index (comic_id, title, author, description, categories, tags,
+                    chinese_team, creator_id, creator_name, thumb, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?11)",
+                params![
+                    document.comic_id,
+                    document.title,
+                    document.author,
+                    description,
+                    to_json(&document.categories)?,
+                    to_json(&document.tags)?,
+                    chinese_team,
+                    creator_id,
+                    creator_name,
+                    to_json(&document.thumb)?,
+                    now
+                ],
+            )?;
+            connection.last_insert_rowid()
+        }
+    };
+
+    let tags = document
+        .tags
+        .iter()
+        .chain(document.categories.iter())
+        .map(String::as_str);
+    connection.execute(
+        "INSERT INTO comic_fts (rowid, title, author, tags, people, description, pinyin)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            id,
+            join_tokens([document.title.as_str()], index_tokens),
+            join_tokens([document.author.as_str()], index_tokens),
+            join_tokens(tags.clone(), index_tokens),
+            join_tokens([chinese_team.as_str(), creator_name.as_str()], index_tokens),
+            join_tokens([description.as_str()], index_tokens),
+            join_tokens(
+                [document.title.as_str(), document.author.as_str()]
+                    .into_iter()
+                    .chain(tags),
+                pinyin_tokens
+            ),
+        ],
+    )?;
+
+    Ok(())
+}
+
+/// 将漫画写入索引，已有的漫画合并新的字段。
+#[frb(ignore)]
+pub async fn index_comics(documents: Vec<ComicIndexDocument>) -> Result<(), CustomError> {
+    if documents.is_empty() {
+        return Ok(());
+    }
+
+    with_connection(move |connection| {
+        let now = Utc::now();
+        let transaction = connection.transaction()?;
+        for document in documents {
+            write_comic(&transaction, document, now)?;
+        }
+        transaction.commit()?;
+        Ok(())
+    })
+    .await
+}
+
+/// 在后台将请求到的漫画写入索引，写入失败不影响调用方。
+#[frb(ignore)]
+pub fn index_in_background<'a, T: 'a>(comics: impl IntoIterator<Item = &'a T>)
+where
+    &'a T: Into<ComicIndexDocument>,
+{
+    let documents = comics.into_iter().map(Into::into).collect::<Vec<_>>();
+    tokio::spawn(async move {
+        let _ = index_comics(documents).await;
+    });
+}
+
+/// 在本地索引中搜索看到过的漫画，不需要网络。
+///
+/// 索引包含浏览、搜索和查看详情时请求到的所有漫画，匹配标题、作者、简介、标签、分类、
+/// 汉化组和上传者，忽略大小写和简繁写法。标题、作者和标签还可以用全拼或首字母搜索，
+/// 如 `jinji` 或 `jjdjr`。结果按相关度排序，标题匹配的权重最高，并按屏蔽列表过滤。
+///
+/// # 参数
+/// - `query`: 搜索内容，以空白分隔的多个词需要全部匹配。
+/// - `page`: 页码，从 1 开始。
+/// - `limit`: 每页数量。
+#[frb]
+pub async fn picacg_index_search(
+    query: String,
+    page: i32,
+    limit: i32,
+) -> Result<IndexedComicPageData, CustomError> {
+    let Some(query) = match_query(&query) else {
+        return Ok(IndexedComicPageData::from(PageData::paginate(
+            Vec::new(),
+            page,
+            limit,
+        )));
+    };
+
+    let mut comics = with_connection(move |connection| {
+        Ok(connection
+            .prepare(&format!(
+                "SELECT {} FROM comic_fts JOIN comic_index ON comic_index.id = comic_fts.rowid
+                 WHERE comic_fts MATCH ?1
+                 ORDER BY bm25(comic_fts, 10.0, 6.0, 4.0, 3.0, 1.0, 2.0),
+                    comic_index.updated_at DESC",
+                INDEXED_COMIC_COLUMNS
+            ))?
+            .query_map(params![query], indexed_comic_from_row)?
+            .collect::<Result<Vec<IndexedComic>, _>>()?)
+    })
+    .await?;
+
+    let hidden = blocklist_matcher().await.retain(&mut comics);
+    Ok(IndexedComicPageData {
+        hidden,
+        ..IndexedComicPageData::from(PageData::paginate(comics, page, limit))
+    })
+}
+
+/// 获取索引中的漫画数量。
+#[frb]
+pub async fn picacg_index_count() -> Result<i32, CustomError> {
+    with_connection(|connection| {
+        Ok(connection.query_row("SELECT COUNT(*) FROM comic_index", [], |row| row.get(0))?)
+    })
+    .await
+}
+
+/// 从索引中删除一部漫画。
+#[frb]
+pub async fn picacg_index_delete(comic_id: String) -> Result<(), CustomError> {
+    with_connection(move |connection| {
+        let transaction = connection.transaction()?;
+        transaction.execute(
+            "DELETE FROM comic_fts WHERE rowid IN (SELECT id FROM comic_index WHERE comic_id = ?1)",
+            params![comic_id],
+        )?;
+        transaction.execute(
+            "DELETE FROM comic_index WHERE comic_id = ?1",
+            params![comic_id],
+        )?;
+        transaction.commit()?;
+        Ok(())
+    })
+    .await
+}
+
+/// 清空索引。
+#[frb]
+pub async fn picacg_index_clear() -> Result<(), CustomError> {
+    with_connection(|connection| {
+        let transaction = connection.transaction()?;
+        transaction.execute("DELETE FROM comic_fts", [])?;
+        transaction.execute("DELETE FROM comic_index", [])?;
+        transaction.commit()?;
+        Ok(())
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::{
+        storage::{
+            comic_index_data::{
+                index_comics, index_tokens, match_query, picacg_index_delete, picacg_index_search,
+                pinyin_tokens, ComicIndexDocument,
+            },
+            database::open_test_database,
+        },
+        test_support::{comic_fixture, comic_info_fixture, comic_search_fixture},
+    };
+
+    #[test]
+    fn test_tokens() {
+        assert_eq!(
+            index_tokens("進擊的 Titan!"),
+            vec!["进", "击", "的", "进击", "击的", "titan"]
+        );
+        assert_eq!(pinyin_tokens("巨人A"), vec!["juren", "jr", "ren"]);
+        assert_eq!(
+            match_query("巨人 Titan 的").as_deref(),
+            Some("\"巨人\" AND \"titan\"* AND \"的\"")
+        );
+        assert_eq!(match_query(" ,. "), None);
+    }
+
+    #[tokio::test]
+    async fn test_index_search() {
+        open_test_database().await;
+
+        let mut comic = comic_fixture("index_1");
+        comic.title = "進擊的巨人 索引測試".to_string();
+        comic.tags = vec!["全彩".to_string()];
+        let mut search = comic_search_fixture("index_1");
+        search.title = comic.title.clone();
+        search.description = "城墙 index_description".to_string();
+        let mut info = comic_info_fixture("index_2");
+        info.title = "索引测试 另一部".to_string();
+        info.creator.name = "index_creator".to_string();
+
+        index_comics(vec![
+            ComicIndexDocument::from(&search),
+            ComicIndexDocument::from(&info),
+        ])
+        .await
+        .unwrap();
+        // 列表中的漫画没有简介，不会覆盖搜索结果中的简介
+        index_comics(vec![ComicIndexDocument::from(&comic)])
+            .await
+            .unwrap();
+
+        let ids = |query: &str| {
+            let query = query.to_string();
+            async move {
+                picacg_index_search(query, 1, 20)
+                    .await
+                    .unwrap()
+                    .docs
+                    .into_iter()
+                    .map(|comic| comic.comic_id)
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        assert_eq!(ids("进击").await, vec!["index_1"]);
+        assert_eq!(ids("index_description").await, vec!["index_1"]);
+        assert_eq!(ids("jinji").await, vec!["index_1"]);
+        assert_eq!(ids("jjdjr").await, vec!["index_1"]);
+        assert_eq!(ids("juren").await, vec!["index_1"]);
+        assert_eq!(ids("全彩 索引").await, vec!["index_1"]);
+        assert_eq!(ids("index_creator").await, vec!["index_2"]);
+        let both = ids("索引測試").await;
+        assert_eq!(both.len(), 2);
+        assert!(both.contains(&"index_1".to_string()));
+
+        picacg_index_delete("index_1".to_string()).await.unwrap();
+        picacg_index_delete("index_2".to_string()).await.unwrap();
+        assert!(ids("索引").await.is_empty());
+    }
+}
@@ -1,15 +1,42 @@
 use crate::api::storage::net_data::NetData;
 use crate::api::{
     error::custom_error::{CustomError, CustomErrorType},
-    storage::user_data::UserData,
+    storage::{
+        database::with_opened_connection,
+        root::storage_root,
+        secret_data::{get_secret, picacg_secrets_is_unlocked, set_secret, TOKEN_SECRET},
+        setting_data::{get_config_sections, set_config_sections},
+        user_data::UserData,
+    },
+    utils::file::write_file_atomic,
 };
+use crate::frb_generated::StreamSink;
 use flutter_rust_bridge::frb;
 use serde::{Deserialize, Serialize};
-use std::{fs, sync::RwLock};
+use serde_json::Value;
+use std::{
+    fs,
+    path::Path,
+    sync::{Mutex, RwLock},
+};
 
 static CONFIG_FILE_PATH: &str = "config.json";
+static CONFIG_TEMP_FILE_PATH: &str = "config.json.tmp";
+static CONFIG_BACKUP_FILE_PATH: &str = "config.json.bak";
+static CONFIG_BACKUP_TEMP_FILE_PATH: &str = "config.json.bak.tmp";
+static CONFIG_CORRUPT_FILE_PATH: &str = "config.json.corrupt";
+
+/// 当前配置文件的版本号。
+pub const CONFIG_VERSION: u32 = 1;
+
+/// 配置文件迁移函数，下标为迁移前的版本号。
+///
+/// 修改 `Config` 结构时追加新的迁移函数并增加 `CONFIG_VERSION`，
+/// 在迁移函数中把旧字段转换为新字段，避免丢失用户数据。
+const CONFIG_MIGRATIONS: &[fn(&mut serde_json::Map<String, Value>)] = &[migrate_v0_to_v1];
 
 pub static CONFIG: RwLock<Config> = RwLock::new(Config {
+    version: CONFIG_VERSION,
     user_data: UserData {
         token: String::new(),
     },
@@ -18,8 +45,16 @@ pub static CONFIG: RwLock<Config> = RwLock::new(Config {
     },
 });
 
+static CONFIG_LISTENERS: Mutex<Vec<StreamSink<Config>>> = Mutex::new(Vec::new());
+
+/// 上次加载配置时配置文件损坏的原因，配置已从备份或默认值恢复。
+static CONFIG_RECOVERY_ERROR: Mutex<Option<String>> = Mutex::new(None);
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Config {
+    /// 配置文件版本号，旧版本的配置文件没有该字段，视为版本 0。
+    #[serde(default)]
+    pub version: u32,
     #[serde(default = "default_user_data")]
     pub user_data: UserData,
     #[serde(default = "default_net_data")]
@@ -38,156 +73,425 @@ fn default_net_data() -> NetData {
     }
 }
 
-#[frb(sync)]
-pub fn picacg_load_config(path: String) -> Result<Config, CustomError> {
-    let path = if path.ends_with('/') {
-        path
-    } else {
-        path + "/"
+/// 版本 0 没有 `version` 字段，各配置项可能缺失或为 `null`。
+fn migrate_v0_to_v1(config: &mut serde_json::Map<String, Value>) {
+    for section in ["user_data", "net_data"] {
+        if config.get(section).is_some_and(Value::is_null) {
+            config.remove(section);
+        }
+    }
+}
+
+fn config_version(config: &serde_json::Map<String, Value>) -> usize {
+    config.get("version").and_then(Value::as_u64).unwrap_or(0) as usize
+}
+
+/// 配置文件是否由更新版本的程序写入。
+fn is_newer_config_file(file_path: &Path) -> bool {
+    fs::read_to_string(file_path)
+        .ok()
+        .and_then(|config| serde_json::from_str::<serde_json::Map<String, Value>>(&config).ok())
+        .is_some_and(|config| config_version(&config) > CONFIG_VERSION as usize)
+}
+
+/// 按顺序执行迁移函数，把配置文件升级到 `CONFIG_VERSION`。
+fn migrate_config(config: Value) -> Result<Config, CustomError> {
+    let Value::Object(mut config) = config else {
+        return Err(CustomError {
+            error_code: CustomErrorType::ParseJsonError,
+            error_message: "Config file is not a JSON object".to_string(),
+        });
     };
-    let file_path = path + CONFIG_FILE_PATH;
 
-    if !fs::metadata(&file_path).is_ok() {
+    let version = config_version(&config);
+    if version > CONFIG_VERSION as usize {
+        return Err(CustomError {
+            error_code: CustomErrorType::ParseJsonError,
+            error_message: format!(
+                "Config version {} is newer than supported version {}",
+                version, CONFIG_VERSION
+            ),
+        });
+    }
+
+    for migration in &CONFIG_MIGRATIONS[version..] {
+        migration(&mut config);
+    }
+    config.insert("version".to_string(), Value::from(CONFIG_VERSION));
+
+    serde_json::from_value(Value::Object(config)).map_err(|e| CustomError {
+        error_code: CustomErrorType::ParseJsonError,
+        error_message: format!("Failed to parse config file: {}", e),
+    })
+}
+
+fn read_config_file(file_path: &Path) -> Result<Config, CustomError> {
+    let config = fs::read_to_string(file_path).map_err(|e| CustomError {
+        error_code: CustomErrorType::FileReadError,
+        error_message: format!("Failed to read config file: {}", e),
+    })?;
+
+    let config: Value = serde_json::from_str(&config).map_err(|e| CustomError {
+        error_code: CustomErrorType::ParseJsonError,
+        error_message: format!("Failed to parse config file: {}", e),
+    })?;
+
+    migrate_config(config)
+}
+
+/// 原子地写入 `dir` 目录下的配置文件。
+///
+/// 写入前如果现有的配置文件可以正常读取，会先把它保存为备份文件，
+/// 用于主配置文件损坏时恢复。
+fn write_config_file(dir: &Path, config: &Config) -> Result<(), CustomError> {
+    let config = Config {
+        version: CONFIG_VERSION,
+        ..config.clone()
+    };
+    let config_json = serde_json::to_string(&config).map_err(|e| CustomError {
+        error_code: CustomErrorType::SerializeJsonError,
+        error_message: format!("Failed to serialize config: {}", e),
+    })?;
+
+    // 备份重新序列化后的内容而不是原始文件，避免旧版本的明文令牌被复制到备份中
+    let file_path = dir.join(CONFIG_FILE_PATH);
+    if let Ok(backup) = read_config_file(&file_path) {
+        let backup_json = serde_json::to_string(&backup).map_err(|e| CustomError {
+            error_code: CustomErrorType::SerializeJsonError,
+            error_message: format!("Failed to serialize config: {}", e),
+        })?;
+        write_file_atomic(
+            &dir.join(CONFIG_BACKUP_FILE_PATH),
+            &dir.join(CONFIG_BACKUP_TEMP_FILE_PATH),
+            backup_json.as_bytes(),
+        )?;
+    }
+
+    write_file_atomic(
+        &file_path,
+        &dir.join(CONFIG_TEMP_FILE_PATH),
+        config_json.as_bytes(),
+    )
+}
+
+/// 读取 `dir` 目录下的配置文件。
+///
+/// - 配置文件不存在时写入默认配置。
+/// - 配置文件损坏时从备份文件恢复；没有可用的备份时，把损坏的文件
+///   重命名为 `config.json.corrupt` 后使用默认配置，避免用户无法启动应用。
+///
+/// 返回加载的配置，以及发生恢复时配置文件损坏的原因。
+fn load_config_file(dir: &Path) -> Result<(Config, Option<CustomError>), CustomError> {
+    let file_path = dir.join(CONFIG_FILE_PATH);
+    let backup_file_path = dir.join(CONFIG_BACKUP_FILE_PATH);
+
+    if !file_path.exists() && !backup_file_path.exists() {
         let default_config = Config {
+            version: CONFIG_VERSION,
             user_data: default_user_data(),
             net_data: default_net_data(),
         };
+        write_config_file(dir, &default_config)?;
+        return Ok((default_config, None));
+    }
 
-        let default_config_json =
-            serde_json::to_string(&default_config).map_err(|e| CustomError {
-                error_code: CustomErrorType::SerializeJsonError,
-                error_message: format!("Failed to serialize default config: {}", e),
-            })?;
-        fs::write(&file_path, default_config_json).map_err(|e| CustomError {
-            error_code: CustomErrorType::FileWriteError,
-            error_message: format!("Failed to write default config file: {}", e),
-        })?;
-
-        CONFIG
-            .write()
-            .map_err(|_| CustomError {
-                error_code: CustomErrorType::LockError,
-                error_message: "Failed to acquire write lock on CONFIG".to_string(),
-            })?
-            .user_data = default_config.user_data;
-
-        Ok(CONFIG
-            .read()
-            .map_err(|_| CustomError {
-                error_code: CustomErrorType::LockError,
-                error_message: "Failed to acquire read lock on CONFIG".to_string(),
-            })?
-            .clone())
-    } else {
-        let config = fs::read_to_string(&file_path).map_err(|e| CustomError {
-            error_code: CustomErrorType::FileReadError,
-            error_message: format!("Failed to read config file: {}", e),
-        })?;
+    let error = match read_config_file(&file_path) {
+        Ok(config) => return Ok((config, None)),
+        Err(e) => e,
+    };
 
-        let config: Config = serde_json::from_str(&config).map_err(|e| CustomError {
-            error_code: CustomErrorType::ParseJsonError,
-            error_message: format!("Failed to parse config file: {}", e),
-        })?;
+    // 配置文件版本比当前程序新时不做任何修改，避免降级覆盖数据
+    if is_newer_config_file(&file_path) {
+        return Err(error);
+    }
 
-        println!("Loaded config: {:?}", config);
+    let config = match read_config_file(&backup_file_path) {
+        Ok(config) => config,
+        Err(_) => {
+            if file_path.exists() {
+                fs::rename(&file_path, dir.join(CONFIG_CORRUPT_FILE_PATH)).map_err(|e| {
+                    CustomError {
+                        error_code: CustomErrorType::FileWriteError,
+                        error_message: format!("Failed to move corrupt config file: {}", e),
+                    }
+                })?;
+            }
+            Config {
+                version: CONFIG_VERSION,
+                user_data: default_user_data(),
+                net_data: default_net_data(),
+            }
+        }
+    };
 
-        CONFIG
-            .write()
-            .map_err(|_| CustomError {
-                error_code: CustomErrorType::LockError,
-                error_message: "Failed to acquire write lock on CONFIG".to_string(),
+    write_file_atomic(
+        &file_path,
+        &dir.join(CONFIG_TEMP_FILE_PATH),
+        serde_json::to_string(&config)
+            .map_err(|e| CustomError {
+                error_code: CustomErrorType::SerializeJsonError,
+                error_message: format!("Failed to serialize config: {}", e),
             })?
-            .user_data = config.user_data;
+            .as_bytes(),
+    )?;
 
-        CONFIG
-            .write()
-            .map_err(|_| CustomError {
-                error_code: CustomErrorType::LockError,
-                error_message: "Failed to acquire write lock on CONFIG".to_string(),
-            })?
-            .net_data = config.net_data;
+    Ok((config, Some(error)))
+}
 
-        Ok(CONFIG
-            .read()
-            .map_err(|_| CustomError {
-                error_code: CustomErrorType::LockError,
-                error_message: "Failed to acquire read lock on CONFIG".to_string(),
-            })?
-            .clone())
+/// 把设置中保存的配置项转换为配置，没有保存任何配置项时返回 `None`。
+fn config_from_sections(
+    sections: serde_json::Map<String, Value>,
+) -> Result<Option<Config>, CustomError> {
+    if sections.is_empty() {
+        return Ok(None);
     }
+    migrate_config(Value::Object(sections)).map(Some)
 }
 
-#[frb(sync)]
-pub fn picacg_save_config(path: String) -> Result<(), CustomError> {
-    let path = if path.ends_with('/') {
-        path
-    } else {
-        path + "/"
+/// 读取数据库中保存的配置，数据库没有打开或没有保存配置时返回 `None`。
+fn read_database_config() -> Result<Option<Config>, CustomError> {
+    match with_opened_connection(|connection| get_config_sections(connection))? {
+        Some(sections) => config_from_sections(sections),
+        None => Ok(None),
+    }
+}
+
+/// 把配置逐项写入数据库，数据库没有打开时跳过。令牌不会被序列化，不会写入数据库。
+fn write_database_config(config: &Config) -> Result<(), CustomError> {
+    let Value::Object(sections) = serde_json::to_value(config).map_err(|e| CustomError {
+        error_code: CustomErrorType::SerializeJsonError,
+        error_message: format!("Failed to serialize config: {}", e),
+    })?
+    else {
+        return Ok(());
     };
-    let file_path = path + CONFIG_FILE_PATH;
 
-    let config = CONFIG.read().map_err(|_| CustomError {
+    with_opened_connection(|connection| {
+        let transaction = connection.transaction()?;
+        set_config_sections(&transaction, &sections)?;
+        transaction.commit()?;
+        Ok(())
+    })?;
+    Ok(())
+}
+
+fn read_lock_error() -> CustomError {
+    CustomError {
         error_code: CustomErrorType::LockError,
         error_message: "Failed to acquire read lock on CONFIG".to_string(),
-    })?;
+    }
+}
 
-    let config_json = serde_json::to_string(&*config).map_err(|e| CustomError {
-        error_code: CustomErrorType::SerializeJsonError,
-        error_message: format!("Failed to serialize config: {}", e),
-    })?;
+fn write_lock_error() -> CustomError {
+    CustomError {
+        error_code: CustomErrorType::LockError,
+        error_message: "Failed to acquire write lock on CONFIG".to_string(),
+    }
+}
 
-    fs::write(&file_path, config_json).map_err(|e| CustomError {
-        error_code: CustomErrorType::FileWriteError,
-        error_message: format!("Failed to write config file: {}", e),
-    })?;
+/// 通知所有订阅者配置已变更，并移除已关闭的订阅。
+fn notify_config_listeners(config: &Config) {
+    if let Ok(mut listeners) = CONFIG_LISTENERS.lock() {
+        listeners.retain(|sink| sink.add(config.clone()).is_ok());
+    }
+}
+
+/// 密钥库未解锁时，检查 `dir` 目录下的配置文件是否还有未迁移的明文令牌。
+///
+/// 配置文件序列化时不包含令牌，此时写入配置会丢失令牌，因此返回错误。
+fn check_legacy_token(dir: &Path, unlocked: bool) -> Result<(), CustomError> {
+    if !unlocked
+        && read_config_file(&dir.join(CONFIG_FILE_PATH))
+            .is_ok_and(|config| !config.user_data.token.is_empty())
+    {
+        return Err(CustomError {
+            error_code: CustomErrorType::SecretError,
+            error_message: "Secrets are locked, unlock them to migrate the saved token first"
+                .to_string(),
+        });
+    }
 
     Ok(())
 }
 
+/// 修改配置并写入磁盘。
+///
+/// 持有 `CONFIG` 写锁期间完成写入，只有写入成功后才会更新内存中的配置，
+/// 保证内存与磁盘中的配置始终一致。数据库已打开时配置同时写入数据库，
+/// 先写配置文件再写数据库，写入数据库失败时加载的仍然是修改前的配置。
+/// 令牌保存在加密的密钥库中，不会写入配置文件和数据库。
+/// 密钥库未解锁而配置文件中还有旧版本的明文令牌时拒绝写入，避免令牌在迁移前被丢弃。
+fn update_config<F>(update: F) -> Result<Config, CustomError>
+where
+    F: FnOnce(&mut Config),
+{
+    let dir = storage_root()?;
+    let mut current = CONFIG.write().map_err(|_| write_lock_error())?;
+
+    check_legacy_token(dir, picacg_secrets_is_unlocked())?;
+
+    let mut config = current.clone();
+    update(&mut config);
+    config.version = CONFIG_VERSION;
+    if config.user_data.token != current.user_data.token {
+        let token = &config.user_data.token;
+        set_secret(TOKEN_SECRET, (!token.is_empty()).then(|| token.clone()))?;
+    }
+    write_config_file(dir, &config)?;
+    write_database_config(&config)?;
+
+    *current = config.clone();
+    drop(current);
+
+    notify_config_listeners(&config);
+    Ok(config)
+}
+
+/// 从存储目录加载配置文件，并替换内存中的配置。
+///
+/// 数据库已打开时以数据库中导入或保存的配置为准，配置文件只用于迁移令牌和恢复；
+/// 数据库中还没有配置时写入从配置文件加载的配置。
+/// 令牌从密钥库读取，需要先解锁密钥库。旧版本配置文件中的明文令牌会迁移到密钥库，
+/// 并重写配置文件和备份文件；密钥库未解锁时明文令牌只保留在内存中，配置文件保持不变，
+/// 解锁后需要再次调用完成迁移。
+/// 配置文件损坏并已恢复时，可以通过 `picacg_config_take_recovery_error` 获取损坏的原因。
 #[frb(sync)]
-pub fn picacg_set_config(path: String, config: Config) -> Result<(), CustomError> {
-    let path = if path.ends_with('/') {
-        path
-    } else {
-        path + "/"
-    };
-    let file_path = path + CONFIG_FILE_PATH;
+pub fn picacg_load_config() -> Result<Config, CustomError> {
+    let dir = storage_root()?;
+    let mut current = CONFIG.write().map_err(|_| write_lock_error())?;
 
-    let config_json = serde_json::to_string(&config).map_err(|e| CustomError {
-        error_code: CustomErrorType::SerializeJsonError,
-        error_message: format!("Failed to serialize config: {}", e),
-    })?;
+    let (mut config, recovered_from) = load_config_file(dir)?;
+    *CONFIG_RECOVERY_ERROR.lock().map_err(|_| CustomError {
+        error_code: CustomErrorType::LockError,
+        error_message: "Failed to acquire lock on CONFIG_RECOVERY_ERROR".to_string(),
+    })? = recovered_from.map(|e| format!("{:?}: {}", e.error_code, e.error_message));
+    match read_database_config()? {
+        Some(database_config) => {
+            // 数据库中的配置不包含令牌，旧版本的明文令牌仍然来自配置文件
+            let token = std::mem::take(&mut config.user_data.token);
+            config = database_config;
+            config.user_data.token = token;
+        }
+        None => write_database_config(&config)?,
+    }
+    if picacg_secrets_is_unlocked() {
+        if !config.user_data.token.is_empty() {
+            // 配置文件和备份文件都会重新序列化，序列化时不包含令牌
+            set_secret(TOKEN_SECRET, Some(config.user_data.token.clone()))?;
+            write_config_file(dir, &config)?;
+        }
+        config.user_data.token = get_secret(TOKEN_SECRET)?.unwrap_or_default();
+    }
 
-    fs::write(&file_path, config_json).map_err(|e| CustomError {
-        error_code: CustomErrorType::FileWriteError,
-        error_message: format!("Failed to write config file: {}", e),
-    })?;
+    *current = config.clone();
+    drop(current);
+
+    notify_config_listeners(&config);
+    Ok(config)
+}
+
+/// 获取并清除上次加载配置时配置文件损坏的原因，没有发生恢复时返回 `None`。
+///
+/// 应用启动时在 `picacg_load_config` 之后调用，提示用户配置已从备份或默认值恢复。
+#[frb(sync)]
+pub fn picacg_config_take_recovery_error() -> Result<Option<String>, CustomError> {
+    Ok(CONFIG_RECOVERY_ERROR
+        .lock()
+        .map_err(|_| CustomError {
+            error_code: CustomErrorType::LockError,
+            error_message: "Failed to acquire lock on CONFIG_RECOVERY_ERROR".to_string(),
+        })?
+        .take())
+}
+
+/// 获取内存中的配置。
+#[frb(sync)]
+pub fn picacg_config() -> Result<Config, CustomError> {
+    Ok(CONFIG.read().map_err(|_| read_lock_error())?.clone())
+}
+
+/// 替换整个配置。
+#[frb(sync)]
+pub fn picacg_set_config(config: Config) -> Result<Config, CustomError> {
+    update_config(|current| *current = config)
+}
 
-    CONFIG
-        .write()
+/// 获取用户数据。
+#[frb(sync)]
+pub fn picacg_config_user_data() -> Result<UserData, CustomError> {
+    Ok(CONFIG
+        .read()
+        .map_err(|_| read_lock_error())?
+        .user_data
+        .clone())
+}
+
+/// 修改用户数据。
+#[frb(sync)]
+pub fn picacg_config_set_user_data(user_data: UserData) -> Result<Config, CustomError> {
+    update_config(|config| config.user_data = user_data)
+}
+
+/// 获取网络数据。
+#[frb(sync)]
+pub fn picacg_config_net_data() -> Result<NetData, CustomError> {
+    Ok(CONFIG
+        .read()
+        .map_err(|_| read_lock_error())?
+        .net_data
+        .clone())
+}
+
+/// 修改网络数据。
+#[frb(sync)]
+pub fn picacg_config_set_net_data(net_data: NetData) -> Result<Config, CustomError> {
+    update_config(|config| config.net_data = net_data)
+}
+
+/// 订阅配置变更。
+///
+/// 订阅后立即推送一次当前配置，之后每次配置变更都会推送变更后的配置。
+pub fn picacg_config_subscribe(sink: StreamSink<Config>) -> Result<(), CustomError> {
+    let config = picacg_config()?;
+    if sink.add(config).is_err() {
+        return Ok(());
+    }
+
+    CONFIG_LISTENERS
+        .lock()
         .map_err(|_| CustomError {
             error_code: CustomErrorType::LockError,
-            error_message: "Failed to acquire write lock on CONFIG".to_string(),
+            error_message: "Failed to acquire lock on CONFIG_LISTENERS".to_string(),
         })?
-        .user_data = config.user_data;
+        .push(sink);
 
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::api::error::custom_error::CustomErrorType;
     use crate::api::storage::config::{
-        picacg_load_config, picacg_save_config, picacg_set_config, Config, CONFIG_FILE_PATH,
+        check_legacy_token, config_from_sections, load_config_file, picacg_config,
+        picacg_config_net_data, picacg_config_set_net_data, picacg_config_set_user_data,
+        picacg_config_user_data, picacg_load_config, picacg_set_config, write_config_file, Config,
+        CONFIG_BACKUP_FILE_PATH, CONFIG_CORRUPT_FILE_PATH, CONFIG_FILE_PATH, CONFIG_VERSION,
     };
+    use crate::api::storage::database::{open_test_database, with_connection};
     use crate::api::storage::net_data::NetData;
+    use crate::api::storage::root::init_test_storage;
+    use crate::api::storage::secret_data::unlock_test_secrets;
+    use crate::api::storage::setting_data::{get_config_sections, set_raw};
     use crate::api::storage::user_data::UserData;
 
-    #[test]
-    fn test_config_save_read() {
-        let result = picacg_save_config("./".to_string());
-        assert!(result.is_ok());
+    #[tokio::test]
+    async fn test_config_save_read() {
+        unlock_test_secrets();
+        let dir = init_test_storage();
+        let _ = std::fs::remove_file(dir.join(CONFIG_FILE_PATH));
+        let _ = std::fs::remove_file(dir.join(CONFIG_BACKUP_FILE_PATH));
 
-        let config = picacg_load_config("./".to_string());
+        let config = picacg_load_config();
         match config {
             Ok(loaded_config) => {
                 assert_eq!(loaded_config.user_data.token, "");
@@ -198,6 +502,7 @@ mod tests {
         }
 
         let config = Config {
+            version: CONFIG_VERSION,
             user_data: UserData {
                 token: "test_token".to_string(),
             },
@@ -206,19 +511,182 @@ mod tests {
             },
         };
 
-        let result = picacg_set_config("./".to_string(), config.clone());
+        let result = picacg_set_config(config.clone());
         assert!(result.is_ok());
-        let loaded_config = picacg_load_config("./".to_string());
+        assert_eq!(
+            picacg_config_net_data().unwrap().image_server,
+            "https://example.com"
+        );
+        let loaded_config = picacg_load_config();
         match loaded_config {
             Ok(loaded_config) => {
                 assert_eq!(loaded_config.user_data.token, "test_token");
+                assert_eq!(loaded_config.net_data.image_server, "https://example.com");
             }
             Err(e) => {
                 panic!("Failed to load config: {:?}", e);
             }
         }
 
-        let result = std::fs::remove_file(CONFIG_FILE_PATH);
+        // 修改单个配置项时不影响其他配置项，且内存与磁盘保持一致
+        picacg_config_set_user_data(UserData {
+            token: "new_token".to_string(),
+        })
+        .unwrap();
+        picacg_config_set_net_data(NetData {
+            image_server: "https://example.org".to_string(),
+        })
+        .unwrap();
+        assert_eq!(picacg_config_user_data().unwrap().token, "new_token");
+        let memory_config = picacg_config().unwrap();
+        let disk_config = picacg_load_config().unwrap();
+        assert_eq!(disk_config.user_data.token, memory_config.user_data.token);
+        assert_eq!(
+            disk_config.net_data.image_server,
+            memory_config.net_data.image_server
+        );
+        assert_eq!(memory_config.net_data.image_server, "https://example.org");
+        assert!(!std::fs::read_to_string(dir.join(CONFIG_FILE_PATH))
+            .unwrap()
+            .contains("new_token"));
+
+        // 旧版本配置文件中的明文令牌迁移到密钥库
+        std::fs::write(
+            dir.join(CONFIG_FILE_PATH),
+            r#"{"version":1,"user_data":{"token":"plaintext_token"}}"#,
+        )
+        .unwrap();
+        let config = picacg_load_config().unwrap();
+        assert_eq!(config.user_data.token, "plaintext_token");
+        for file in [CONFIG_FILE_PATH, CONFIG_BACKUP_FILE_PATH] {
+            assert!(!std::fs::read_to_string(dir.join(file))
+                .unwrap()
+                .contains("plaintext_token"));
+        }
+
+        // 打开数据库后以数据库中的配置为准，修改配置时同时写入数据库
+        open_test_database().await;
+        picacg_load_config().unwrap();
+        with_connection(|connection| {
+            set_raw(
+                connection,
+                "config.net_data",
+                r#"{"image_server":"https://example.net"}"#,
+            )
+        })
+        .await
+        .unwrap();
+        let config = picacg_load_config().unwrap();
+        assert_eq!(config.net_data.image_server, "https://example.net");
+        assert_eq!(config.user_data.token, "plaintext_token");
+
+        picacg_config_set_user_data(UserData {
+            token: String::new(),
+        })
+        .unwrap();
+        let sections = with_connection(|connection| get_config_sections(connection))
+            .await
+            .unwrap();
+        assert_eq!(sections["net_data"]["image_server"], "https://example.net");
+        assert!(!sections["user_data"].to_string().contains("token"));
+
+        let result = std::fs::remove_file(dir.join(CONFIG_FILE_PATH));
+        assert!(result.is_ok());
+        let result = std::fs::remove_file(dir.join(CONFIG_BACKUP_FILE_PATH));
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_config_from_sections() {
+        assert!(config_from_sections(serde_json::Map::new())
+            .unwrap()
+            .is_none());
+
+        // 导入的旧版本配置项同样会被迁移
+        let serde_json::Value::Object(sections) = serde_json::json!({
+            "user_data": {},
+            "net_data": null,
+        }) else {
+            unreachable!()
+        };
+        let config = config_from_sections(sections).unwrap().unwrap();
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert_eq!(config.net_data.image_server, "");
+    }
+
+    #[test]
+    fn test_config_migrate_and_recover() {
+        let dir = std::env::temp_dir().join("picacg_test_config_recover");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let file_path = dir.join(CONFIG_FILE_PATH);
+
+        // 没有 version 字段的旧配置文件会被迁移到当前版本
+        std::fs::write(
+            &file_path,
+            r#"{"user_data":{"token":"old"},"net_data":null}"#,
+        )
+        .unwrap();
+        let (config, recovered_from) = load_config_file(&dir).unwrap();
+        assert!(recovered_from.is_none());
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert_eq!(config.user_data.token, "old");
+        assert_eq!(config.net_data.image_server, "");
+
+        // 写入新配置时保留上一次可用的配置作为备份，两者都不包含明文令牌
+        let mut config = config.clone();
+        config.net_data.image_server = "old".to_string();
+        write_config_file(&dir, &config).unwrap();
+        let mut new_config = config.clone();
+        new_config.net_data.image_server = "new".to_string();
+        write_config_file(&dir, &new_config).unwrap();
+        assert!(!dir.join("config.json.tmp").exists());
+        for file in [CONFIG_FILE_PATH, CONFIG_BACKUP_FILE_PATH] {
+            assert!(!std::fs::read_to_string(dir.join(file))
+                .unwrap()
+                .contains("token"));
+        }
+
+        // 密钥库未解锁时不能覆盖还有明文令牌的配置文件
+        std::fs::write(&file_path, r#"{"version":1,"user_data":{"token":"old"}}"#).unwrap();
+        assert!(matches!(
+            check_legacy_token(&dir, false).map_err(|e| e.error_code),
+            Err(CustomErrorType::SecretError)
+        ));
+        assert!(check_legacy_token(&dir, true).is_ok());
+        write_config_file(&dir, &config).unwrap();
+        write_config_file(&dir, &new_config).unwrap();
+        assert!(check_legacy_token(&dir, false).is_ok());
+
+        // 主配置文件损坏时从备份恢复
+        std::fs::write(&file_path, r#"{"user_data":{"tok"#).unwrap();
+        let (config, recovered_from) = load_config_file(&dir).unwrap();
+        assert_eq!(config.net_data.image_server, "old");
+        assert!(matches!(
+            recovered_from.map(|e| e.error_code),
+            Some(CustomErrorType::ParseJsonError)
+        ));
+        let (config, recovered_from) = load_config_file(&dir).unwrap();
+        assert_eq!(config.net_data.image_server, "old");
+        assert!(recovered_from.is_none());
+
+        // 备份也不可用时保留损坏的文件并使用默认配置
+        std::fs::write(&file_path, "corrupt").unwrap();
+        std::fs::write(dir.join(CONFIG_BACKUP_FILE_PATH), "corrupt").unwrap();
+        let (config, recovered_from) = load_config_file(&dir).unwrap();
+        assert_eq!(config.net_data.image_server, "");
+        assert!(recovered_from.is_some());
+        assert!(dir.join(CONFIG_CORRUPT_FILE_PATH).exists());
+
+        // 更新版本的配置文件不会被覆盖
+        std::fs::write(&file_path, r#"{"version":999}"#).unwrap();
+        assert!(load_config_file(&dir).is_err());
+        assert_eq!(
+            std::fs::read_to_string(&file_path).unwrap(),
+            r#"{"version":999}"#
+        );
+
+        let result = std::fs::remove_dir_all(&dir);
         assert!(result.is_ok());
     }
 }
@@ -0,0 +1,607 @@
+use crate::api::{
+    error::custom_error::{CustomError, CustomErrorType},
+    storage::{
+        follow_data::{self, FollowedCreator},
+        history_data::HistoryEntry,
+        root::storage_root,
+        setting_data,
+    },
+};
+use flutter_rust_bridge::frb;
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::{params, types::Type, Connection, Row};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::{
+    fs,
+    path::Path,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        RwLock,
+    },
+    time::Duration,
+};
+
+static DATABASE_FILE_PATH: &str = "picacg.db";
+static LEGACY_CONFIG_FILE_PATH: &str = "config.json";
+static LEGACY_FOLLOW_FILE_PATH: &str = "follow.json";
+static LEGACY_HISTORY_FILE_PATH: &str = "history.json";
+
+const LEGACY_CONFIG_IMPORTED_KEY: &str = "migration.config_json_imported_at";
+const LEGACY_FOLLOW_IMPORTED_KEY: &str = "migration.follow_json_imported_at";
+const LEGACY_HISTORY_IMPORTED_KEY: &str = "migration.history_json_imported_at";
+
+pub type DatabasePool = Pool<SqliteConnectionManager>;
+
+/// 已打开的数据库，以及区分每次打开的编号。
+static DATABASE: RwLock<Option<(u64, DatabasePool)>> = RwLock::new(None);
+static DATABASE_ID: AtomicU64 = AtomicU64::new(0);
+
+#[cfg(test)]
+thread_local! {
+    /// 当前测试线程使用的数据库，每个测试使用独立的数据库，互不影响。
+    static TEST_DATABASE: std::cell::RefCell<Option<(u64, DatabasePool)>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+/// 数据库结构迁移脚本，按顺序执行，下标 + 1 即为执行后的 `user_version`。
+///
+/// 已发布的脚本不能修改，结构变更只能追加新的脚本。
+const MIGRATIONS: &[&str] = &[
+    // 1: 设置、阅读记录、下载和收藏快照
+    r#"
+    CREATE TABLE setting (
+        key TEXT PRIMARY KEY NOT NULL,
+        value TEXT NOT NULL,
+        updated_at TEXT NOT NULL
+    );
+
+    CREATE TABLE history (
+        comic_id TEXT PRIMARY KEY NOT NULL,
+        title TEXT NOT NULL,
+        thumb TEXT NOT NULL,
+        ep_order INTEGER NOT NULL,
+        page_index INTEGER NOT NULL,
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL
+    );
+    CREATE INDEX history_updated_at ON history (updated_at DESC);
+
+    CREATE TABLE download_comic (
+        comic_id TEXT PRIMARY KEY NOT NULL,
+        info TEXT NOT NULL,
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL
+    );
+
+    CREATE TABLE download_episode (
+        comic_id TEXT NOT NULL REFERENCES download_comic (comic_id) ON DELETE CASCADE,
+        ep_order INTEGER NOT NULL,
+        ep_title TEXT NOT NULL,
+        status TEXT NOT NULL,
+        downloaded_pages INTEGER NOT NULL,
+        total_pages INTEGER NOT NULL,
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL,
+        PRIMARY KEY (comic_id, ep_order)
+    );
+
+    CREATE TABLE favourite_snapshot (
+        comic_id TEXT PRIMARY KEY NOT NULL,
+        comic TEXT NOT NULL,
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL
+    );
+    "#,
+    // 2: 令牌改为保存在加密的密钥库中，移除导入时复制的明文令牌
+    r#"
+    UPDATE setting SET value = json_remove(value, '$.token')
+    WHERE key = 'config.user_data' AND json_valid(value);
+    "#,
+    // 3: 收藏镜像记录服务器排序和最后一次发现章节、页数变化的时间
+    r#"
+    ALTER TABLE favourite_snapshot ADD COLUMN position INTEGER NOT NULL DEFAULT 0;
+    ALTER TABLE favourite_snapshot ADD COLUMN last_updated_at TEXT;
+    UPDATE favourite_snapshot SET last_updated_at = updated_at;
+    "#,
+    // 4: 本地收藏夹，一部漫画可以在多个收藏夹中，元数据快照由所有收藏夹共用
+    r#"
+    CREATE TABLE folder (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        name TEXT NOT NULL UNIQUE,
+        position INTEGER NOT NULL,
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL
+    );
+
+    CREATE TABLE folder_comic_snapshot (
+        comic_id TEXT PRIMARY KEY NOT NULL,
+        comic TEXT,
+        info TEXT,
+        updated_at TEXT NOT NULL
+    );
+
+    CREATE TABLE folder_comic (
+        folder_id INTEGER NOT NULL REFERENCES folder (id) ON DELETE CASCADE,
+        comic_id TEXT NOT NULL REFERENCES folder_comic_snapshot (comic_id),
+        added_at TEXT NOT NULL,
+        PRIMARY KEY (folder_id, comic_id)
+    );
+    CREATE INDEX folder_comic_comic_id ON folder_comic (comic_id);
+    "#,
+    // 5: 订阅连载漫画，记录最后看到的章节和检查到的新章节
+    r#"
+    CREATE TABLE subscription (
+        comic_id TEXT PRIMARY KEY NOT NULL,
+        title TEXT NOT NULL,
+        thumb TEXT NOT NULL,
+        comic_updated_at TEXT NOT NULL,
+        last_ep_order INTEGER NOT NULL,
+        last_ep_updated_at TEXT,
+        created_at TEXT NOT NULL,
+        last_checked_at TEXT
+    );
+
+    CREATE TABLE subscription_episode (
+        comic_id TEXT NOT NULL REFERENCES subscription (comic_id) ON DELETE CASCADE,
+        ep_order INTEGER NOT NULL,
+        ep_title TEXT NOT NULL,
+        ep_updated_at TEXT NOT NULL,
+        detected_at TEXT NOT NULL,
+        seen INTEGER NOT NULL DEFAULT 0,
+        PRIMARY KEY (comic_id, ep_order, ep_updated_at)
+    );
+    CREATE INDEX subscription_episode_detected_at ON subscription_episode (detected_at DESC);
+    "#,
+    // 6: 离线操作队列，点赞和收藏每部漫画只保留最后一次的目标状态
+    r#"
+    CREATE TABLE outbox (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        kind TEXT NOT NULL,
+        comic_id TEXT NOT NULL,
+        desired INTEGER,
+        content TEXT,
+        status TEXT NOT NULL,
+        attempts INTEGER NOT NULL DEFAULT 0,
+        last_error TEXT,
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL
+    );
+    CREATE INDEX outbox_comic_id ON outbox (comic_id);
+    "#,
+    // 7: 搜索历史按账号保存，以规范化后的关键词去重；搜索建议使用的标签、分类和热门关键词
+    r#"
+    CREATE TABLE search_history (
+        account TEXT NOT NULL,
+        normalized TEXT NOT NULL,
+        keyword TEXT NOT NULL,
+        pinned INTEGER NOT NULL DEFAULT 0,
+        search_count INTEGER NOT NULL DEFAULT 1,
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL,
+        PRIMARY KEY (account, normalized)
+    );
+    CREATE TABLE search_term (
+        kind TEXT NOT NULL,
+        term TEXT NOT NULL,
+        seen_count INTEGER NOT NULL DEFAULT 1,
+        position INTEGER NOT NULL DEFAULT 0,
+        updated_at TEXT NOT NULL,
+        PRIMARY KEY (kind, term)
+    );
+    "#,
+    // 8: 本地全文索引，comic_fts 的 rowid 与 comic_index.id 相同，保存预先分好的词
+    r#"
+    CREATE TABLE comic_index (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        comic_id TEXT NOT NULL UNIQUE,
+        title TEXT NOT NULL,
+        author TEXT NOT NULL,
+        description TEXT NOT NULL,
+        categories TEXT NOT NULL,
+        tags TEXT NOT NULL,
+        chinese_team TEXT NOT NULL,
+        creator_id TEXT NOT NULL,
+        creator_name TEXT NOT NULL,
+        thumb TEXT NOT NULL,
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL
+    );
+    CREATE VIRTUAL TABLE comic_fts USING fts5(
+        title, author, tags, people, description, pinyin
+    );
+    "#,
+    // 9: 关注的创作者和每位创作者的更新检查进度
+    r#"
+    CREATE TABLE followed_creator (
+        creator_id TEXT PRIMARY KEY NOT NULL,
+        name TEXT NOT NULL,
+        avatar TEXT NOT NULL,
+        followed_at TEXT NOT NULL,
+        last_checked_at TEXT,
+        last_comic_id TEXT NOT NULL,
+        last_uploaded_at TEXT
+    );
+    "#,
+];
+
+/// 读取 JSON 文本列并反序列化为 `T`。
+pub fn json_column<T: DeserializeOwned>(row: &Row<'_>, index: usize) -> rusqlite::Result<T> {
+    let value: String = row.get(index)?;
+    serde_json::from_str(&value)
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(index, Type::Text, Box::new(e)))
+}
+
+/// 将 `value` 序列化为 JSON 文本，用于写入 JSON 文本列。
+pub fn to_json<T: Serialize>(value: &T) -> Result<String, CustomError> {
+    serde_json::to_string(value).map_err(|e| CustomError {
+        error_code: CustomErrorType::SerializeJsonError,
+        error_message: format!("Failed to serialize database column: {}", e),
+    })
+}
+
+fn database_error(message: String) -> CustomError {
+    CustomError {
+        error_code: CustomErrorType::DatabaseError,
+        error_message: message,
+    }
+}
+
+/// 将数据库结构升级到最新版本。
+fn migrate(connection: &mut Connection) -> Result<(), CustomError> {
+    let version: i64 = connection.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    let version = version as usize;
+
+    if version > MIGRATIONS.len() {
+        return Err(database_error(format!(
+            "Database version {} is newer than supported version {}",
+            version,
+            MIGRATIONS.len()
+        )));
+    }
+
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+        let transaction = connection.transaction()?;
+        transaction.execute_batch(migration)?;
+        transaction.pragma_update(None, "user_version", (index + 1) as i64)?;
+        transaction.commit()?;
+    }
+
+    Ok(())
+}
+
+/// 一次性导入旧版本的 `config.json`，每个配置项保存为一条 `config.<name>` 设置。
+fn import_legacy_config(connection: &mut Connection, dir: &Path) -> Result<(), CustomError> {
+    if setting_data::get_raw(connection, LEGACY_CONFIG_IMPORTED_KEY)?.is_some() {
+        return Ok(());
+    }
+
+    let file_path = dir.join(LEGACY_CONFIG_FILE_PATH);
+    let mut sections = serde_json::Map::new();
+    if file_path.exists() {
+        let config = fs::read_to_string(&file_path).map_err(|e| CustomError {
+            error_code: CustomErrorType::FileReadError,
+            error_message: format!("Failed to read config file: {}", e),
+        })?;
+
+        // 配置文件损坏时跳过导入，避免因为旧数据导致数据库无法打开。
+        if let Ok(Value::Object(config)) = serde_json::from_str::<Value>(&config) {
+            sections = config;
+        }
+        // 令牌保存在加密的密钥库中，不导入明文令牌
+        if let Some(user_data) = sections.get_mut("user_data").and_then(Value::as_object_mut) {
+            user_data.remove("token");
+        }
+    }
+
+    let transaction = connection.transaction()?;
+    setting_data::set_config_sections(&transaction, &sections)?;
+    setting_data::set_raw(
+        &transaction,
+        LEGACY_CONFIG_IMPORTED_KEY,
+        &Value::String(chrono::Utc::now().to_rfc3339()).to_string(),
+    )?;
+    transaction.commit()?;
+
+    Ok(())
+}
+
+/// 一次性导入旧版本的 `follow.json` 关注列表。
+fn import_legacy_follows(connection: &mut Connection, dir: &Path) -> Result<(), CustomError> {
+    if setting_data::get_raw(connection, LEGACY_FOLLOW_IMPORTED_KEY)?.is_some() {
+        return Ok(());
+    }
+
+    let file_path = dir.join(LEGACY_FOLLOW_FILE_PATH);
+    let creators: Vec<FollowedCreator> = if file_path.exists() {
+        let follows = fs::read_to_string(&file_path).map_err(|e| CustomError {
+            error_code: CustomErrorType::FileReadError,
+            error_message: format!("Failed to read follow file: {}", e),
+        })?;
+
+        serde_json::from_str::<Value>(&follows)
+            .ok()
+            .and_then(|follows| serde_json::from_value(follows["creators"].clone()).ok())
+            .unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+
+    let transaction = connection.transaction()?;
+    for creator in &creators {
+        follow_data::insert_followed_creator(&transaction, creator)?;
+    }
+    setting_data::set_raw(
+        &transaction,
+        LEGACY_FOLLOW_IMPORTED_KEY,
+        &Value::String(chrono::Utc::now().to_rfc3339()).to_string(),
+    )?;
+    transaction.commit()?;
+
+    Ok(())
+}
+
+/// 一次性导入旧版本的 `history.json` 阅读记录。
+fn import_legacy_history(connection: &mut Connection, dir: &Path) -> Result<(), CustomError> {
+    if setting_data::get_raw(connection, LEGACY_HISTORY_IMPORTED_KEY)?.is_some() {
+        return Ok(());
+    }
+
+    let file_path = dir.join(LEGACY_HISTORY_FILE_PATH);
+    let entries: Vec<HistoryEntry> = if file_path.exists() {
+        let history = fs::read_to_string(&file_path).map_err(|e| CustomError {
+            error_code: CustomErrorType::FileReadError,
+            error_message: format!("Failed to read history file: {}", e),
+        })?;
+
+        serde_json::from_str::<Value>(&history)
+            .ok()
+            .and_then(|history| serde_json::from_value(history["entries"].clone()).ok())
+            .unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+
+    let transaction = connection.transaction()?;
+    for entry in entries {
+        transaction.execute(
+            "INSERT OR IGNORE INTO history
+                (comic_id, title, thumb, ep_order, page_index, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                entry.comic_id,
+                entry.title,
+                to_json(&entry.thumb)?,
+                entry.ep_order,
+                entry.page_index,
+                entry.created_at,
+                entry.updated_at,
+            ],
+        )?;
+    }
+    setting_data::set_raw(
+        &transaction,
+        LEGACY_HISTORY_IMPORTED_KEY,
+        &Value::String(chrono::Utc::now().to_rfc3339()).to_string(),
+    )?;
+    transaction.commit()?;
+
+    Ok(())
+}
+
+/// 打开 `path` 目录下的数据库文件，执行结构迁移并导入旧版本的 JSON 数据。
+pub fn open_database(path: &Path) -> Result<DatabasePool, CustomError> {
+    fs::create_dir_all(path).map_err(|e| CustomError {
+        error_code: CustomErrorType::FileWriteError,
+        error_message: format!("Failed to create database directory: {}", e),
+    })?;
+
+    let manager = SqliteConnectionManager::file(path.join(DATABASE_FILE_PATH)).with_init(|c| {
+        c.busy_timeout(Duration::from_secs(5))?;
+        c.execute_batch("PRAGMA journal_mode = WAL; PRAGMA foreign_keys = ON;")
+    });
+    let pool = Pool::builder().max_size(4).build(manager)?;
+
+    let mut connection = pool.get()?;
+    migrate(&mut connection)?;
+    import_legacy_config(&mut connection, path)?;
+    import_legacy_follows(&mut connection, path)?;
+    import_legacy_history(&mut connection, path)?;
+
+    Ok(pool)
+}
+
+/// 打开（或重新打开）存储目录下的本地数据库。
+///
+/// 应用启动时在 `picacg_init_storage` 之后调用一次，之后所有本地数据的读写都通过该数据库完成。
+/// 第一次打开时会自动导入旧版本的 `config.json`、`follow.json` 和 `history.json`，
+/// 之后 `picacg_load_config` 以数据库中的配置为准。
+#[frb]
+pub async fn picacg_open_database() -> Result<(), CustomError> {
+    let path = storage_root()?;
+    let pool = tokio::task::spawn_blocking(move || open_database(path))
+        .await
+        .map_err(|e| database_error(format!("Failed to open database: {}", e)))??;
+
+    *DATABASE.write().map_err(|_| CustomError {
+        error_code: CustomErrorType::LockError,
+        error_message: "Failed to acquire write lock on DATABASE".to_string(),
+    })? = Some((DATABASE_ID.fetch_add(1, Ordering::Relaxed), pool));
+
+    Ok(())
+}
+
+fn try_opened_database() -> Result<Option<(u64, DatabasePool)>, CustomError> {
+    #[cfg(test)]
+    if let Some(database) = TEST_DATABASE.with(|database| database.borrow().clone()) {
+        return Ok(Some(database));
+    }
+
+    Ok(DATABASE
+        .read()
+        .map_err(|_| CustomError {
+            error_code: CustomErrorType::LockError,
+            error_message: "Failed to acquire read lock on DATABASE".to_string(),
+        })?
+        .clone())
+}
+
+fn opened_database() -> Result<(u64, DatabasePool), CustomError> {
+    try_opened_database()?.ok_or_else(|| database_error("Database is not opened".to_string()))
+}
+
+fn database_pool() -> Result<DatabasePool, CustomError> {
+    Ok(opened_database()?.1)
+}
+
+/// 当前打开的数据库的编号，每次打开数据库时都不同，用于判断根据数据库内容建立的缓存是否过期。
+pub fn database_id() -> Result<u64, CustomError> {
+    Ok(opened_database()?.0)
+}
+
+/// 在阻塞线程池中获取一个数据库连接并执行 `f`，避免阻塞 tokio 的工作线程。
+pub async fn with_connection<T, F>(f: F) -> Result<T, CustomError>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut Connection) -> Result<T, CustomError> + Send + 'static,
+{
+    let pool = database_pool()?;
+
+    tokio::task::spawn_blocking(move || f(&mut *pool.get()?))
+        .await
+        .map_err(|e| database_error(format!("Database task failed: {}", e)))?
+}
+
+/// 在当前线程中获取一个数据库连接并执行 `f`，数据库没有打开时返回 `None`。
+///
+/// 只用于必须同步完成的少量读写，例如同步导出的配置接口。
+pub fn with_opened_connection<T, F>(f: F) -> Result<Option<T>, CustomError>
+where
+    F: FnOnce(&mut Connection) -> Result<T, CustomError>,
+{
+    match try_opened_database()? {
+        Some((_, pool)) => Ok(Some(f(&mut *pool.get()?)?)),
+        None => Ok(None),
+    }
+}
+
+/// 为当前测试打开一个独立的空数据库，同一个测试中重复调用时不会重新打开。
+///
+/// 数据库只对调用的测试线程可见，测试中 `tokio::spawn` 的任务同样运行在该线程上。
+#[cfg(test)]
+pub async fn open_test_database() {
+    if TEST_DATABASE.with(|database| database.borrow().is_some()) {
+        return;
+    }
+
+    let id = DATABASE_ID.fetch_add(1, Ordering::Relaxed);
+    let path = crate::api::storage::root::init_test_storage()
+        .join("databases")
+        .join(id.to_string());
+    let pool = open_database(&path).unwrap();
+    TEST_DATABASE.with(|database| *database.borrow_mut() = Some((id, pool)));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::storage::{
+        database::{open_database, MIGRATIONS},
+        setting_data,
+    };
+
+    #[test]
+    fn test_open_database_migrate_and_import() {
+        let dir = std::env::temp_dir().join("picacg_test_open_database");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("config.json"),
+            r#"{"version":1,"user_data":{"token":"token"},"net_data":{"image_server":"server"}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("follow.json"),
+            r#"{"creators":[{"id":"creator","name":"name",
+            "avatar":{"fileServer":"","originalName":"","path":""},
+            "followed_at":"2024-01-01T00:00:00Z","last_comic_id":"comic"}]}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("history.json"),
+            r#"{"entries":[{"comicId":"comic","title":"title",
+            "thumb":{"fileServer":"","originalName":"","path":""},
+            "epOrder":2,"pageIndex":7,
+            "createdAt":"2024-01-01T00:00:00Z","updatedAt":"2024-01-02T00:00:00Z"}]}"#,
+        )
+        .unwrap();
+
+        let pool = open_database(&dir).unwrap();
+        let connection = pool.get().unwrap();
+
+        let version: i64 = connection
+            .pragma_query_value(None, "user_version", |row| row.get(0))
+            .unwrap();
+        assert_eq!(version as usize, MIGRATIONS.len());
+
+        // 配置项逐项导入，不包含明文令牌
+        let sections = setting_data::get_config_sections(&connection).unwrap();
+        assert_eq!(sections.len(), 3);
+        assert_eq!(sections["net_data"]["image_server"], "server");
+        assert_eq!(sections["user_data"], serde_json::json!({}));
+
+        let last_comic_id: String = connection
+            .query_row(
+                "SELECT last_comic_id FROM followed_creator WHERE creator_id = 'creator'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(last_comic_id, "comic");
+
+        let page_index: i32 = connection
+            .query_row(
+                "SELECT page_index FROM history WHERE comic_id = 'comic'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(page_index, 7);
+        drop(connection);
+        drop(pool);
+
+        // 再次打开时不会重复导入，也不会覆盖已导入的数据
+        std::fs::write(
+            dir.join("history.json"),
+            r#"{"entries":[{"comicId":"comic","title":"title",
+            "thumb":{"fileServer":"","originalName":"","path":""},
+            "epOrder":2,"pageIndex":9,
+            "createdAt":"2024-01-01T00:00:00Z","updatedAt":"2024-01-03T00:00:00Z"}]}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("config.json"),
+            r#"{"net_data":{"image_server":"new"}}"#,
+        )
+        .unwrap();
+        let pool = open_database(&dir).unwrap();
+        let connection = pool.get().unwrap();
+        assert_eq!(
+            setting_data::get_raw(&connection, "config.net_data").unwrap(),
+            Some(r#"{"image_server":"server"}"#.to_string())
+        );
+        let page_index: i32 = connection
+            .query_row(
+                "SELECT page_index FROM history WHERE comic_id = 'comic'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(page_index, 7);
+
+        drop(connection);
+        drop(pool);
+        let result = std::fs::remove_dir_all(&dir);
+        assert!(result.is_ok());
+    }
+}
@@ -0,0 +1,392 @@
+use crate::api::{
+    error::custom_error::{CustomError, CustomErrorType},
+    storage::{
+        database::{json_column, to_json, with_connection},
+        root::storage_root,
+    },
+    types::comic_info_entity::ComicInfoEntity,
+};
+use chrono::{DateTime, Utc};
+use flutter_rust_bridge::frb;
+use rusqlite::{params, types::Type, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+
+const DOWNLOAD_DIR: &str = "downloads";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DownloadStatus {
+    Pending,
+    Downloading,
+    Completed,
+    Failed,
+}
+
+impl DownloadStatus {
+    #[frb(ignore)]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DownloadStatus::Pending => "pending",
+            DownloadStatus::Downloading => "downloading",
+            DownloadStatus::Completed => "completed",
+            DownloadStatus::Failed => "failed",
+        }
+    }
+
+    fn from_column(row: &Row<'_>, index: usize) -> rusqlite::Result<Self> {
+        let status: String = row.get(index)?;
+        match status.as_str() {
+            "pending" => Ok(DownloadStatus::Pending),
+            "downloading" => Ok(DownloadStatus::Downloading),
+            "completed" => Ok(DownloadStatus::Completed),
+            "failed" => Ok(DownloadStatus::Failed),
+            _ => Err(rusqlite::Error::FromSqlConversionFailure(
+                index,
+                Type::Text,
+                format!("Unknown download status: {}", status).into(),
+            )),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct DownloadComic {
+    /// 下载时保存的漫画详情，用于离线展示。
+    pub info: ComicInfoEntity,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct DownloadEpisode {
+    pub comic_id: String,
+    pub ep_order: i32,
+    pub ep_title: String,
+    pub status: DownloadStatus,
+    pub downloaded_pages: i32,
+    pub total_pages: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+fn download_comic_from_row(row: &Row<'_>) -> rusqlite::Result<DownloadComic> {
+    Ok(DownloadComic {
+        info: json_column(row, 0)?,
+        created_at: row.get(1)?,
+        updated_at: row.get(2)?,
+    })
+}
+
+fn download_episode_from_row(row: &Row<'_>) -> rusqlite::Result<DownloadEpisode> {
+    Ok(DownloadEpisode {
+        comic_id: row.get(0)?,
+        ep_order: row.get(1)?,
+        ep_title: row.get(2)?,
+        status: DownloadStatus::from_column(row, 3)?,
+        downloaded_pages: row.get(4)?,
+        total_pages: row.get(5)?,
+        created_at: row.get(6)?,
+        updated_at: row.get(7)?,
+    })
+}
+
+/// 章节图片的保存目录 `downloads/{comic_id}/{ep_order}`，文件名为从 1 开始的四位页码加原扩展名。
+#[frb(ignore)]
+pub fn download_episode_dir(comic_id: &str, ep_order: i32) -> Result<PathBuf, CustomError> {
+    if comic_id.is_empty() || comic_id.contains(['/', '\\']) || comic_id.starts_with('.') {
+        return Err(CustomError {
+            error_code: CustomErrorType::ParameterError,
+            error_message: format!("Invalid comic id: {}", comic_id),
+        });
+    }
+
+    Ok(storage_root()?
+        .join(DOWNLOAD_DIR)
+        .join(comic_id)
+        .join(ep_order.to_string()))
+}
+
+/// 保存（或更新）下载漫画的详情快照。
+#[frb]
+pub async fn picacg_download_save_comic(info: ComicInfoEntity) -> Result<(), CustomError> {
+    let comic_id = info.id.clone();
+    let info = to_json(&info)?;
+
+    with_connection(move |connection| {
+        connection.execute(
+            "INSERT INTO download_comic (comic_id, info, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?3)
+             ON CONFLICT (comic_id) DO UPDATE SET
+                info = excluded.info,
+                updated_at = excluded.updated_at",
+            params![comic_id, info, Utc::now()],
+        )?;
+        Ok(())
+    })
+    .await
+}
+
+/// 获取所有下载的漫画，按更新时间从新到旧排列。
+#[frb]
+pub async fn picacg_download_comics() -> Result<Vec<DownloadComic>, CustomError> {
+    with_connection(|connection| {
+        Ok(connection
+            .prepare(
+                "SELECT info, created_at, updated_at FROM download_comic
+                 ORDER BY updated_at DESC",
+            )?
+            .query_map([], download_comic_from_row)?
+            .collect::<Result<Vec<DownloadComic>, _>>()?)
+    })
+    .await
+}
+
+/// 获取一部下载的漫画，未下载时返回 `None`。
+#[frb]
+pub async fn picacg_download_comic(comic_id: String) -> Result<Option<DownloadComic>, CustomError> {
+    with_connection(move |connection| {
+        Ok(connection
+            .query_row(
+                "SELECT info, created_at, updated_at FROM download_comic WHERE comic_id = ?1",
+                params![comic_id],
+                download_comic_from_row,
+            )
+            .optional()?)
+    })
+    .await
+}
+
+/// 保存（或更新）章节的下载进度。
+///
+/// 漫画详情需要先通过 `picacg_download_save_comic` 保存。
+///
+/// # 参数
+/// - `comic_id`: 漫画 ID。
+/// - `ep_order`: 章节序号。
+/// - `ep_title`: 章节标题。
+/// - `status`: 下载状态。
+/// - `downloaded_pages`: 已下载的页数。
+/// - `total_pages`: 章节总页数。
+#[frb]
+pub async fn picacg_download_save_episode(
+    comic_id: String,
+    ep_order: i32,
+    ep_title: String,
+    status: DownloadStatus,
+    downloaded_pages: i32,
+    total_pages: i32,
+) -> Result<DownloadEpisode, CustomError> {
+    with_connection(move |connection| {
+        connection.execute(
+            "INSERT INTO download_episode
+                (comic_id, ep_order, ep_title, status, downloaded_pages, total_pages,
+                 created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)
+             ON CONFLICT (comic_id, ep_order) DO UPDATE SET
+                ep_title = excluded.ep_title,
+                status = excluded.status,
+                downloaded_pages = excluded.downloaded_pages,
+                total_pages = excluded.total_pages,
+                updated_at = excluded.updated_at",
+            params![
+                comic_id,
+                ep_order,
+                ep_title,
+                status.as_str(),
+                downloaded_pages,
+                total_pages,
+                Utc::now(),
+            ],
+        )?;
+
+        Ok(connection.query_row(
+            "SELECT comic_id, ep_order, ep_title, status, downloaded_pages, total_pages,
+                    created_at, updated_at
+             FROM download_episode WHERE comic_id = ?1 AND ep_order = ?2",
+            params![comic_id, ep_order],
+            download_episode_from_row,
+        )?)
+    })
+    .await
+}
+
+/// 获取一部漫画所有章节的下载进度，按章节序号排列。
+#[frb]
+pub async fn picacg_download_episodes(
+    comic_id: String,
+) -> Result<Vec<DownloadEpisode>, CustomError> {
+    with_connection(move |connection| {
+        Ok(connection
+            .prepare(
+                "SELECT comic_id, ep_order, ep_title, status, downloaded_pages, total_pages,
+                        created_at, updated_at
+                 FROM download_episode WHERE comic_id = ?1 ORDER BY ep_order",
+            )?
+            .query_map(params![comic_id], download_episode_from_row)?
+            .collect::<Result<Vec<DownloadEpisode>, _>>()?)
+    })
+    .await
+}
+
+/// 获取所有漫画中已下载完成的章节，按漫画 ID 和章节序号排列。
+#[frb(ignore)]
+pub async fn completed_download_episodes() -> Result<Vec<DownloadEpisode>, CustomError> {
+    with_connection(|connection| {
+        Ok(connection
+            .prepare(
+                "SELECT comic_id, ep_order, ep_title, status, downloaded_pages, total_pages,
+                        created_at, updated_at
+                 FROM download_episode WHERE status = ?1 ORDER BY comic_id, ep_order",
+            )?
+            .query_map(
+                params![DownloadStatus::Completed.as_str()],
+                download_episode_from_row,
+            )?
+            .collect::<Result<Vec<DownloadEpisode>, _>>()?)
+    })
+    .await
+}
+
+/// 章节已下载的图片文件，按页码排列，不包含未写完的临时文件。
+#[frb(ignore)]
+pub fn download_episode_pages(comic_id: &str, ep_order: i32) -> Result<Vec<PathBuf>, CustomError> {
+    let entries = match fs::read_dir(download_episode_dir(comic_id, ep_order)?) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(Vec::new()),
+    };
+
+    let mut pages = entries
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|e| e != "tmp"))
+        .collect::<Vec<_>>();
+    pages.sort();
+    Ok(pages)
+}
+
+/// 删除一部漫画的下载记录及其所有章节。
+#[frb]
+pub async fn picacg_download_delete(comic_id: String) -> Result<(), CustomError> {
+    with_connection(move |connection| {
+        connection.execute(
+            "DELETE FROM download_comic WHERE comic_id = ?1",
+            params![comic_id],
+        )?;
+        Ok(())
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::{
+        storage::{
+            database::open_test_database,
+            download_data::{
+                completed_download_episodes, download_episode_dir, download_episode_pages,
+                picacg_download_comic, picacg_download_comics, picacg_download_delete,
+                picacg_download_episodes, picacg_download_save_comic, picacg_download_save_episode,
+                DownloadStatus,
+            },
+        },
+        test_support::comic_info_fixture,
+    };
+
+    #[tokio::test]
+    async fn test_download() {
+        open_test_database().await;
+
+        picacg_download_save_comic(comic_info_fixture("download"))
+            .await
+            .unwrap();
+        picacg_download_save_episode(
+            "download".to_string(),
+            2,
+            "ep 2".to_string(),
+            DownloadStatus::Pending,
+            0,
+            20,
+        )
+        .await
+        .unwrap();
+        picacg_download_save_episode(
+            "download".to_string(),
+            1,
+            "ep 1".to_string(),
+            DownloadStatus::Downloading,
+            0,
+            10,
+        )
+        .await
+        .unwrap();
+        let episode = picacg_download_save_episode(
+            "download".to_string(),
+            1,
+            "ep 1".to_string(),
+            DownloadStatus::Completed,
+            10,
+            10,
+        )
+        .await
+        .unwrap();
+        assert_eq!(episode.status, DownloadStatus::Completed);
+        assert!(episode.created_at < episode.updated_at);
+        let completed = completed_download_episodes().await.unwrap();
+        assert!(completed
+            .iter()
+            .any(|e| e.comic_id == "download" && e.ep_order == 1));
+        assert!(!completed
+            .iter()
+            .any(|e| e.comic_id == "download" && e.ep_order == 2));
+
+        let dir = download_episode_dir("download", 1).unwrap();
+        std::fs::create_dir_all(&dir).unwrap();
+        for name in ["0002.jpg", "0001.png", "0003.jpg.tmp"] {
+            std::fs::write(dir.join(name), b"page").unwrap();
+        }
+        assert_eq!(
+            download_episode_pages("download", 1)
+                .unwrap()
+                .iter()
+                .map(|path| path.file_name().unwrap().to_string_lossy().to_string())
+                .collect::<Vec<_>>(),
+            vec!["0001.png", "0002.jpg"]
+        );
+        assert!(download_episode_pages("download", 2).unwrap().is_empty());
+        assert!(download_episode_dir("../download", 1).is_err());
+
+        let comic = picacg_download_comic("download".to_string())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(comic.info.title, "title download");
+        assert!(picacg_download_comics()
+            .await
+            .unwrap()
+            .iter()
+            .any(|c| c.info.id == "download"));
+
+        let episodes = picacg_download_episodes("download".to_string())
+            .await
+            .unwrap();
+        assert_eq!(
+            episodes.iter().map(|e| e.ep_order).collect::<Vec<i32>>(),
+            vec![1, 2]
+        );
+
+        picacg_download_delete("download".to_string())
+            .await
+            .unwrap();
+        assert!(picacg_download_comic("download".to_string())
+            .await
+            .unwrap()
+            .is_none());
+        assert!(picacg_download_episodes("download".to_string())
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
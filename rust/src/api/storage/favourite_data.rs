@@ -0,0 +1,453 @@
+use crate::api::{
+    error::custom_error::CustomError,
+    storage::{
+        database::{json_column, to_json, with_connection},
+        setting_data,
+    },
+    types::{
+        comic_entity::ComicEntity,
+        page_data::{FavouriteSnapshotPageData, PageData},
+    },
+    utils::chinese::normalize_chinese,
+};
+use chrono::{DateTime, Utc};
+use flutter_rust_bridge::frb;
+use rusqlite::{params, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const FAVOURITE_SYNCED_AT_KEY: &str = "favourite.synced_at";
+
+const FAVOURITE_SNAPSHOT_COLUMNS: &str = "comic, created_at, updated_at, last_updated_at";
+
+/// 章节数或页数变化时刷新 `last_updated_at`。
+const UPSERT_FAVOURITE_SNAPSHOT: &str =
+    "INSERT INTO favourite_snapshot (comic_id, comic, created_at, updated_at, last_updated_at)
+     VALUES (?1, ?2, ?3, ?3, ?3)
+     ON CONFLICT (comic_id) DO UPDATE SET
+        comic = excluded.comic,
+        updated_at = excluded.updated_at,
+        last_updated_at = CASE
+            WHEN json_extract(favourite_snapshot.comic, '$.epsCount')
+                    IS NOT json_extract(excluded.comic, '$.epsCount')
+                OR json_extract(favourite_snapshot.comic, '$.pagesCount')
+                    IS NOT json_extract(excluded.comic, '$.pagesCount')
+            THEN excluded.updated_at
+            ELSE favourite_snapshot.last_updated_at
+        END";
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct FavouriteSnapshot {
+    pub comic: ComicEntity,
+    /// 第一次保存快照的时间。
+    pub created_at: DateTime<Utc>,
+    /// 最后一次更新快照的时间。
+    pub updated_at: DateTime<Utc>,
+    /// 最后一次发现章节数或页数变化的时间。
+    pub last_updated_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct FavouriteUpdate {
+    pub comic: ComicEntity,
+    pub previous_eps_count: i32,
+    pub previous_pages_count: i32,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct FavouriteSyncResult {
+    /// 上次同步后新收藏的漫画。
+    pub added: Vec<ComicEntity>,
+    /// 上次同步后取消收藏的漫画。
+    pub removed: Vec<ComicEntity>,
+    /// 章节数或页数发生变化的漫画。
+    pub updated: Vec<FavouriteUpdate>,
+    /// 上次同步的时间，第一次同步时为空。
+    pub previous_synced_at: Option<DateTime<Utc>>,
+    pub synced_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FavouriteSort {
+    /// 与服务器的收藏顺序一致
+    #[default]
+    Favourited,
+    /// 最近更新
+    RecentlyUpdated,
+    /// 标题
+    Title,
+    /// 作者
+    Author,
+    /// 最多章节
+    EpsMost,
+    /// 最多页数
+    PagesMost,
+    /// 最多点赞
+    LikeMost,
+    /// 最多观看
+    ViewMost,
+}
+
+impl FavouriteSort {
+    fn order_by(&self) -> &'static str {
+        match self {
+            FavouriteSort::Favourited => "position",
+            FavouriteSort::RecentlyUpdated => "last_updated_at DESC",
+            FavouriteSort::Title => "json_extract(comic, '$.title')",
+            FavouriteSort::Author => "json_extract(comic, '$.author')",
+            FavouriteSort::EpsMost => "json_extract(comic, '$.epsCount') DESC",
+            FavouriteSort::PagesMost => "json_extract(comic, '$.pagesCount') DESC",
+            FavouriteSort::LikeMost => "json_extract(comic, '$.totalLikes') DESC",
+            FavouriteSort::ViewMost => "json_extract(comic, '$.totalViews') DESC",
+        }
+    }
+}
+
+fn favourite_snapshot_from_row(row: &Row<'_>) -> rusqlite::Result<FavouriteSnapshot> {
+    Ok(FavouriteSnapshot {
+        comic: json_column(row, 0)?,
+        created_at: row.get(1)?,
+        updated_at: row.get(2)?,
+        last_updated_at: row.get(3)?,
+    })
+}
+
+/// 将搜索内容按空白拆分为关键词，转为小写和简体。
+fn search_terms(query: &str) -> Vec<String> {
+    query.split_whitespace().map(normalize_chinese).collect()
+}
+
+/// 漫画的标题、作者、分类或标签包含所有关键词时匹配，忽略大小写和简繁写法。
+fn matches_terms(comic: &ComicEntity, terms: &[String]) -> bool {
+    let text = [comic.title.as_str(), comic.author.as_str()]
+        .into_iter()
+        .chain(comic.categories.iter().map(String::as_str))
+        .chain(comic.tags.iter().map(String::as_str))
+        .collect::<Vec<&str>>()
+        .join("\n");
+    let text = normalize_chinese(&text);
+
+    terms.iter().all(|term| text.contains(term.as_str()))
+}
+
+/// 保存（或更新）收藏漫画的快照。
+#[frb]
+pub async fn picacg_favourite_snapshot_save(comics: Vec<ComicEntity>) -> Result<(), CustomError> {
+    let comics = comics
+        .iter()
+        .map(|comic| Ok((comic.id.clone(), to_json(comic)?)))
+        .collect::<Result<Vec<(String, String)>, CustomError>>()?;
+
+    with_connection(move |connection| {
+        let now = Utc::now();
+        let transaction = connection.transaction()?;
+        for (comic_id, comic) in comics {
+            transaction.execute(UPSERT_FAVOURITE_SNAPSHOT, params![comic_id, comic, now])?;
+        }
+        transaction.commit()?;
+        Ok(())
+    })
+    .await
+}
+
+/// 用服务器的完整收藏列表替换本地镜像，并返回与上次同步相比的变化。
+///
+/// `comics` 需按服务器的收藏顺序排列，不在列表中的快照会被删除。
+#[frb(ignore)]
+pub async fn sync_favourite_snapshots(
+    comics: Vec<ComicEntity>,
+) -> Result<FavouriteSyncResult, CustomError> {
+    let comics = comics
+        .into_iter()
+        .map(|comic| Ok((to_json(&comic)?, comic)))
+        .collect::<Result<Vec<(String, ComicEntity)>, CustomError>>()?;
+
+    with_connection(move |connection| {
+        let synced_at = Utc::now();
+        let transaction = connection.transaction()?;
+
+        let mut previous = transaction
+            .prepare("SELECT comic_id, comic FROM favourite_snapshot")?
+            .query_map([], |row| Ok((row.get(0)?, json_column(row, 1)?)))?
+            .collect::<Result<HashMap<String, ComicEntity>, _>>()?;
+        let previous_synced_at = setting_data::get_raw(&transaction, FAVOURITE_SYNCED_AT_KEY)?
+            .and_then(|value| serde_json::from_str(&value).ok());
+
+        let mut added = Vec::new();
+        let mut updated = Vec::new();
+        for (position, (json, comic)) in comics.into_iter().enumerate() {
+            transaction.execute(
+                UPSERT_FAVOURITE_SNAPSHOT,
+                params![comic.id, json, synced_at],
+            )?;
+            transaction.execute(
+                "UPDATE favourite_snapshot SET position = ?2 WHERE comic_id = ?1",
+                params![comic.id, position as i64],
+            )?;
+
+            match previous.remove(&comic.id) {
+                None => added.push(comic),
+                Some(old)
+                    if old.eps_count != comic.eps_count || old.pages_count != comic.pages_count =>
+                {
+                    updated.push(FavouriteUpdate {
+                        previous_eps_count: old.eps_count,
+                        previous_pages_count: old.pages_count,
+                        comic,
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+
+        let mut removed = previous.into_values().collect::<Vec<ComicEntity>>();
+        removed.sort_by(|a, b| a.id.cmp(&b.id));
+        for comic in &removed {
+            transaction.execute(
+                "DELETE FROM favourite_snapshot WHERE comic_id = ?1",
+                params![comic.id],
+            )?;
+        }
+
+        setting_data::set_raw(&transaction, FAVOURITE_SYNCED_AT_KEY, &to_json(&synced_at)?)?;
+        transaction.commit()?;
+
+        Ok(FavouriteSyncResult {
+            added,
+            removed,
+            updated,
+            previous_synced_at,
+            synced_at,
+        })
+    })
+    .await
+}
+
+/// 获取上次同步收藏的时间，从未同步时返回 `None`。
+#[frb]
+pub async fn picacg_favourite_synced_at() -> Result<Option<DateTime<Utc>>, CustomError> {
+    setting_data::get_setting(FAVOURITE_SYNCED_AT_KEY).await
+}
+
+/// 获取所有收藏漫画的快照，按第一次保存的时间从新到旧排列。
+#[frb]
+pub async fn picacg_favourite_snapshots() -> Result<Vec<FavouriteSnapshot>, CustomError> {
+    with_connection(|connection| {
+        Ok(connection
+            .prepare(&format!(
+                "SELECT {} FROM favourite_snapshot ORDER BY created_at DESC, comic_id",
+                FAVOURITE_SNAPSHOT_COLUMNS
+            ))?
+            .query_map([], favourite_snapshot_from_row)?
+            .collect::<Result<Vec<FavouriteSnapshot>, _>>()?)
+    })
+    .await
+}
+
+/// 获取一部漫画的收藏快照。
+///
+/// # 返回
+/// - `Ok(Some(FavouriteSnapshot))`：漫画在本地收藏镜像中。
+/// - `Ok(None)`：漫画不在本地收藏镜像中。
+#[frb]
+pub async fn picacg_favourite_snapshot(
+    comic_id: String,
+) -> Result<Option<FavouriteSnapshot>, CustomError> {
+    with_connection(move |connection| {
+        Ok(connection
+            .query_row(
+                &format!(
+                    "SELECT {} FROM favourite_snapshot WHERE comic_id = ?1",
+                    FAVOURITE_SNAPSHOT_COLUMNS
+                ),
+                params![comic_id],
+                favourite_snapshot_from_row,
+            )
+            .optional()?)
+    })
+    .await
+}
+
+/// 在本地收藏镜像中搜索。
+///
+/// 搜索内容按空白拆分为关键词，漫画的标题、作者、分类或标签包含所有关键词时匹配，
+/// 不区分大小写。搜索内容为空时返回全部收藏。
+///
+/// # 参数
+/// - `query`: 搜索内容。
+/// - `sort`: 排序方式（`FavouriteSort` 枚举）。
+/// - `page`: 页码（从 1 开始）。
+/// - `limit`: 每页数量。
+#[frb]
+pub async fn picacg_favourite_search(
+    query: String,
+    sort: FavouriteSort,
+    page: i32,
+    limit: i32,
+) -> Result<FavouriteSnapshotPageData, CustomError> {
+    let terms = search_terms(&query);
+
+    let snapshots = with_connection(move |connection| {
+        Ok(connection
+            .prepare(&format!(
+                "SELECT {} FROM favourite_snapshot ORDER BY {}, comic_id",
+                FAVOURITE_SNAPSHOT_COLUMNS,
+                sort.order_by()
+            ))?
+            .query_map([], favourite_snapshot_from_row)?
+            .collect::<Result<Vec<FavouriteSnapshot>, _>>()?)
+    })
+    .await?;
+
+    let matched = snapshots
+        .into_iter()
+        .filter(|snapshot| matches_terms(&snapshot.comic, &terms))
+        .collect();
+
+    Ok(FavouriteSnapshotPageData::from(PageData::paginate(
+        matched, page, limit,
+    )))
+}
+
+/// 删除一部漫画的收藏快照。
+#[frb]
+pub async fn picacg_favourite_snapshot_delete(comic_id: String) -> Result<(), CustomError> {
+    with_connection(move |connection| {
+        connection.execute(
+            "DELETE FROM favourite_snapshot WHERE comic_id = ?1",
+            params![comic_id],
+        )?;
+        Ok(())
+    })
+    .await
+}
+
+/// 清空所有收藏快照。
+#[frb]
+pub async fn picacg_favourite_snapshot_clear() -> Result<(), CustomError> {
+    with_connection(|connection| {
+        connection.execute("DELETE FROM favourite_snapshot", [])?;
+        connection.execute(
+            "DELETE FROM setting WHERE key = ?1",
+            params![FAVOURITE_SYNCED_AT_KEY],
+        )?;
+        Ok(())
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::{
+        storage::{
+            database::open_test_database,
+            favourite_data::{
+                picacg_favourite_search, picacg_favourite_snapshot_clear,
+                picacg_favourite_snapshot_delete, picacg_favourite_snapshot_save,
+                picacg_favourite_snapshots, sync_favourite_snapshots, FavouriteSort,
+            },
+        },
+        test_support::comic_fixture,
+    };
+    use tokio::sync::Mutex;
+
+    /// 收藏测试共用同一张表，需要依次执行。
+    static FAVOURITE_TEST_LOCK: Mutex<()> = Mutex::const_new(());
+
+    #[tokio::test]
+    async fn test_favourite_snapshot() {
+        let _lock = FAVOURITE_TEST_LOCK.lock().await;
+        open_test_database().await;
+        picacg_favourite_snapshot_clear().await.unwrap();
+
+        picacg_favourite_snapshot_save(vec![comic_fixture("a"), comic_fixture("b")])
+            .await
+            .unwrap();
+        let mut comic = comic_fixture("a");
+        comic.eps_count = 3;
+        picacg_favourite_snapshot_save(vec![comic]).await.unwrap();
+
+        let snapshots = picacg_favourite_snapshots().await.unwrap();
+        assert_eq!(snapshots.len(), 2);
+        let snapshot = snapshots.iter().find(|s| s.comic.id == "a").unwrap();
+        assert_eq!(snapshot.comic.eps_count, 3);
+        assert!(snapshot.created_at < snapshot.updated_at);
+        assert_eq!(snapshot.last_updated_at, snapshot.updated_at);
+
+        picacg_favourite_snapshot_delete("a".to_string())
+            .await
+            .unwrap();
+        let snapshots = picacg_favourite_snapshots().await.unwrap();
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(snapshots[0].comic.id, "b");
+    }
+
+    #[tokio::test]
+    async fn test_favourite_sync_and_search() {
+        let _lock = FAVOURITE_TEST_LOCK.lock().await;
+        open_test_database().await;
+        picacg_favourite_snapshot_clear().await.unwrap();
+
+        let result = sync_favourite_snapshots(vec![comic_fixture("a"), comic_fixture("b")])
+            .await
+            .unwrap();
+        assert_eq!(result.added.len(), 2);
+        assert!(result.previous_synced_at.is_none());
+
+        let mut c = comic_fixture("c");
+        c.author = "Someone".to_string();
+        c.tags = vec!["全彩".to_string()];
+        let mut b = comic_fixture("b");
+        b.eps_count = 2;
+        let result = sync_favourite_snapshots(vec![c, b]).await.unwrap();
+        assert_eq!(
+            result
+                .added
+                .iter()
+                .map(|c| c.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["c"]
+        );
+        assert_eq!(
+            result
+                .removed
+                .iter()
+                .map(|c| c.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a"]
+        );
+        assert_eq!(result.updated.len(), 1);
+        assert_eq!(result.updated[0].comic.id, "b");
+        assert_eq!(result.updated[0].previous_eps_count, 0);
+        assert!(result.previous_synced_at.is_some());
+
+        let page = picacg_favourite_search(String::new(), FavouriteSort::Favourited, 1, 10)
+            .await
+            .unwrap();
+        assert_eq!(
+            page.docs
+                .iter()
+                .map(|s| s.comic.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["c", "b"]
+        );
+
+        let page = picacg_favourite_search("someone 全彩".to_string(), FavouriteSort::Title, 1, 10)
+            .await
+            .unwrap();
+        assert_eq!(page.total, 1);
+        assert_eq!(page.docs[0].comic.id, "c");
+
+        let page = picacg_favourite_search(String::new(), FavouriteSort::EpsMost, 1, 1)
+            .await
+            .unwrap();
+        assert_eq!(page.pages, 2);
+        assert_eq!(page.docs[0].comic.id, "b");
+    }
+}
@@ -0,0 +1,470 @@
+use crate::api::{
+    error::custom_error::{CustomError, CustomErrorType},
+    storage::{
+        database::{json_column, to_json, with_connection},
+        setting_data::{get_setting, set_setting},
+    },
+    types::{
+        comic_entity::ComicEntity,
+        comic_info_entity::ComicInfoEntity,
+        page_data::{FolderComicPageData, PageData},
+    },
+};
+use chrono::{DateTime, Utc};
+use flutter_rust_bridge::frb;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+const FOLDER_SYNC_FAVOURITE_KEY: &str = "folder.sync_favourite";
+
+const FOLDER_COLUMNS: &str = "id, name, position,
+    (SELECT COUNT(*) FROM folder_comic WHERE folder_comic.folder_id = folder.id),
+    created_at, updated_at";
+
+const FOLDER_COMIC_COLUMNS: &str = "folder_comic.comic_id, folder_comic_snapshot.comic,
+    folder_comic_snapshot.info, folder_comic.added_at, folder_comic_snapshot.updated_at";
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct Folder {
+    pub id: i64,
+    pub name: String,
+    /// 排序位置，从 0 开始。
+    pub position: i32,
+    pub comic_count: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct FolderComic {
+    pub comic_id: String,
+    /// 最近一次保存的漫画列表数据。
+    pub comic: Option<ComicEntity>,
+    /// 最近一次保存的漫画详情。
+    pub info: Option<ComicInfoEntity>,
+    /// 加入收藏夹的时间。
+    pub added_at: DateTime<Utc>,
+    /// 最后一次更新元数据快照的时间。
+    pub snapshot_updated_at: DateTime<Utc>,
+}
+
+fn folder_from_row(row: &Row<'_>) -> rusqlite::Result<Folder> {
+    Ok(Folder {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        position: row.get(2)?,
+        comic_count: row.get(3)?,
+        created_at: row.get(4)?,
+        updated_at: row.get(5)?,
+    })
+}
+
+fn folder_comic_from_row(row: &Row<'_>) -> rusqlite::Result<FolderComic> {
+    let comic: Option<String> = row.get(1)?;
+    let info: Option<String> = row.get(2)?;
+
+    Ok(FolderComic {
+        comic_id: row.get(0)?,
+        comic: comic.map(|_| json_column(row, 1)).transpose()?,
+        info: info.map(|_| json_column(row, 2)).transpose()?,
+        added_at: row.get(3)?,
+        snapshot_updated_at: row.get(4)?,
+    })
+}
+
+fn folder_by_id(connection: &Connection, folder_id: i64) -> Result<Folder, CustomError> {
+    connection
+        .query_row(
+            &format!("SELECT {} FROM folder WHERE id = ?1", FOLDER_COLUMNS),
+            params![folder_id],
+            folder_from_row,
+        )
+        .optional()?
+        .ok_or_else(|| CustomError {
+            error_code: CustomErrorType::ParameterError,
+            error_message: format!("Folder {} not found", folder_id),
+        })
+}
+
+/// 去除首尾空白，并检查名称不为空且没有被其他收藏夹使用。
+fn validate_folder_name(
+    connection: &Connection,
+    name: &str,
+    folder_id: Option<i64>,
+) -> Result<String, CustomError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(CustomError {
+            error_code: CustomErrorType::ParameterError,
+            error_message: "Folder name cannot be empty".to_string(),
+        });
+    }
+
+    let existing: Option<i64> = connection
+        .query_row(
+            "SELECT id FROM folder WHERE name = ?1",
+            params![name],
+            |row| row.get(0),
+        )
+        .optional()?;
+    if existing.is_some() && existing != folder_id {
+        return Err(CustomError {
+            error_code: CustomErrorType::ParameterError,
+            error_message: format!("Folder {} already exists", name),
+        });
+    }
+
+    Ok(name.to_string())
+}
+
+/// 删除不在任何收藏夹中的漫画快照。
+fn delete_orphan_snapshots(connection: &Connection) -> Result<(), CustomError> {
+    connection.execute(
+        "DELETE FROM folder_comic_snapshot
+         WHERE comic_id NOT IN (SELECT comic_id FROM folder_comic)",
+        [],
+    )?;
+    Ok(())
+}
+
+/// 创建收藏夹，新收藏夹排在最后。
+///
+/// # 参数
+/// - `name`: 收藏夹名称，不能为空且不能与已有的收藏夹重名。
+#[frb]
+pub async fn picacg_folder_create(name: String) -> Result<Folder, CustomError> {
+    with_connection(move |connection| {
+        let name = validate_folder_name(connection, &name, None)?;
+        let now = Utc::now();
+        connection.execute(
+            "INSERT INTO folder (name, position, created_at, updated_at)
+             VALUES (?1, (SELECT COUNT(*) FROM folder), ?2, ?2)",
+            params![name, now],
+        )?;
+
+        folder_by_id(connection, connection.last_insert_rowid())
+    })
+    .await
+}
+
+/// 获取所有收藏夹，按排序位置排列。
+#[frb]
+pub async fn picacg_folders() -> Result<Vec<Folder>, CustomError> {
+    with_connection(|connection| {
+        Ok(connection
+            .prepare(&format!(
+                "SELECT {} FROM folder ORDER BY position, id",
+                FOLDER_COLUMNS
+            ))?
+            .query_map([], folder_from_row)?
+            .collect::<Result<Vec<Folder>, _>>()?)
+    })
+    .await
+}
+
+/// 重命名收藏夹。
+#[frb]
+pub async fn picacg_folder_rename(folder_id: i64, name: String) -> Result<Folder, CustomError> {
+    with_connection(move |connection| {
+        folder_by_id(connection, folder_id)?;
+        let name = validate_folder_name(connection, &name, Some(folder_id))?;
+        connection.execute(
+            "UPDATE folder SET name = ?2, updated_at = ?3 WHERE id = ?1",
+            params![folder_id, name, Utc::now()],
+        )?;
+
+        folder_by_id(connection, folder_id)
+    })
+    .await
+}
+
+/// 调整收藏夹的顺序。
+///
+/// # 参数
+/// - `folder_ids`: 按新顺序排列的全部收藏夹 ID，必须包含且只包含每个收藏夹一次。
+#[frb]
+pub async fn picacg_folder_reorder(folder_ids: Vec<i64>) -> Result<Vec<Folder>, CustomError> {
+    with_connection(move |connection| {
+        let transaction = connection.transaction()?;
+        let existing = transaction
+            .prepare("SELECT id FROM folder")?
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<HashSet<i64>, _>>()?;
+        let requested = folder_ids.iter().copied().collect::<HashSet<i64>>();
+        if requested.len() != folder_ids.len() || requested != existing {
+            return Err(CustomError {
+                error_code: CustomErrorType::ParameterError,
+                error_message: "Folder ids must contain every folder exactly once".to_string(),
+            });
+        }
+
+        for (position, folder_id) in folder_ids.iter().enumerate() {
+            transaction.execute(
+                "UPDATE folder SET position = ?2 WHERE id = ?1",
+                params![folder_id, position as i64],
+            )?;
+        }
+        transaction.commit()?;
+        Ok(())
+    })
+    .await?;
+
+    picacg_folders().await
+}
+
+/// 删除收藏夹，其他收藏夹的排序位置保持连续。
+#[frb]
+pub async fn picacg_folder_delete(folder_id: i64) -> Result<(), CustomError> {
+    with_connection(move |connection| {
+        let transaction = connection.transaction()?;
+        let folder = folder_by_id(&transaction, folder_id)?;
+        transaction.execute("DELETE FROM folder WHERE id = ?1", params![folder_id])?;
+        transaction.execute(
+            "UPDATE folder SET position = position - 1 WHERE position > ?1",
+            params![folder.position],
+        )?;
+        delete_orphan_snapshots(&transaction)?;
+        transaction.commit()?;
+        Ok(())
+    })
+    .await
+}
+
+/// 将漫画加入收藏夹并保存其元数据快照，已在收藏夹中时只更新快照。
+///
+/// `comic` 和 `info` 为空时保留之前保存的快照。
+#[frb(ignore)]
+pub async fn add_folder_comic(
+    folder_id: i64,
+    comic_id: String,
+    comic: Option<&ComicEntity>,
+    info: Option<&ComicInfoEntity>,
+) -> Result<FolderComic, CustomError> {
+    let comic = comic.map(to_json).transpose()?;
+    let info = info.map(to_json).transpose()?;
+
+    with_connection(move |connection| {
+        let transaction = connection.transaction()?;
+        folder_by_id(&transaction, folder_id)?;
+
+        let now = Utc::now();
+        transaction.execute(
+            "INSERT INTO folder_comic_snapshot (comic_id, comic, info, updated_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT (comic_id) DO UPDATE SET
+                comic = COALESCE(excluded.comic, folder_comic_snapshot.comic),
+                info = COALESCE(excluded.info, folder_comic_snapshot.info),
+                updated_at = excluded.updated_at",
+            params![comic_id, comic, info, now],
+        )?;
+        transaction.execute(
+            "INSERT OR IGNORE INTO folder_comic (folder_id, comic_id, added_at)
+             VALUES (?1, ?2, ?3)",
+            params![folder_id, comic_id, now],
+        )?;
+        transaction.execute(
+            "UPDATE folder SET updated_at = ?2 WHERE id = ?1",
+            params![folder_id, now],
+        )?;
+
+        let folder_comic = transaction.query_row(
+            &format!(
+                "SELECT {} FROM folder_comic
+                 JOIN folder_comic_snapshot USING (comic_id)
+                 WHERE folder_comic.folder_id = ?1 AND folder_comic.comic_id = ?2",
+                FOLDER_COMIC_COLUMNS
+            ),
+            params![folder_id, comic_id],
+            folder_comic_from_row,
+        )?;
+        transaction.commit()?;
+
+        Ok(folder_comic)
+    })
+    .await
+}
+
+/// 将漫画移出收藏夹，漫画不在任何收藏夹中时同时删除其快照。
+#[frb]
+pub async fn picacg_folder_remove_comic(
+    folder_id: i64,
+    comic_id: String,
+) -> Result<(), CustomError> {
+    with_connection(move |connection| {
+        let transaction = connection.transaction()?;
+        let removed = transaction.execute(
+            "DELETE FROM folder_comic WHERE folder_id = ?1 AND comic_id = ?2",
+            params![folder_id, comic_id],
+        )?;
+        if removed > 0 {
+            transaction.execute(
+                "UPDATE folder SET updated_at = ?2 WHERE id = ?1",
+                params![folder_id, Utc::now()],
+            )?;
+        }
+        delete_orphan_snapshots(&transaction)?;
+        transaction.commit()?;
+        Ok(())
+    })
+    .await
+}
+
+/// 分页获取收藏夹中的漫画，按加入时间从新到旧排列。
+///
+/// # 参数
+/// - `folder_id`: 收藏夹 ID。
+/// - `page`: 页码（从 1 开始）。
+/// - `limit`: 每页数量。
+#[frb]
+pub async fn picacg_folder_comics(
+    folder_id: i64,
+    page: i32,
+    limit: i32,
+) -> Result<FolderComicPageData, CustomError> {
+    let page = page.max(1);
+    let limit = limit.max(1);
+
+    with_connection(move |connection| {
+        let total = folder_by_id(connection, folder_id)?.comic_count;
+        let docs = connection
+            .prepare(&format!(
+                "SELECT {} FROM folder_comic
+                 JOIN folder_comic_snapshot USING (comic_id)
+                 WHERE folder_comic.folder_id = ?1
+                 ORDER BY folder_comic.added_at DESC, folder_comic.comic_id
+                 LIMIT ?2 OFFSET ?3",
+                FOLDER_COMIC_COLUMNS
+            ))?
+            .query_map(
+                params![folder_id, limit, (page - 1) * limit],
+                folder_comic_from_row,
+            )?
+            .collect::<Result<Vec<FolderComic>, _>>()?;
+
+        Ok(FolderComicPageData::from(PageData {
+            total,
+            limit,
+            page,
+            pages: (total + limit - 1) / limit,
+            docs,
+        }))
+    })
+    .await
+}
+
+/// 获取包含指定漫画的收藏夹，按排序位置排列。
+#[frb]
+pub async fn picacg_comic_folders(comic_id: String) -> Result<Vec<Folder>, CustomError> {
+    with_connection(move |connection| {
+        Ok(connection
+            .prepare(&format!(
+                "SELECT {} FROM folder
+                 WHERE id IN (SELECT folder_id FROM folder_comic WHERE comic_id = ?1)
+                 ORDER BY position, id",
+                FOLDER_COLUMNS
+            ))?
+            .query_map(params![comic_id], folder_from_row)?
+            .collect::<Result<Vec<Folder>, _>>()?)
+    })
+    .await
+}
+
+/// 加入收藏夹时是否同时在服务器上收藏漫画，默认关闭。
+#[frb]
+pub async fn picacg_folder_sync_favourite() -> Result<bool, CustomError> {
+    Ok(get_setting(FOLDER_SYNC_FAVOURITE_KEY)
+        .await?
+        .unwrap_or_default())
+}
+
+/// 设置加入收藏夹时是否同时在服务器上收藏漫画。
+#[frb]
+pub async fn picacg_folder_set_sync_favourite(enabled: bool) -> Result<(), CustomError> {
+    set_setting(FOLDER_SYNC_FAVOURITE_KEY, &enabled).await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::{
+        error::custom_error::CustomErrorType,
+        storage::{
+            database::open_test_database,
+            folder_data::{
+                add_folder_comic, picacg_comic_folders, picacg_folder_comics, picacg_folder_create,
+                picacg_folder_delete, picacg_folder_remove_comic, picacg_folder_rename,
+                picacg_folder_reorder, picacg_folders,
+            },
+        },
+        test_support::{comic_fixture, comic_info_fixture},
+    };
+
+    #[tokio::test]
+    async fn test_folder() {
+        open_test_database().await;
+        for folder in picacg_folders().await.unwrap() {
+            picacg_folder_delete(folder.id).await.unwrap();
+        }
+
+        let a = picacg_folder_create(" a ".to_string()).await.unwrap();
+        let b = picacg_folder_create("b".to_string()).await.unwrap();
+        assert_eq!(a.name, "a");
+        assert_eq!((a.position, b.position), (0, 1));
+        assert!(matches!(
+            picacg_folder_create("a".to_string())
+                .await
+                .map_err(|e| e.error_code),
+            Err(CustomErrorType::ParameterError)
+        ));
+        assert!(picacg_folder_rename(b.id, "a".to_string()).await.is_err());
+        let b = picacg_folder_rename(b.id, "c".to_string()).await.unwrap();
+        assert_eq!(b.name, "c");
+
+        let folders = picacg_folder_reorder(vec![b.id, a.id]).await.unwrap();
+        assert_eq!(
+            folders.iter().map(|f| f.id).collect::<Vec<_>>(),
+            vec![b.id, a.id]
+        );
+        assert!(picacg_folder_reorder(vec![b.id]).await.is_err());
+
+        let comic = comic_fixture("x");
+        add_folder_comic(a.id, comic.id.clone(), Some(&comic), None)
+            .await
+            .unwrap();
+        let info = comic_info_fixture("x");
+        let folder_comic = add_folder_comic(b.id, info.id.clone(), None, Some(&info))
+            .await
+            .unwrap();
+        assert!(folder_comic.comic.is_some());
+        assert!(folder_comic.info.is_some());
+        assert!(add_folder_comic(-1, "x".to_string(), None, None)
+            .await
+            .is_err());
+
+        assert_eq!(
+            picacg_comic_folders("x".to_string()).await.unwrap().len(),
+            2
+        );
+        let page = picacg_folder_comics(a.id, 1, 10).await.unwrap();
+        assert_eq!(page.total, 1);
+        assert_eq!(page.docs[0].comic_id, "x");
+
+        picacg_folder_remove_comic(a.id, "x".to_string())
+            .await
+            .unwrap();
+        picacg_folder_delete(b.id).await.unwrap();
+        assert!(picacg_comic_folders("x".to_string())
+            .await
+            .unwrap()
+            .is_empty());
+
+        let folders = picacg_folders().await.unwrap();
+        assert_eq!(folders.len(), 1);
+        assert_eq!(folders[0].position, 0);
+        assert_eq!(folders[0].comic_count, 0);
+    }
+}
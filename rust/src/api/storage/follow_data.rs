@@ -0,0 +1,193 @@
+use crate::api::{
+    error::custom_error::CustomError,
+    storage::database::{json_column, to_json, with_connection},
+    types::image_entity::ImageEntity,
+};
+use chrono::{DateTime, Utc};
+use flutter_rust_bridge::frb;
+use rusqlite::{params, Connection, Row};
+use serde::{Deserialize, Serialize};
+
+const FOLLOWED_CREATOR_COLUMNS: &str =
+    "creator_id, name, avatar, followed_at, last_checked_at, last_comic_id, last_uploaded_at";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FollowedCreator {
+    pub id: String,
+    pub name: String,
+    pub avatar: ImageEntity,
+    pub followed_at: DateTime<Utc>,
+    /// 上次检查更新的时间，从未检查过时为 `None`。
+    #[serde(default)]
+    pub last_checked_at: Option<DateTime<Utc>>,
+    /// 上次检查时该创作者最新上传的漫画 ID，没有上传时为空字符串。
+    #[serde(default)]
+    pub last_comic_id: String,
+    /// `last_comic_id` 对应漫画的上传时间，该漫画被删除后据此判断哪些上传是新的。
+    #[serde(default)]
+    pub last_uploaded_at: Option<DateTime<Utc>>,
+}
+
+fn followed_creator_from_row(row: &Row<'_>) -> rusqlite::Result<FollowedCreator> {
+    Ok(FollowedCreator {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        avatar: json_column(row, 2)?,
+        followed_at: row.get(3)?,
+        last_checked_at: row.get(4)?,
+        last_comic_id: row.get(5)?,
+        last_uploaded_at: row.get(6)?,
+    })
+}
+
+/// 写入一条关注记录，已关注时覆盖原有记录。
+#[frb(ignore)]
+pub fn insert_followed_creator(
+    connection: &Connection,
+    creator: &FollowedCreator,
+) -> Result<(), CustomError> {
+    connection.execute(
+        "INSERT INTO followed_creator (creator_id, name, avatar, followed_at, last_checked_at,
+            last_comic_id, last_uploaded_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+         ON CONFLICT (creator_id) DO UPDATE SET
+            name = excluded.name,
+            avatar = excluded.avatar,
+            followed_at = excluded.followed_at,
+            last_checked_at = excluded.last_checked_at,
+            last_comic_id = excluded.last_comic_id,
+            last_uploaded_at = excluded.last_uploaded_at",
+        params![
+            creator.id,
+            creator.name,
+            to_json(&creator.avatar)?,
+            creator.followed_at,
+            creator.last_checked_at,
+            creator.last_comic_id,
+            creator.last_uploaded_at,
+        ],
+    )?;
+    Ok(())
+}
+
+/// 保存关注记录，已关注时刷新其资料和检查进度。
+#[frb(ignore)]
+pub async fn save_followed_creator(creator: FollowedCreator) -> Result<(), CustomError> {
+    with_connection(move |connection| insert_followed_creator(connection, &creator)).await
+}
+
+/// 保存检查进度，检查期间被取消关注的创作者会被忽略。
+#[frb(ignore)]
+pub async fn save_follow_progress(creators: Vec<FollowedCreator>) -> Result<(), CustomError> {
+    with_connection(move |connection| {
+        let transaction = connection.transaction()?;
+        for creator in &creators {
+            transaction.execute(
+                "UPDATE followed_creator
+                 SET last_checked_at = ?2, last_comic_id = ?3, last_uploaded_at = ?4
+                 WHERE creator_id = ?1",
+                params![
+                    creator.id,
+                    creator.last_checked_at,
+                    creator.last_comic_id,
+                    creator.last_uploaded_at,
+                ],
+            )?;
+        }
+        transaction.commit()?;
+        Ok(())
+    })
+    .await
+}
+
+/// 获取已关注的创作者列表，按关注时间从早到晚排列。
+#[frb]
+pub async fn picacg_followed_creators() -> Result<Vec<FollowedCreator>, CustomError> {
+    with_connection(|connection| {
+        Ok(connection
+            .prepare(&format!(
+                "SELECT {} FROM followed_creator ORDER BY followed_at, creator_id",
+                FOLLOWED_CREATOR_COLUMNS
+            ))?
+            .query_map([], followed_creator_from_row)?
+            .collect::<Result<Vec<_>, _>>()?)
+    })
+    .await
+}
+
+/// 取消关注创作者。
+///
+/// # 参数
+/// - `creator_id`: 创作者 ID。
+#[frb]
+pub async fn picacg_unfollow_creator(creator_id: String) -> Result<(), CustomError> {
+    with_connection(move |connection| {
+        connection.execute(
+            "DELETE FROM followed_creator WHERE creator_id = ?1",
+            params![creator_id],
+        )?;
+        Ok(())
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::{
+        storage::{
+            database::open_test_database,
+            follow_data::{
+                picacg_followed_creators, picacg_unfollow_creator, save_follow_progress,
+                save_followed_creator, FollowedCreator,
+            },
+        },
+        types::image_entity::avatar_default,
+    };
+    use chrono::Utc;
+
+    #[tokio::test]
+    async fn test_follow_data_save_read() {
+        open_test_database().await;
+        assert!(picacg_followed_creators().await.unwrap().is_empty());
+
+        let creator = FollowedCreator {
+            id: "creator".to_string(),
+            name: "name".to_string(),
+            avatar: avatar_default(),
+            followed_at: Utc::now(),
+            last_checked_at: None,
+            last_comic_id: "comic".to_string(),
+            last_uploaded_at: None,
+        };
+        save_followed_creator(creator.clone()).await.unwrap();
+
+        let creators = picacg_followed_creators().await.unwrap();
+        assert_eq!(creators.len(), 1);
+        assert_eq!(creators[0].last_comic_id, "comic");
+
+        // 只更新仍在关注的创作者的检查进度
+        let now = Utc::now();
+        let checked = FollowedCreator {
+            last_checked_at: Some(now),
+            last_comic_id: "new".to_string(),
+            last_uploaded_at: Some(now),
+            ..creator.clone()
+        };
+        let unfollowed = FollowedCreator {
+            id: "unfollowed".to_string(),
+            ..checked.clone()
+        };
+        save_follow_progress(vec![checked, unfollowed])
+            .await
+            .unwrap();
+        let creators = picacg_followed_creators().await.unwrap();
+        assert_eq!(creators.len(), 1);
+        assert_eq!(creators[0].last_comic_id, "new");
+        assert_eq!(creators[0].last_uploaded_at, Some(now));
+
+        picacg_unfollow_creator("creator".to_string())
+            .await
+            .unwrap();
+        assert!(picacg_followed_creators().await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,228 @@
+use crate::api::{
+    error::custom_error::CustomError,
+    storage::database::{json_column, to_json, with_connection},
+    types::{
+        comic_info_entity::ComicInfoEntity,
+        image_entity::ImageEntity,
+        page_data::{HistoryPageData, PageData},
+    },
+};
+use chrono::{DateTime, Utc};
+use flutter_rust_bridge::frb;
+use rusqlite::{params, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct HistoryEntry {
+    pub comic_id: String,
+    pub title: String,
+    pub thumb: ImageEntity,
+    /// 最后阅读的章节序号。
+    pub ep_order: i32,
+    /// 最后阅读的页码，在章节内从 0 开始。
+    pub page_index: i32,
+    /// 第一次阅读的时间。
+    pub created_at: DateTime<Utc>,
+    /// 最后一次阅读的时间。
+    pub updated_at: DateTime<Utc>,
+}
+
+const HISTORY_COLUMNS: &str =
+    "comic_id, title, thumb, ep_order, page_index, created_at, updated_at";
+
+fn history_entry_from_row(row: &Row<'_>) -> rusqlite::Result<HistoryEntry> {
+    Ok(HistoryEntry {
+        comic_id: row.get(0)?,
+        title: row.get(1)?,
+        thumb: json_column(row, 2)?,
+        ep_order: row.get(3)?,
+        page_index: row.get(4)?,
+        created_at: row.get(5)?,
+        updated_at: row.get(6)?,
+    })
+}
+
+/// 更新漫画的阅读进度。
+///
+/// 以 `comic` 的标题和封面更新快照，并将最后阅读时间设为当前时间。
+///
+/// # 参数
+/// - `comic`: 正在阅读的漫画详情。
+/// - `ep_order`: 正在阅读的章节序号。
+/// - `page_index`: 正在阅读的页码，在章节内从 0 开始。
+///
+/// # 返回
+/// - `Ok(HistoryEntry)`：更新后的阅读记录。
+/// - `Err(CustomError)`：写入失败时返回错误信息。
+#[frb]
+pub async fn picacg_history_update(
+    comic: ComicInfoEntity,
+    ep_order: i32,
+    page_index: i32,
+) -> Result<HistoryEntry, CustomError> {
+    let thumb = to_json(&comic.thumb)?;
+
+    with_connection(move |connection| {
+        connection.execute(
+            "INSERT INTO history
+                (comic_id, title, thumb, ep_order, page_index, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6)
+             ON CONFLICT (comic_id) DO UPDATE SET
+                title = excluded.title,
+                thumb = excluded.thumb,
+                ep_order = excluded.ep_order,
+                page_index = excluded.page_index,
+                updated_at = excluded.updated_at",
+            params![
+                comic.id,
+                comic.title,
+                thumb,
+                ep_order,
+                page_index,
+                Utc::now()
+            ],
+        )?;
+
+        Ok(connection.query_row(
+            &format!(
+                "SELECT {} FROM history WHERE comic_id = ?1",
+                HISTORY_COLUMNS
+            ),
+            params![comic.id],
+            history_entry_from_row,
+        )?)
+    })
+    .await
+}
+
+/// 分页获取最近阅读的漫画，按最后阅读时间从新到旧排列。
+///
+/// # 参数
+/// - `page`: 页码（从 1 开始）。
+/// - `limit`: 每页数量。
+#[frb]
+pub async fn picacg_history_page(page: i32, limit: i32) -> Result<HistoryPageData, CustomError> {
+    let page = page.max(1);
+    let limit = limit.max(1);
+
+    with_connection(move |connection| {
+        let total: i32 =
+            connection.query_row("SELECT COUNT(*) FROM history", [], |row| row.get(0))?;
+
+        let docs = connection
+            .prepare(&format!(
+                "SELECT {} FROM history ORDER BY updated_at DESC LIMIT ?1 OFFSET ?2",
+                HISTORY_COLUMNS
+            ))?
+            .query_map(params![limit, (page - 1) * limit], history_entry_from_row)?
+            .collect::<Result<Vec<HistoryEntry>, _>>()?;
+
+        Ok(HistoryPageData::from(PageData {
+            total,
+            limit,
+            page,
+            pages: (total + limit - 1) / limit,
+            docs,
+        }))
+    })
+    .await
+}
+
+/// 获取漫画的阅读进度，用于从上次阅读的章节和页码继续阅读。
+///
+/// # 返回
+/// - `Ok(Some(HistoryEntry))`：存在阅读记录。
+/// - `Ok(None)`：没有读过这部漫画。
+#[frb]
+pub async fn picacg_history_resume(comic_id: String) -> Result<Option<HistoryEntry>, CustomError> {
+    with_connection(move |connection| {
+        Ok(connection
+            .query_row(
+                &format!(
+                    "SELECT {} FROM history WHERE comic_id = ?1",
+                    HISTORY_COLUMNS
+                ),
+                params![comic_id],
+                history_entry_from_row,
+            )
+            .optional()?)
+    })
+    .await
+}
+
+/// 删除一部漫画的阅读记录。
+#[frb]
+pub async fn picacg_history_delete(comic_id: String) -> Result<(), CustomError> {
+    with_connection(move |connection| {
+        connection.execute("DELETE FROM history WHERE comic_id = ?1", params![comic_id])?;
+        Ok(())
+    })
+    .await
+}
+
+/// 清空所有阅读记录。
+#[frb]
+pub async fn picacg_history_clear() -> Result<(), CustomError> {
+    with_connection(|connection| {
+        connection.execute("DELETE FROM history", [])?;
+        Ok(())
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::{
+        storage::{
+            database::open_test_database,
+            history_data::{
+                picacg_history_clear, picacg_history_delete, picacg_history_page,
+                picacg_history_resume, picacg_history_update,
+            },
+        },
+        test_support::comic_info_fixture,
+    };
+
+    #[tokio::test]
+    async fn test_history() {
+        open_test_database().await;
+        picacg_history_clear().await.unwrap();
+
+        picacg_history_update(comic_info_fixture("a"), 1, 5)
+            .await
+            .unwrap();
+        picacg_history_update(comic_info_fixture("b"), 2, 0)
+            .await
+            .unwrap();
+        let first = picacg_history_update(comic_info_fixture("a"), 3, 12)
+            .await
+            .unwrap();
+
+        let page = picacg_history_page(1, 1).await.unwrap();
+        assert_eq!(page.total, 2);
+        assert_eq!(page.pages, 2);
+        assert_eq!(page.docs[0].comic_id, "a");
+        assert_eq!(
+            picacg_history_page(2, 1).await.unwrap().docs[0].comic_id,
+            "b"
+        );
+
+        let resume = picacg_history_resume("a".to_string())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!((resume.ep_order, resume.page_index), (3, 12));
+        assert!(resume.created_at < first.updated_at);
+
+        picacg_history_delete("a".to_string()).await.unwrap();
+        assert!(picacg_history_resume("a".to_string())
+            .await
+            .unwrap()
+            .is_none());
+
+        picacg_history_clear().await.unwrap();
+        assert_eq!(picacg_history_page(1, 20).await.unwrap().total, 0);
+    }
+}
@@ -0,0 +1,198 @@
+use crate::api::{
+    error::custom_error::{CustomError, CustomErrorType},
+    storage::{config::picacg_config_net_data, root::storage_root},
+    types::image_entity::ImageEntity,
+    utils::{
+        client::{send_request, HttpExpectBody, HttpResponseBody},
+        crypto::{random_bytes, sha256_hex},
+        file::write_file_atomic,
+    },
+};
+use flutter_rust_bridge::frb;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+const IMAGE_CACHE_DIR: &str = "cache/images";
+
+/// 拼接图片的完整地址，与 Dart 端 `getImageUrl` 的规则一致，使用配置中的图片服务器。
+#[frb(sync)]
+pub fn picacg_image_url(image: ImageEntity) -> Result<String, CustomError> {
+    Ok(image_url(&picacg_config_net_data()?.image_server, &image))
+}
+
+/// 拼接图片的完整地址。
+///
+/// `image_server` 为配置中的图片服务器，忽略其中 `/static` 及之后的部分。
+#[frb(ignore)]
+pub fn image_url(image_server: &str, image: &ImageEntity) -> String {
+    let image_server = image_server
+        .find("/static")
+        .map_or(image_server, |index| &image_server[..index]);
+    format!(
+        "{}/static/{}",
+        image_server.trim_end_matches('/'),
+        image.path.trim_start_matches('/')
+    )
+}
+
+fn image_cache_dir() -> Result<PathBuf, CustomError> {
+    Ok(storage_root()?.join(IMAGE_CACHE_DIR))
+}
+
+/// 图片在缓存目录中的路径，文件名为图片地址的 SHA-256。
+fn image_cache_path(dir: &Path, url: &str) -> PathBuf {
+    dir.join(sha256_hex(url))
+}
+
+/// 查询图片是否已缓存。
+///
+/// # 参数
+/// - `url`: 图片地址。
+///
+/// # 返回
+/// - `Ok(Some(String))`：已缓存时返回本地文件路径。
+/// - `Ok(None)`：未缓存。
+#[frb(sync)]
+pub fn picacg_image_cached(url: String) -> Result<Option<String>, CustomError> {
+    let path = image_cache_path(&image_cache_dir()?, &url);
+    Ok(path.is_file().then(|| path.to_string_lossy().to_string()))
+}
+
+/// 下载图片的原始数据，状态码不是 200 时返回错误。
+#[frb(ignore)]
+pub async fn download_image(url: &str) -> Result<Vec<u8>, CustomError> {
+    let response = send_request("GET", url, None, None, None, Some(HttpExpectBody::Bytes))
+        .await
+        .map_err(|e| CustomError {
+            error_code: CustomErrorType::BadRequest,
+            error_message: format!("Failed to download image: {}", e),
+        })?;
+
+    match response.body {
+        HttpResponseBody::Bytes(bytes) if response.status_code == 200 => Ok(bytes),
+        _ => Err(CustomError {
+            error_code: CustomErrorType::BadRequest,
+            error_message: format!(
+                "Failed to download image: status code {}",
+                response.status_code
+            ),
+        }),
+    }
+}
+
+/// 获取图片的本地文件路径，未缓存时先下载到缓存目录。
+///
+/// # 参数
+/// - `url`: 图片地址。
+///
+/// # 返回
+/// - `Ok(String)`：本地文件路径。
+/// - `Err(CustomError)`：下载失败或写入失败时返回错误信息。
+#[frb]
+pub async fn picacg_image_fetch(url: String) -> Result<String, CustomError> {
+    if let Some(path) = picacg_image_cached(url.clone())? {
+        return Ok(path);
+    }
+
+    let bytes = download_image(&url).await?;
+
+    let dir = image_cache_dir()?;
+    tokio::task::spawn_blocking(move || {
+        fs::create_dir_all(&dir).map_err(|e| CustomError {
+            error_code: CustomErrorType::FileWriteError,
+            error_message: format!("Failed to create image cache directory: {}", e),
+        })?;
+
+        // 同一图片可能被同时下载，临时文件名需要互不相同
+        let path = image_cache_path(&dir, &url);
+        let temp_path = path.with_extension(format!("{}.tmp", hex::encode(random_bytes::<8>())));
+        write_file_atomic(&path, &temp_path, &bytes)?;
+
+        Ok(path.to_string_lossy().to_string())
+    })
+    .await
+    .map_err(|e| CustomError {
+        error_code: CustomErrorType::UnKnownError,
+        error_message: format!("Image cache task failed: {}", e),
+    })?
+}
+
+/// 获取图片缓存占用的字节数。
+#[frb]
+pub fn picacg_image_cache_size() -> Result<u64, CustomError> {
+    let entries = match fs::read_dir(image_cache_dir()?) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(0),
+    };
+
+    Ok(entries
+        .filter_map(|entry| entry.ok()?.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .sum())
+}
+
+/// 清空图片缓存。
+#[frb]
+pub fn picacg_image_cache_clear() -> Result<(), CustomError> {
+    let dir = image_cache_dir()?;
+    if !dir.exists() {
+        return Ok(());
+    }
+
+    fs::remove_dir_all(&dir).map_err(|e| CustomError {
+        error_code: CustomErrorType::FileWriteError,
+        error_message: format!("Failed to clear image cache: {}", e),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::{
+        storage::{
+            image_cache::{image_cache_dir, image_cache_path, image_url, picacg_image_cached},
+            root::init_test_storage,
+        },
+        types::image_entity::ImageEntity,
+    };
+    use std::fs;
+
+    #[test]
+    fn test_image_url() {
+        // 图片自带的 fileServer 不参与拼接
+        let image = |path: &str| ImageEntity {
+            file_server: "https://ignored.example.com".to_string(),
+            original_name: String::new(),
+            path: path.to_string(),
+        };
+
+        assert_eq!(
+            image_url("https://example.com/", &image("/a.jpg")),
+            "https://example.com/static/a.jpg"
+        );
+        assert_eq!(
+            image_url("https://example.com/static/", &image("a.jpg")),
+            "https://example.com/static/a.jpg"
+        );
+        assert_eq!(
+            image_url("https://example.com/static/tobeimg", &image("a.jpg")),
+            "https://example.com/static/a.jpg"
+        );
+    }
+
+    #[test]
+    fn test_picacg_image_cached() {
+        init_test_storage();
+        let url = "https://example.com/static/cached.jpg".to_string();
+        assert!(picacg_image_cached(url.clone()).unwrap().is_none());
+
+        let dir = image_cache_dir().unwrap();
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(image_cache_path(&dir, &url), b"image").unwrap();
+
+        let path = picacg_image_cached(url).unwrap().unwrap();
+        assert_eq!(fs::read(path).unwrap(), b"image");
+    }
+}
@@ -1,3 +1,19 @@
+pub mod blocklist_data;
+pub mod comic_index_data;
 pub mod config;
+pub mod database;
+pub mod download_data;
+pub mod favourite_data;
+pub mod folder_data;
+pub mod follow_data;
+pub mod history_data;
+pub mod image_cache;
 pub mod net_data;
+pub mod outbox_data;
+pub mod punch_in_data;
+pub mod root;
+pub mod search_data;
+pub mod secret_data;
+pub mod setting_data;
+pub mod subscription_data;
 pub mod user_data;
@@ -0,0 +1,427 @@
+use crate::api::{
+    error::custom_error::{CustomError, CustomErrorType},
+    storage::database::with_connection,
+    types::comic_info_entity::ComicInfoEntity,
+};
+use chrono::{DateTime, Utc};
+use flutter_rust_bridge::frb;
+use rusqlite::{params, types::Type, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+
+const OUTBOX_COLUMNS: &str =
+    "id, kind, comic_id, desired, content, status, attempts, last_error, created_at, updated_at";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutboxKind {
+    /// 点赞或取消点赞
+    Like,
+    /// 收藏或取消收藏
+    Favourite,
+    /// 发表评论
+    Comment,
+}
+
+impl OutboxKind {
+    #[frb(ignore)]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutboxKind::Like => "like",
+            OutboxKind::Favourite => "favourite",
+            OutboxKind::Comment => "comment",
+        }
+    }
+
+    fn from_column(row: &Row<'_>, index: usize) -> rusqlite::Result<Self> {
+        let kind: String = row.get(index)?;
+        match kind.as_str() {
+            "like" => Ok(OutboxKind::Like),
+            "favourite" => Ok(OutboxKind::Favourite),
+            "comment" => Ok(OutboxKind::Comment),
+            _ => Err(rusqlite::Error::FromSqlConversionFailure(
+                index,
+                Type::Text,
+                format!("Unknown outbox kind: {}", kind).into(),
+            )),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutboxStatus {
+    /// 等待发送
+    Pending,
+    /// 服务器拒绝，不再自动重试
+    Failed,
+}
+
+impl OutboxStatus {
+    #[frb(ignore)]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutboxStatus::Pending => "pending",
+            OutboxStatus::Failed => "failed",
+        }
+    }
+
+    fn from_column(row: &Row<'_>, index: usize) -> rusqlite::Result<Self> {
+        let status: String = row.get(index)?;
+        match status.as_str() {
+            "pending" => Ok(OutboxStatus::Pending),
+            "failed" => Ok(OutboxStatus::Failed),
+            _ => Err(rusqlite::Error::FromSqlConversionFailure(
+                index,
+                Type::Text,
+                format!("Unknown outbox status: {}", status).into(),
+            )),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct OutboxAction {
+    pub id: i64,
+    pub kind: OutboxKind,
+    pub comic_id: String,
+    /// 点赞或收藏的目标状态，评论时为空。
+    pub desired: Option<bool>,
+    /// 评论内容，点赞和收藏时为空。
+    pub content: Option<String>,
+    pub status: OutboxStatus,
+    /// 已尝试发送的次数。
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// 最后一次修改的时间，发送期间被修改的操作不会被移出队列。
+    pub updated_at: DateTime<Utc>,
+}
+
+fn outbox_action_from_row(row: &Row<'_>) -> rusqlite::Result<OutboxAction> {
+    Ok(OutboxAction {
+        id: row.get(0)?,
+        kind: OutboxKind::from_column(row, 1)?,
+        comic_id: row.get(2)?,
+        desired: row.get(3)?,
+        content: row.get(4)?,
+        status: OutboxStatus::from_column(row, 5)?,
+        attempts: row.get(6)?,
+        last_error: row.get(7)?,
+        created_at: row.get(8)?,
+        updated_at: row.get(9)?,
+    })
+}
+
+fn outbox_action_by_id(connection: &Connection, id: i64) -> Result<OutboxAction, CustomError> {
+    connection
+        .query_row(
+            &format!("SELECT {} FROM outbox WHERE id = ?1", OUTBOX_COLUMNS),
+            params![id],
+            outbox_action_from_row,
+        )
+        .optional()?
+        .ok_or_else(|| CustomError {
+            error_code: CustomErrorType::ParameterError,
+            error_message: format!("Outbox action {} not found", id),
+        })
+}
+
+/// 记录点赞或收藏的目标状态。
+///
+/// 同一部漫画同一类操作只保留一条记录，重复操作时更新目标状态并重新等待发送。
+#[frb(ignore)]
+pub async fn enqueue_toggle(
+    kind: OutboxKind,
+    comic_id: String,
+    desired: bool,
+) -> Result<OutboxAction, CustomError> {
+    with_connection(move |connection| {
+        let now = Utc::now();
+        let transaction = connection.transaction()?;
+        let existing: Option<i64> = transaction
+            .query_row(
+                "SELECT id FROM outbox WHERE kind = ?1 AND comic_id = ?2",
+                params![kind.as_str(), comic_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        let id = match existing {
+            Some(id) => {
+                transaction.execute(
+                    "UPDATE outbox SET desired = ?2, status = ?3, attempts = 0, last_error = NULL,
+                        updated_at = ?4
+                     WHERE id = ?1",
+                    params![id, desired, OutboxStatus::Pending.as_str(), now],
+                )?;
+                id
+            }
+            None => {
+                transaction.execute(
+                    "INSERT INTO outbox (kind, comic_id, desired, status, created_at, updated_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?5)",
+                    params![
+                        kind.as_str(),
+                        comic_id,
+                        desired,
+                        OutboxStatus::Pending.as_str(),
+                        now
+                    ],
+                )?;
+                transaction.last_insert_rowid()
+            }
+        };
+
+        let action = outbox_action_by_id(&transaction, id)?;
+        transaction.commit()?;
+        Ok(action)
+    })
+    .await
+}
+
+/// 记录待发表的评论。
+#[frb(ignore)]
+pub async fn enqueue_comment(
+    comic_id: String,
+    content: String,
+) -> Result<OutboxAction, CustomError> {
+    with_connection(move |connection| {
+        connection.execute(
+            "INSERT INTO outbox (kind, comic_id, content, status, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?5)",
+            params![
+                OutboxKind::Comment.as_str(),
+                comic_id,
+                content,
+                OutboxStatus::Pending.as_str(),
+                Utc::now()
+            ],
+        )?;
+
+        outbox_action_by_id(connection, connection.last_insert_rowid())
+    })
+    .await
+}
+
+/// 获取最早记录的等待发送的操作。
+#[frb(ignore)]
+pub async fn next_pending_action() -> Result<Option<OutboxAction>, CustomError> {
+    with_connection(|connection| {
+        Ok(connection
+            .query_row(
+                &format!(
+                    "SELECT {} FROM outbox WHERE status = ?1 ORDER BY id LIMIT 1",
+                    OUTBOX_COLUMNS
+                ),
+                params![OutboxStatus::Pending.as_str()],
+                outbox_action_from_row,
+            )
+            .optional()?)
+    })
+    .await
+}
+
+/// 发送成功后移出队列。发送期间操作被修改时保留，之后按新的目标状态重新发送。
+///
+/// # 返回
+/// - `Ok(true)`：已移出队列。
+#[frb(ignore)]
+pub async fn complete_action(action: &OutboxAction) -> Result<bool, CustomError> {
+    let id = action.id;
+    let updated_at = action.updated_at;
+
+    with_connection(move |connection| {
+        Ok(connection.execute(
+            "DELETE FROM outbox WHERE id = ?1 AND updated_at = ?2",
+            params![id, updated_at],
+        )? > 0)
+    })
+    .await
+}
+
+/// 记录一次发送失败。`give_up` 为 `true` 时不再自动重试。
+#[frb(ignore)]
+pub async fn fail_action(
+    action: &OutboxAction,
+    error_message: String,
+    give_up: bool,
+) -> Result<OutboxAction, CustomError> {
+    let id = action.id;
+    let updated_at = action.updated_at;
+    let status = match give_up {
+        true => OutboxStatus::Failed,
+        false => OutboxStatus::Pending,
+    };
+
+    with_connection(move |connection| {
+        connection.execute(
+            "UPDATE outbox SET attempts = attempts + 1, last_error = ?3,
+                status = CASE WHEN updated_at = ?2 THEN ?4 ELSE status END
+             WHERE id = ?1",
+            params![id, updated_at, error_message, status.as_str()],
+        )?;
+
+        outbox_action_by_id(connection, id)
+    })
+    .await
+}
+
+/// 获取队列中的所有操作，按记录顺序排列。
+#[frb]
+pub async fn picacg_outbox_actions() -> Result<Vec<OutboxAction>, CustomError> {
+    with_connection(|connection| {
+        Ok(connection
+            .prepare(&format!(
+                "SELECT {} FROM outbox ORDER BY id",
+                OUTBOX_COLUMNS
+            ))?
+            .query_map([], outbox_action_from_row)?
+            .collect::<Result<Vec<OutboxAction>, _>>()?)
+    })
+    .await
+}
+
+/// 获取一部漫画在队列中的操作，按记录顺序排列。
+#[frb]
+pub async fn picacg_outbox_comic_actions(
+    comic_id: String,
+) -> Result<Vec<OutboxAction>, CustomError> {
+    with_connection(move |connection| {
+        Ok(connection
+            .prepare(&format!(
+                "SELECT {} FROM outbox WHERE comic_id = ?1 ORDER BY id",
+                OUTBOX_COLUMNS
+            ))?
+            .query_map(params![comic_id], outbox_action_from_row)?
+            .collect::<Result<Vec<OutboxAction>, _>>()?)
+    })
+    .await
+}
+
+/// 将失败的操作重新放回队列。
+#[frb]
+pub async fn picacg_outbox_retry(id: i64) -> Result<OutboxAction, CustomError> {
+    with_connection(move |connection| {
+        connection.execute(
+            "UPDATE outbox SET status = ?2, attempts = 0, updated_at = ?3 WHERE id = ?1",
+            params![id, OutboxStatus::Pending.as_str(), Utc::now()],
+        )?;
+
+        outbox_action_by_id(connection, id)
+    })
+    .await
+}
+
+/// 放弃一个操作并移出队列。
+#[frb]
+pub async fn picacg_outbox_discard(id: i64) -> Result<(), CustomError> {
+    with_connection(move |connection| {
+        connection.execute("DELETE FROM outbox WHERE id = ?1", params![id])?;
+        Ok(())
+    })
+    .await
+}
+
+/// 将队列中的点赞和收藏的目标状态应用到漫画详情上，用于在发送前显示操作后的状态。
+///
+/// 只应用等待发送的操作，被服务器拒绝的操作不会生效。
+#[frb]
+pub async fn picacg_outbox_apply_info(
+    mut info: ComicInfoEntity,
+) -> Result<ComicInfoEntity, CustomError> {
+    for action in picacg_outbox_comic_actions(info.id.clone())
+        .await?
+        .into_iter()
+        .filter(|action| action.status == OutboxStatus::Pending)
+    {
+        match (action.kind, action.desired) {
+            (OutboxKind::Like, Some(liked)) if liked != info.is_liked => {
+                info.is_liked = liked;
+                info.likes_count += if liked { 1 } else { -1 };
+            }
+            (OutboxKind::Favourite, Some(favourite)) => info.is_favourite = favourite,
+            _ => {}
+        }
+    }
+
+    Ok(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::{
+        storage::{
+            database::open_test_database,
+            outbox_data::{
+                complete_action, enqueue_comment, enqueue_toggle, fail_action,
+                picacg_outbox_apply_info, picacg_outbox_comic_actions, picacg_outbox_discard,
+                picacg_outbox_retry, OutboxKind, OutboxStatus,
+            },
+        },
+        test_support::comic_info_fixture,
+    };
+
+    #[tokio::test]
+    async fn test_outbox() {
+        open_test_database().await;
+        let comic_id = "outbox".to_string();
+        for action in picacg_outbox_comic_actions(comic_id.clone()).await.unwrap() {
+            picacg_outbox_discard(action.id).await.unwrap();
+        }
+
+        let like = enqueue_toggle(OutboxKind::Like, comic_id.clone(), true)
+            .await
+            .unwrap();
+        enqueue_comment(comic_id.clone(), "hello".to_string())
+            .await
+            .unwrap();
+        let unlike = enqueue_toggle(OutboxKind::Like, comic_id.clone(), false)
+            .await
+            .unwrap();
+        assert_eq!(like.id, unlike.id);
+        assert_eq!(unlike.desired, Some(false));
+
+        // 发送期间被修改的操作不会被移出队列
+        assert!(!complete_action(&like).await.unwrap());
+        let failed = fail_action(&unlike, "rejected".to_string(), true)
+            .await
+            .unwrap();
+        assert_eq!(failed.status, OutboxStatus::Failed);
+        assert_eq!(failed.attempts, 1);
+        let retried = picacg_outbox_retry(failed.id).await.unwrap();
+        assert_eq!(retried.status, OutboxStatus::Pending);
+
+        enqueue_toggle(OutboxKind::Favourite, comic_id.clone(), true)
+            .await
+            .unwrap();
+        let mut info = comic_info_fixture(&comic_id);
+        info.is_liked = true;
+        info.likes_count = 5;
+        let info = picacg_outbox_apply_info(info).await.unwrap();
+        assert!(!info.is_liked);
+        assert_eq!(info.likes_count, 4);
+        assert!(info.is_favourite);
+
+        // 失败的操作不应用到漫画详情上
+        let failed = fail_action(&retried, "rejected".to_string(), true)
+            .await
+            .unwrap();
+        let mut info = comic_info_fixture(&comic_id);
+        info.is_liked = true;
+        let info = picacg_outbox_apply_info(info).await.unwrap();
+        assert!(info.is_liked);
+        let retried = picacg_outbox_retry(failed.id).await.unwrap();
+
+        let actions = picacg_outbox_comic_actions(comic_id.clone()).await.unwrap();
+        assert_eq!(
+            actions.iter().map(|a| a.kind).collect::<Vec<_>>(),
+            vec![OutboxKind::Like, OutboxKind::Comment, OutboxKind::Favourite]
+        );
+        assert!(complete_action(&retried).await.unwrap());
+        let actions = picacg_outbox_comic_actions(comic_id).await.unwrap();
+        assert_eq!(actions.len(), 2);
+        for action in actions {
+            picacg_outbox_discard(action.id).await.unwrap();
+        }
+    }
+}
@@ -0,0 +1,377 @@
+use crate::api::{
+    error::custom_error::{CustomError, CustomErrorType},
+    storage::{
+        secret_data::{get_secret, set_secret, Credentials, PUNCH_IN_ACCOUNTS_SECRET},
+        setting_data::{get_setting, set_setting},
+    },
+};
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use flutter_rust_bridge::frb;
+use serde::{Deserialize, Serialize};
+
+const PUNCH_IN_SETTINGS_KEY: &str = "punch_in.settings";
+const PUNCH_IN_RECORDS_KEY: &str = "punch_in.records";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct PunchInSettings {
+    /// 是否自动签到，默认关闭。
+    pub enabled: bool,
+    /// 每天自动签到的时间（本地时间），0 到 23。
+    pub hour: u32,
+    /// 0 到 59。
+    pub minute: u32,
+}
+
+impl Default for PunchInSettings {
+    fn default() -> Self {
+        PunchInSettings {
+            enabled: false,
+            hour: 8,
+            minute: 0,
+        }
+    }
+}
+
+impl PunchInSettings {
+    /// 检查签到时间是否合法。
+    #[frb(ignore)]
+    pub fn validate(&self) -> Result<(), CustomError> {
+        if self.hour > 23 || self.minute > 59 {
+            return Err(CustomError {
+                error_code: CustomErrorType::ParameterError,
+                error_message: format!("Invalid punch-in time {:02}:{:02}", self.hour, self.minute),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PunchInStatus {
+    /// 本次签到成功
+    Punched,
+    /// 检查时已经签到过
+    AlreadyPunched,
+    /// 检查或签到失败
+    Failed,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct PunchInRecord {
+    /// 账号的用户 ID，自动签到账号登录失败时为空。
+    pub user_id: Option<String>,
+    /// 自动签到账号的登录名，当前登录的账号为空。
+    #[serde(default)]
+    pub account: Option<String>,
+    pub user_name: Option<String>,
+    /// 最近一次检查的结果。
+    pub status: PunchInStatus,
+    /// 失败时的错误信息。
+    pub error_message: Option<String>,
+    pub checked_at: DateTime<Utc>,
+    /// 最近一次确认已签到的时间，失败时保留之前的值。
+    pub last_success_at: Option<DateTime<Utc>>,
+}
+
+impl PunchInRecord {
+    /// 是否为用户 ID 为 `user_id` 或登录名为 `account` 的账号的记录。
+    fn is_account(&self, user_id: &Option<String>, account: &Option<String>) -> bool {
+        (user_id.is_some() && self.user_id == *user_id)
+            || (account.is_some() && self.account == *account)
+    }
+
+    /// 本地日期 `today` 是否已经签到。
+    #[frb(ignore)]
+    pub fn is_punched_on(&self, today: NaiveDate) -> bool {
+        self.last_success_at
+            .is_some_and(|at| at.with_timezone(&Local).date_naive() == today)
+    }
+}
+
+/// 获取自动签到设置。
+#[frb]
+pub async fn picacg_punch_in_settings() -> Result<PunchInSettings, CustomError> {
+    Ok(get_setting(PUNCH_IN_SETTINGS_KEY)
+        .await?
+        .unwrap_or_default())
+}
+
+/// 保存自动签到设置。
+///
+/// # 错误
+/// - 当时间不合法时，返回参数错误。
+#[frb(ignore)]
+pub async fn set_punch_in_settings(settings: &PunchInSettings) -> Result<(), CustomError> {
+    settings.validate()?;
+    set_setting(PUNCH_IN_SETTINGS_KEY, settings).await
+}
+
+/// 获取每个账号最近一次签到的结果。
+#[frb]
+pub async fn picacg_punch_in_records() -> Result<Vec<PunchInRecord>, CustomError> {
+    Ok(get_setting(PUNCH_IN_RECORDS_KEY).await?.unwrap_or_default())
+}
+
+/// 获取一个账号最近一次签到的结果，账号由用户 ID 或自动签到账号的登录名确定。
+#[frb(ignore)]
+pub async fn punch_in_record(
+    user_id: Option<String>,
+    account: Option<String>,
+) -> Result<Option<PunchInRecord>, CustomError> {
+    Ok(picacg_punch_in_records()
+        .await?
+        .into_iter()
+        .find(|record| record.is_account(&user_id, &account)))
+}
+
+/// 保存一个账号的签到结果，返回保存后的记录。
+///
+/// 失败时保留该账号之前的签到成功时间，以及之前记录的用户 ID、登录名和用户名。
+/// 用户 ID 和登录名都为空时无法确定账号，只返回记录而不保存。
+#[frb(ignore)]
+pub async fn record_punch_in(
+    user_id: Option<String>,
+    account: Option<String>,
+    user_name: Option<String>,
+    status: PunchInStatus,
+    error_message: Option<String>,
+) -> Result<PunchInRecord, CustomError> {
+    if user_id.is_none() && account.is_none() {
+        return Ok(PunchInRecord {
+            user_id,
+            account,
+            user_name,
+            status,
+            error_message,
+            checked_at: Utc::now(),
+            last_success_at: None,
+        });
+    }
+
+    let mut records = picacg_punch_in_records().await?;
+    let previous = records
+        .iter()
+        .position(|record| record.is_account(&user_id, &account))
+        .map(|index| records.remove(index));
+
+    let now = Utc::now();
+    let record = PunchInRecord {
+        user_name: user_name.or_else(|| previous.as_ref().and_then(|r| r.user_name.clone())),
+        user_id: user_id.or_else(|| previous.as_ref().and_then(|r| r.user_id.clone())),
+        account: account.or_else(|| previous.as_ref().and_then(|r| r.account.clone())),
+        status,
+        error_message,
+        checked_at: now,
+        last_success_at: match status {
+            PunchInStatus::Failed => previous.and_then(|r| r.last_success_at),
+            _ => Some(now),
+        },
+    };
+    records.push(record.clone());
+
+    set_setting(PUNCH_IN_RECORDS_KEY, &records).await?;
+    Ok(record)
+}
+
+/// 读取自动签到的其他账号，密钥库未解锁时返回错误。
+#[frb(ignore)]
+pub fn punch_in_accounts() -> Result<Vec<Credentials>, CustomError> {
+    get_secret(PUNCH_IN_ACCOUNTS_SECRET)?
+        .map(|accounts| {
+            serde_json::from_str(&accounts).map_err(|e| CustomError {
+                error_code: CustomErrorType::ParseJsonError,
+                error_message: format!("Failed to parse punch-in accounts: {}", e),
+            })
+        })
+        .transpose()
+        .map(Option::unwrap_or_default)
+}
+
+fn save_punch_in_accounts(accounts: &[Credentials]) -> Result<(), CustomError> {
+    if accounts.is_empty() {
+        return set_secret(PUNCH_IN_ACCOUNTS_SECRET, None);
+    }
+
+    let accounts = serde_json::to_string(accounts).map_err(|e| CustomError {
+        error_code: CustomErrorType::SerializeJsonError,
+        error_message: format!("Failed to serialize punch-in accounts: {}", e),
+    })?;
+    set_secret(PUNCH_IN_ACCOUNTS_SECRET, Some(accounts))
+}
+
+/// 获取除当前登录账号外自动签到的账号登录名。
+#[frb(sync)]
+pub fn picacg_punch_in_accounts() -> Result<Vec<String>, CustomError> {
+    Ok(punch_in_accounts()?
+        .into_iter()
+        .map(|credentials| credentials.username)
+        .collect())
+}
+
+/// 添加一个自动签到的账号，账号和密码加密保存在密钥库中，已存在的账号会更新密码。
+///
+/// 签到时使用该账号单独登录，不影响当前登录的账号。
+///
+/// # 错误
+/// - 当账号或密码为空时，返回参数错误。
+#[frb(sync)]
+pub fn picacg_punch_in_add_account(credentials: Credentials) -> Result<(), CustomError> {
+    if credentials.username.trim().is_empty() || credentials.password.is_empty() {
+        return Err(CustomError {
+            error_code: CustomErrorType::ParameterError,
+            error_message: "Username or password cannot be empty".to_string(),
+        });
+    }
+
+    let credentials = Credentials {
+        username: credentials.username.trim().to_string(),
+        ..credentials
+    };
+    let mut accounts = punch_in_accounts()?;
+    accounts.retain(|account| account.username != credentials.username);
+    accounts.push(credentials);
+    save_punch_in_accounts(&accounts)
+}
+
+/// 移除一个自动签到的账号。
+#[frb(sync)]
+pub fn picacg_punch_in_remove_account(username: String) -> Result<(), CustomError> {
+    let mut accounts = punch_in_accounts()?;
+    accounts.retain(|account| account.username != username);
+    save_punch_in_accounts(&accounts)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::{
+        error::custom_error::CustomErrorType,
+        storage::{
+            database::open_test_database,
+            punch_in_data::{
+                picacg_punch_in_accounts, picacg_punch_in_add_account, picacg_punch_in_records,
+                picacg_punch_in_remove_account, punch_in_accounts, punch_in_record,
+                record_punch_in, set_punch_in_settings, PunchInSettings, PunchInStatus,
+            },
+            secret_data::{unlock_test_secrets, Credentials},
+        },
+    };
+    use chrono::Local;
+
+    #[tokio::test]
+    async fn test_punch_in_record() {
+        open_test_database().await;
+        let user_id = Some("punch_in".to_string());
+
+        let punched = record_punch_in(
+            user_id.clone(),
+            None,
+            Some("name".to_string()),
+            PunchInStatus::Punched,
+            None,
+        )
+        .await
+        .unwrap();
+        assert!(punched.is_punched_on(Local::now().date_naive()));
+
+        let failed = record_punch_in(
+            user_id.clone(),
+            None,
+            None,
+            PunchInStatus::Failed,
+            Some("offline".to_string()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(failed.last_success_at, punched.last_success_at);
+        assert_eq!(failed.user_name.as_deref(), Some("name"));
+
+        let record = punch_in_record(user_id.clone(), None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(record.status, PunchInStatus::Failed);
+        assert_eq!(record.error_message.as_deref(), Some("offline"));
+
+        // 自动签到账号登录失败时按登录名记录，之后获取到用户 ID 时合并为同一条记录
+        let account = Some("punch_in@example.com".to_string());
+        record_punch_in(
+            None,
+            account.clone(),
+            None,
+            PunchInStatus::Failed,
+            Some("offline".to_string()),
+        )
+        .await
+        .unwrap();
+        let record = record_punch_in(
+            Some("punch_in_account".to_string()),
+            account.clone(),
+            None,
+            PunchInStatus::Punched,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(record.account, account);
+        let found = punch_in_record(None, account).await.unwrap().unwrap();
+        assert_eq!(found.user_id.as_deref(), Some("punch_in_account"));
+        assert_eq!(found.status, PunchInStatus::Punched);
+
+        // 无法确定账号的结果不保存
+        let count = picacg_punch_in_records().await.unwrap().len();
+        record_punch_in(
+            None,
+            None,
+            None,
+            PunchInStatus::Failed,
+            Some("unauthorized".to_string()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(picacg_punch_in_records().await.unwrap().len(), count);
+
+        let invalid = PunchInSettings {
+            enabled: true,
+            hour: 24,
+            minute: 0,
+        };
+        assert!(matches!(
+            set_punch_in_settings(&invalid)
+                .await
+                .map_err(|e| e.error_code),
+            Err(CustomErrorType::ParameterError)
+        ));
+    }
+
+    #[test]
+    fn test_punch_in_accounts() {
+        unlock_test_secrets();
+        let credentials = |username: &str, password: &str| Credentials {
+            username: username.to_string(),
+            password: password.to_string(),
+        };
+
+        picacg_punch_in_add_account(credentials(" a@example.com ", "old")).unwrap();
+        picacg_punch_in_add_account(credentials("a@example.com", "new")).unwrap();
+        assert!(picacg_punch_in_add_account(credentials("b@example.com", "")).is_err());
+        assert!(picacg_punch_in_accounts()
+            .unwrap()
+            .contains(&"a@example.com".to_string()));
+        let account = punch_in_accounts()
+            .unwrap()
+            .into_iter()
+            .find(|account| account.username == "a@example.com")
+            .unwrap();
+        assert_eq!(account.password, "new");
+
+        picacg_punch_in_remove_account("a@example.com".to_string()).unwrap();
+        assert!(!picacg_punch_in_accounts()
+            .unwrap()
+            .contains(&"a@example.com".to_string()));
+    }
+}
@@ -0,0 +1,78 @@
+use crate::api::error::custom_error::{CustomError, CustomErrorType};
+use flutter_rust_bridge::frb;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+static STORAGE_ROOT: OnceLock<PathBuf> = OnceLock::new();
+
+/// 设置本地数据的存储目录。
+///
+/// 应用启动时调用一次，之后配置、数据库等本地数据都保存在该目录下。
+/// 重复设置相同的目录不会报错，设置不同的目录会返回错误。
+///
+/// # 参数
+/// - `path`: 存储目录，不存在时会自动创建。
+#[frb(sync)]
+pub fn picacg_init_storage(path: String) -> Result<(), CustomError> {
+    let path = PathBuf::from(path);
+    fs::create_dir_all(&path).map_err(|e| CustomError {
+        error_code: CustomErrorType::FileWriteError,
+        error_message: format!("Failed to create storage directory: {}", e),
+    })?;
+
+    let root = STORAGE_ROOT.get_or_init(|| path.clone());
+    if root != &path {
+        return Err(CustomError {
+            error_code: CustomErrorType::ParameterError,
+            error_message: format!("Storage root is already set to {}", root.display()),
+        });
+    }
+
+    Ok(())
+}
+
+/// 获取存储目录，未调用 `picacg_init_storage` 时返回错误。
+pub fn storage_root() -> Result<&'static Path, CustomError> {
+    STORAGE_ROOT
+        .get()
+        .map(PathBuf::as_path)
+        .ok_or_else(|| CustomError {
+            error_code: CustomErrorType::ParameterError,
+            error_message: "Storage root is not initialized".to_string(),
+        })
+}
+
+/// 测试使用的存储目录，每个测试进程使用独立的临时目录。
+#[cfg(test)]
+pub fn init_test_storage() -> &'static Path {
+    static INIT: std::sync::Once = std::sync::Once::new();
+
+    let dir = std::env::temp_dir().join(format!("picacg_test_storage_{}", std::process::id()));
+    INIT.call_once(|| {
+        let _ = fs::remove_dir_all(&dir);
+    });
+    picacg_init_storage(dir.to_string_lossy().to_string()).unwrap();
+
+    storage_root().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::storage::root::{init_test_storage, picacg_init_storage, storage_root};
+
+    #[test]
+    fn test_init_storage() {
+        let root = init_test_storage();
+        assert!(root.exists());
+        assert_eq!(storage_root().unwrap(), root);
+
+        let result = picacg_init_storage(root.to_string_lossy().to_string());
+        assert!(result.is_ok());
+
+        let result = picacg_init_storage(root.join("other").to_string_lossy().to_string());
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,585 @@
+use crate::api::{
+    error::custom_error::CustomError, storage::database::with_connection,
+    utils::chinese::normalize_chinese,
+};
+use chrono::{DateTime, Utc};
+use flutter_rust_bridge::frb;
+use rusqlite::{params, types::Type, Connection, Row};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 每个账号最多保存的未置顶搜索历史数量。
+const SEARCH_HISTORY_LIMIT: i32 = 100;
+
+const SEARCH_HISTORY_COLUMNS: &str = "keyword, pinned, search_count, created_at, updated_at";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct SearchHistoryEntry {
+    /// 最近一次搜索时输入的关键词。
+    pub keyword: String,
+    pub pinned: bool,
+    pub search_count: i32,
+    /// 第一次搜索的时间。
+    pub created_at: DateTime<Utc>,
+    /// 最近一次搜索的时间。
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SearchTermKind {
+    /// 在漫画列表中见过的标签
+    Tag,
+    /// 分类标题
+    Category,
+    /// 服务器的热门关键词
+    Keyword,
+}
+
+impl SearchTermKind {
+    #[frb(ignore)]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SearchTermKind::Tag => "tag",
+            SearchTermKind::Category => "category",
+            SearchTermKind::Keyword => "keyword",
+        }
+    }
+
+    fn from_column(row: &Row<'_>, index: usize) -> rusqlite::Result<Self> {
+        let kind: String = row.get(index)?;
+        match kind.as_str() {
+            "tag" => Ok(SearchTermKind::Tag),
+            "category" => Ok(SearchTermKind::Category),
+            "keyword" => Ok(SearchTermKind::Keyword),
+            _ => Err(rusqlite::Error::FromSqlConversionFailure(
+                index,
+                Type::Text,
+                format!("Unknown search term kind: {}", kind).into(),
+            )),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchSuggestionSource {
+    History,
+    Keyword,
+    Tag,
+    Category,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct SearchSuggestion {
+    pub text: String,
+    pub source: SearchSuggestionSource,
+    /// 搜索历史是否置顶。
+    pub pinned: bool,
+    /// 匹配得分，越大越靠前。
+    pub score: i32,
+}
+
+/// 去掉首尾空白并合并连续空白。
+#[frb(ignore)]
+pub fn normalize_keyword(keyword: &str) -> String {
+    keyword.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// 用于去重和匹配的关键词，忽略大小写、简繁写法和多余空白。
+fn keyword_key(keyword: &str) -> String {
+    normalize_chinese(&normalize_keyword(keyword))
+}
+
+fn search_history_entry_from_row(row: &Row<'_>) -> rusqlite::Result<SearchHistoryEntry> {
+    Ok(SearchHistoryEntry {
+        keyword: row.get(0)?,
+        pinned: row.get(1)?,
+        search_count: row.get(2)?,
+        created_at: row.get(3)?,
+        updated_at: row.get(4)?,
+    })
+}
+
+fn query_search_history(
+    connection: &Connection,
+    account: &str,
+) -> Result<Vec<SearchHistoryEntry>, CustomError> {
+    Ok(connection
+        .prepare(&format!(
+            "SELECT {} FROM search_history WHERE account = ?1
+             ORDER BY pinned DESC, updated_at DESC",
+            SEARCH_HISTORY_COLUMNS
+        ))?
+        .query_map(params![account], search_history_entry_from_row)?
+        .collect::<Result<Vec<SearchHistoryEntry>, _>>()?)
+}
+
+/// 记录一次搜索。相同的关键词只保留一条记录，超出数量上限时删除最早的未置顶记录。
+///
+/// # 返回
+/// - `Ok(None)`：关键词为空，没有记录。
+#[frb(ignore)]
+pub async fn record_search(
+    account: String,
+    keyword: String,
+) -> Result<Option<SearchHistoryEntry>, CustomError> {
+    let keyword = normalize_keyword(&keyword);
+    if keyword.is_empty() {
+        return Ok(None);
+    }
+
+    with_connection(move |connection| {
+        let normalized = keyword_key(&keyword);
+        let transaction = connection.transaction()?;
+        transaction.execute(
+            "INSERT INTO search_history (account, normalized, keyword, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?4)
+             ON CONFLICT (account, normalized) DO UPDATE SET
+                keyword = excluded.keyword,
+                search_count = search_count + 1,
+                updated_at = excluded.updated_at",
+            params![account, normalized, keyword, Utc::now()],
+        )?;
+        transaction.execute(
+            "DELETE FROM search_history WHERE account = ?1 AND pinned = 0 AND normalized NOT IN (
+                SELECT normalized FROM search_history WHERE account = ?1 AND pinned = 0
+                ORDER BY updated_at DESC LIMIT ?2
+             )",
+            params![account, SEARCH_HISTORY_LIMIT],
+        )?;
+
+        let entry = transaction.query_row(
+            &format!(
+                "SELECT {} FROM search_history WHERE account = ?1 AND normalized = ?2",
+                SEARCH_HISTORY_COLUMNS
+            ),
+            params![account, normalized],
+            search_history_entry_from_row,
+        )?;
+        transaction.commit()?;
+        Ok(Some(entry))
+    })
+    .await
+}
+
+/// 获取账号的搜索历史，置顶的在前，其余按最近搜索时间排列。
+#[frb(ignore)]
+pub async fn search_history(account: String) -> Result<Vec<SearchHistoryEntry>, CustomError> {
+    with_connection(move |connection| query_search_history(connection, &account)).await
+}
+
+/// 置顶或取消置顶一条搜索历史。
+///
+/// # 返回
+/// - `Ok(false)`：没有该搜索历史。
+#[frb(ignore)]
+pub async fn pin_search(
+    account: String,
+    keyword: String,
+    pinned: bool,
+) -> Result<bool, CustomError> {
+    with_connection(move |connection| {
+        Ok(connection.execute(
+            "UPDATE search_history SET pinned = ?3 WHERE account = ?1 AND normalized = ?2",
+            params![account, keyword_key(&keyword), pinned],
+        )? > 0)
+    })
+    .await
+}
+
+/// 删除一条搜索历史。
+#[frb(ignore)]
+pub async fn delete_search(account: String, keyword: String) -> Result<(), CustomError> {
+    with_connection(move |connection| {
+        connection.execute(
+            "DELETE FROM search_history WHERE account = ?1 AND normalized = ?2",
+            params![account, keyword_key(&keyword)],
+        )?;
+        Ok(())
+    })
+    .await
+}
+
+/// 清空账号的搜索历史，`keep_pinned` 为 `true` 时保留置顶的记录。
+#[frb(ignore)]
+pub async fn clear_search_history(account: String, keep_pinned: bool) -> Result<(), CustomError> {
+    with_connection(move |connection| {
+        connection.execute(
+            "DELETE FROM search_history WHERE account = ?1 AND (?2 = 0 OR pinned = 0)",
+            params![account, keep_pinned],
+        )?;
+        Ok(())
+    })
+    .await
+}
+
+/// 记录在漫画列表中见过的词条，重复出现时增加次数。
+#[frb(ignore)]
+pub async fn record_search_terms(
+    kind: SearchTermKind,
+    terms: Vec<String>,
+) -> Result<(), CustomError> {
+    let terms = terms
+        .iter()
+        .map(|term| normalize_keyword(term))
+        .filter(|term| !term.is_empty())
+        .collect::<Vec<_>>();
+    if terms.is_empty() {
+        return Ok(());
+    }
+
+    with_connection(move |connection| {
+        let now = Utc::now();
+        let transaction = connection.transaction()?;
+        {
+            let mut statement = transaction.prepare(
+                "INSERT INTO search_term (kind, term, updated_at) VALUES (?1, ?2, ?3)
+                 ON CONFLICT (kind, term) DO UPDATE SET
+                    seen_count = seen_count + 1,
+                    updated_at = excluded.updated_at",
+            )?;
+            for term in &terms {
+                statement.execute(params![kind.as_str(), term, now])?;
+            }
+        }
+        transaction.commit()?;
+        Ok(())
+    })
+    .await
+}
+
+/// 用服务器返回的完整列表替换一类词条，`position` 保留服务器的顺序。
+#[frb(ignore)]
+pub async fn replace_search_terms(
+    kind: SearchTermKind,
+    terms: Vec<String>,
+) -> Result<(), CustomError> {
+    with_connection(move |connection| {
+        let now = Utc::now();
+        let transaction = connection.transaction()?;
+        transaction.execute(
+            "DELETE FROM search_term WHERE kind = ?1",
+            params![kind.as_str()],
+        )?;
+        {
+            let mut statement = transaction.prepare(
+                "INSERT OR IGNORE INTO search_term (kind, term, position, updated_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            for (position, term) in terms.iter().enumerate() {
+                let term = normalize_keyword(term);
+                if !term.is_empty() {
+                    statement.execute(params![kind.as_str(), term, position as i64, now])?;
+                }
+            }
+        }
+        transaction.commit()?;
+        Ok(())
+    })
+    .await
+}
+
+/// 计算 `candidate` 与输入的匹配得分，两者都已转为小写。
+///
+/// 完全相同 > 前缀 > 包含 > 按顺序包含所有字符，不匹配时返回 `None`。
+fn match_score(candidate: &str, input: &str) -> Option<i32> {
+    let length_penalty = (candidate.chars().count() as i32 - input.chars().count() as i32).min(50);
+
+    if candidate == input {
+        return Some(1000);
+    }
+    if candidate.starts_with(input) {
+        return Some(800 - length_penalty);
+    }
+    if let Some(index) = candidate.find(input) {
+        let offset = candidate[..index].chars().count() as i32;
+        return Some(600 - offset.min(50) - length_penalty);
+    }
+
+    // 模糊匹配：输入的字符按顺序出现在候选词中，间隔越少得分越高
+    let mut gaps = 0;
+    let mut candidate_chars = candidate.chars();
+    for c in input.chars() {
+        loop {
+            match candidate_chars.next() {
+                Some(next) if next == c => break,
+                Some(_) => gaps += 1,
+                None => return None,
+            }
+        }
+    }
+    Some(300 - gaps.min(100) - length_penalty)
+}
+
+/// 按来源调整得分，搜索历史优先，见过次数越多的标签越靠前。
+fn source_bonus(source: SearchSuggestionSource, pinned: bool, seen_count: i32) -> i32 {
+    match source {
+        SearchSuggestionSource::History if pinned => 300,
+        SearchSuggestionSource::History => 150 + seen_count.min(50),
+        SearchSuggestionSource::Keyword => 80,
+        SearchSuggestionSource::Category => 60,
+        SearchSuggestionSource::Tag => 20 + seen_count.min(40),
+    }
+}
+
+struct SearchCandidate {
+    text: String,
+    source: SearchSuggestionSource,
+    pinned: bool,
+    seen_count: i32,
+    /// 输入为空时的排序依据，越小越靠前。
+    rank: i32,
+}
+
+fn query_search_candidates(
+    connection: &Connection,
+    account: Option<&str>,
+) -> Result<Vec<SearchCandidate>, CustomError> {
+    let history = match account {
+        Some(account) => query_search_history(connection, account)?,
+        None => Vec::new(),
+    };
+    let mut candidates = history
+        .into_iter()
+        .enumerate()
+        .map(|(index, entry)| SearchCandidate {
+            text: entry.keyword,
+            source: SearchSuggestionSource::History,
+            pinned: entry.pinned,
+            seen_count: entry.search_count,
+            rank: index as i32,
+        })
+        .collect::<Vec<_>>();
+
+    let terms = connection
+        .prepare(
+            "SELECT kind, term, seen_count, position FROM search_term
+             ORDER BY position, seen_count DESC, term",
+        )?
+        .query_map([], |row| {
+            Ok((
+                SearchTermKind::from_column(row, 0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i32>(2)?,
+                row.get::<_, i32>(3)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let history_count = candidates.len() as i32;
+    candidates.extend(terms.into_iter().map(|(kind, term, seen_count, position)| {
+        SearchCandidate {
+            text: term,
+            source: match kind {
+                SearchTermKind::Tag => SearchSuggestionSource::Tag,
+                SearchTermKind::Category => SearchSuggestionSource::Category,
+                SearchTermKind::Keyword => SearchSuggestionSource::Keyword,
+            },
+            pinned: false,
+            seen_count,
+            rank: history_count + position,
+        }
+    }));
+
+    Ok(candidates)
+}
+
+/// 按输入生成搜索建议。
+///
+/// 候选词来自账号的搜索历史、热门关键词、见过的标签和分类标题，按前缀和模糊匹配的得分排序，
+/// 相同的词只保留得分最高的一条。输入为空时返回搜索历史和热门关键词。`account` 为空时不包含搜索历史。
+#[frb(ignore)]
+pub async fn search_suggestions(
+    account: Option<String>,
+    input: String,
+    limit: i32,
+) -> Result<Vec<SearchSuggestion>, CustomError> {
+    let candidates =
+        with_connection(move |connection| query_search_candidates(connection, account.as_deref()))
+            .await?;
+    let input = keyword_key(&input);
+
+    let mut best: HashMap<String, (SearchSuggestion, i32)> = HashMap::new();
+    for candidate in candidates {
+        let key = keyword_key(&candidate.text);
+        let score = if input.is_empty() {
+            match candidate.source {
+                SearchSuggestionSource::History | SearchSuggestionSource::Keyword => {
+                    Some(source_bonus(candidate.source, candidate.pinned, 0) - candidate.rank)
+                }
+                _ => None,
+            }
+        } else {
+            match_score(&key, &input).map(|score| {
+                score + source_bonus(candidate.source, candidate.pinned, candidate.seen_count)
+            })
+        };
+        let Some(score) = score else {
+            continue;
+        };
+
+        let suggestion = SearchSuggestion {
+            text: candidate.text,
+            source: candidate.source,
+            pinned: candidate.pinned,
+            score,
+        };
+        match best.get(&key) {
+            Some((existing, _)) if existing.score >= score => {}
+            _ => {
+                best.insert(key, (suggestion, candidate.rank));
+            }
+        }
+    }
+
+    let mut suggestions = best.into_values().collect::<Vec<_>>();
+    suggestions.sort_by(|(a, a_rank), (b, b_rank)| {
+        b.score
+            .cmp(&a.score)
+            .then(a_rank.cmp(b_rank))
+            .then_with(|| a.text.cmp(&b.text))
+    });
+
+    Ok(suggestions
+        .into_iter()
+        .take(limit.max(0) as usize)
+        .map(|(suggestion, _)| suggestion)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::storage::{
+        database::open_test_database,
+        search_data::{
+            clear_search_history, delete_search, match_score, pin_search, record_search,
+            record_search_terms, replace_search_terms, search_history, search_suggestions,
+            SearchSuggestionSource, SearchTermKind,
+        },
+    };
+
+    #[test]
+    fn test_match_score() {
+        assert!(match_score("全彩", "全彩") > match_score("全彩漫画", "全彩"));
+        assert!(match_score("全彩漫画", "全彩") > match_score("漫画全彩", "全彩"));
+        assert!(match_score("漫画全彩", "全彩") > match_score("全本彩色", "全彩"));
+        assert_eq!(match_score("彩色", "全彩"), None);
+    }
+
+    #[tokio::test]
+    async fn test_search_history() {
+        open_test_database().await;
+        let account = "search_history".to_string();
+
+        record_search(account.clone(), " Full  Color ".to_string())
+            .await
+            .unwrap();
+        let entry = record_search(account.clone(), "full color".to_string())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry.keyword, "full color");
+        assert_eq!(entry.search_count, 2);
+        record_search(account.clone(), "進擊".to_string())
+            .await
+            .unwrap();
+        let entry = record_search(account.clone(), "进击".to_string())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry.search_count, 2);
+        delete_search(account.clone(), "進擊".to_string())
+            .await
+            .unwrap();
+        assert!(record_search(account.clone(), "  ".to_string())
+            .await
+            .unwrap()
+            .is_none());
+
+        record_search(account.clone(), "pinned".to_string())
+            .await
+            .unwrap();
+        record_search(account.clone(), "latest".to_string())
+            .await
+            .unwrap();
+        assert!(pin_search(account.clone(), "PINNED".to_string(), true)
+            .await
+            .unwrap());
+        let keywords = search_history(account.clone())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.keyword)
+            .collect::<Vec<_>>();
+        assert_eq!(keywords, vec!["pinned", "latest", "full color"]);
+        assert!(search_history("other".to_string())
+            .await
+            .unwrap()
+            .is_empty());
+
+        delete_search(account.clone(), "latest".to_string())
+            .await
+            .unwrap();
+        clear_search_history(account.clone(), true).await.unwrap();
+        let history = search_history(account.clone()).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert!(history[0].pinned);
+
+        clear_search_history(account.clone(), false).await.unwrap();
+        assert!(search_history(account).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_search_suggestions() {
+        open_test_database().await;
+        let account = "search_suggestions".to_string();
+
+        record_search(account.clone(), "suggest history".to_string())
+            .await
+            .unwrap();
+        record_search_terms(
+            SearchTermKind::Tag,
+            vec!["suggest tag".to_string(), "suggest history".to_string()],
+        )
+        .await
+        .unwrap();
+        replace_search_terms(
+            SearchTermKind::Category,
+            vec!["suggest category".to_string()],
+        )
+        .await
+        .unwrap();
+
+        let suggestions = search_suggestions(Some(account.clone()), "SUGGEST".to_string(), 10)
+            .await
+            .unwrap();
+        assert_eq!(suggestions[0].text, "suggest history");
+        assert_eq!(suggestions[0].source, SearchSuggestionSource::History);
+        assert_eq!(
+            suggestions
+                .iter()
+                .filter(|s| s.text == "suggest history")
+                .count(),
+            1
+        );
+        assert!(suggestions.iter().any(|s| s.text == "suggest tag"));
+        assert!(suggestions.iter().any(|s| s.text == "suggest category"));
+
+        let fuzzy = search_suggestions(Some(account.clone()), "sgcat".to_string(), 10)
+            .await
+            .unwrap();
+        assert_eq!(fuzzy[0].text, "suggest category");
+
+        let empty = search_suggestions(Some(account.clone()), String::new(), 10)
+            .await
+            .unwrap();
+        assert!(empty
+            .iter()
+            .all(|s| s.source != SearchSuggestionSource::Tag));
+
+        clear_search_history(account, false).await.unwrap();
+    }
+}
@@ -0,0 +1,390 @@
+use crate::api::{
+    error::custom_error::{CustomError, CustomErrorType},
+    storage::root::storage_root,
+    utils::{
+        crypto::{
+            decrypt, derive_key, encrypt, random_bytes, SECRET_KEY_LENGTH, SECRET_SALT_LENGTH,
+        },
+        file::write_file_atomic,
+    },
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use flutter_rust_bridge::frb;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs, path::Path, sync::RwLock};
+
+static SECRET_FILE_PATH: &str = "secrets.json";
+static SECRET_TEMP_FILE_PATH: &str = "secrets.json.tmp";
+
+/// 加密数据的附加认证数据，防止密文被挪作他用。
+const SECRET_AAD: &[u8] = b"picacg-secrets-v1";
+
+/// 登录令牌。
+pub const TOKEN_SECRET: &str = "token";
+/// 登录账号和密码，用于自动重新登录。
+pub const CREDENTIALS_SECRET: &str = "credentials";
+/// 自动签到的其他账号和密码。
+pub const PUNCH_IN_ACCOUNTS_SECRET: &str = "punch_in_accounts";
+
+static SECRETS: RwLock<Option<SecretStore>> = RwLock::new(None);
+
+/// 密钥来源。
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum SecretKeySource {
+    /// 由宿主平台的安全存储（Keychain、Keystore 等）提供的密钥。
+    Platform,
+    /// 由用户口令通过 Argon2id 派生的密钥。
+    Passphrase,
+}
+
+/// 加密后的密钥文件内容。
+#[derive(Serialize, Deserialize)]
+struct SecretFile {
+    version: u32,
+    key_source: SecretKeySource,
+    /// 口令派生密钥使用的盐，仅 `Passphrase` 时存在。
+    #[serde(default)]
+    salt: Option<String>,
+    nonce: String,
+    ciphertext: String,
+}
+
+struct SecretStore {
+    key: [u8; SECRET_KEY_LENGTH],
+    key_source: SecretKeySource,
+    salt: Option<Vec<u8>>,
+    values: HashMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Credentials {
+    pub username: String,
+    pub password: String,
+}
+
+fn secret_error(message: String) -> CustomError {
+    CustomError {
+        error_code: CustomErrorType::SecretError,
+        error_message: message,
+    }
+}
+
+fn decode_base64(value: &str) -> Result<Vec<u8>, CustomError> {
+    STANDARD
+        .decode(value)
+        .map_err(|e| secret_error(format!("Failed to decode secret file: {}", e)))
+}
+
+fn read_secret_file(dir: &Path) -> Result<Option<SecretFile>, CustomError> {
+    let file_path = dir.join(SECRET_FILE_PATH);
+    if !file_path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(&file_path).map_err(|e| CustomError {
+        error_code: CustomErrorType::FileReadError,
+        error_message: format!("Failed to read secret file: {}", e),
+    })?;
+
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| CustomError {
+            error_code: CustomErrorType::ParseJsonError,
+            error_message: format!("Failed to parse secret file: {}", e),
+        })
+}
+
+impl SecretStore {
+    /// 解密 `dir` 目录下的密钥文件，文件不存在时创建空的密钥库。
+    fn open(
+        dir: &Path,
+        key_source: SecretKeySource,
+        key: impl FnOnce(Option<&[u8]>) -> Result<[u8; SECRET_KEY_LENGTH], CustomError>,
+    ) -> Result<SecretStore, CustomError> {
+        let Some(file) = read_secret_file(dir)? else {
+            let salt = match key_source {
+                SecretKeySource::Platform => None,
+                SecretKeySource::Passphrase => Some(random_bytes::<SECRET_SALT_LENGTH>().to_vec()),
+            };
+            let store = SecretStore {
+                key: key(salt.as_deref())?,
+                key_source,
+                salt,
+                values: HashMap::new(),
+            };
+            store.save(dir)?;
+            return Ok(store);
+        };
+
+        if file.key_source != key_source {
+            return Err(secret_error(format!(
+                "Secrets are protected by {:?} key",
+                file.key_source
+            )));
+        }
+
+        let salt = file.salt.as_deref().map(decode_base64).transpose()?;
+        let key = key(salt.as_deref())?;
+        let plaintext = decrypt(
+            &key,
+            &decode_base64(&file.nonce)?,
+            &decode_base64(&file.ciphertext)?,
+            SECRET_AAD,
+        )
+        .map_err(|_| secret_error("Failed to decrypt secrets, wrong key".to_string()))?;
+        let values = serde_json::from_slice(&plaintext)
+            .map_err(|e| secret_error(format!("Failed to parse secrets: {}", e)))?;
+
+        Ok(SecretStore {
+            key,
+            key_source,
+            salt,
+            values,
+        })
+    }
+
+    /// 使用新的随机数重新加密全部内容并原子地写入 `dir` 目录。
+    fn save(&self, dir: &Path) -> Result<(), CustomError> {
+        let plaintext = serde_json::to_vec(&self.values).map_err(|e| CustomError {
+            error_code: CustomErrorType::SerializeJsonError,
+            error_message: format!("Failed to serialize secrets: {}", e),
+        })?;
+        let (nonce, ciphertext) = encrypt(&self.key, &plaintext, SECRET_AAD)
+            .map_err(|e| secret_error(format!("Failed to encrypt secrets: {}", e)))?;
+
+        let file = SecretFile {
+            version: 1,
+            key_source: self.key_source,
+            salt: self.salt.as_ref().map(|salt| STANDARD.encode(salt)),
+            nonce: STANDARD.encode(nonce),
+            ciphertext: STANDARD.encode(ciphertext),
+        };
+        let content = serde_json::to_vec(&file).map_err(|e| CustomError {
+            error_code: CustomErrorType::SerializeJsonError,
+            error_message: format!("Failed to serialize secret file: {}", e),
+        })?;
+
+        write_file_atomic(
+            &dir.join(SECRET_FILE_PATH),
+            &dir.join(SECRET_TEMP_FILE_PATH),
+            &content,
+        )
+    }
+}
+
+fn set_store(store: Option<SecretStore>) -> Result<(), CustomError> {
+    *SECRETS.write().map_err(|_| CustomError {
+        error_code: CustomErrorType::LockError,
+        error_message: "Failed to acquire write lock on SECRETS".to_string(),
+    })? = store;
+    Ok(())
+}
+
+/// 使用宿主平台提供的密钥解锁密钥库。
+///
+/// 密钥应当由平台的安全存储生成和保管，每次启动时传入相同的密钥。
+///
+/// # 参数
+/// - `key`: 32 字节的密钥。
+#[frb(sync)]
+pub fn picacg_secrets_unlock_with_key(key: Vec<u8>) -> Result<(), CustomError> {
+    let key: [u8; SECRET_KEY_LENGTH] = key.try_into().map_err(|_| CustomError {
+        error_code: CustomErrorType::ParameterError,
+        error_message: format!("Secret key must be {} bytes", SECRET_KEY_LENGTH),
+    })?;
+
+    let store = SecretStore::open(storage_root()?, SecretKeySource::Platform, |_| Ok(key))?;
+    set_store(Some(store))
+}
+
+/// 平台没有安全存储时，使用用户口令派生的密钥解锁密钥库。
+///
+/// # 参数
+/// - `passphrase`: 用户口令。
+#[frb]
+pub async fn picacg_secrets_unlock_with_passphrase(passphrase: String) -> Result<(), CustomError> {
+    let dir = storage_root()?;
+    let store = tokio::task::spawn_blocking(move || {
+        SecretStore::open(dir, SecretKeySource::Passphrase, |salt| {
+            derive_key(&passphrase, salt.unwrap_or_default())
+                .map_err(|e| secret_error(format!("Failed to derive key: {}", e)))
+        })
+    })
+    .await
+    .map_err(|e| secret_error(format!("Failed to unlock secrets: {}", e)))??;
+
+    set_store(Some(store))
+}
+
+/// 密钥库是否已解锁。
+#[frb(sync)]
+pub fn picacg_secrets_is_unlocked() -> bool {
+    SECRETS.read().is_ok_and(|store| store.is_some())
+}
+
+/// 清除内存中的密钥和已解密的内容。
+#[frb(sync)]
+pub fn picacg_secrets_lock() -> Result<(), CustomError> {
+    set_store(None)
+}
+
+/// 删除密钥库文件，用于密钥丢失后重新开始。
+#[frb(sync)]
+pub fn picacg_secrets_reset() -> Result<(), CustomError> {
+    set_store(None)?;
+
+    let file_path = storage_root()?.join(SECRET_FILE_PATH);
+    if file_path.exists() {
+        fs::remove_file(file_path).map_err(|e| CustomError {
+            error_code: CustomErrorType::FileWriteError,
+            error_message: format!("Failed to remove secret file: {}", e),
+        })?;
+    }
+
+    Ok(())
+}
+
+/// 读取一项密钥，密钥库未解锁时返回错误。
+pub fn get_secret(name: &str) -> Result<Option<String>, CustomError> {
+    Ok(SECRETS
+        .read()
+        .map_err(|_| CustomError {
+            error_code: CustomErrorType::LockError,
+            error_message: "Failed to acquire read lock on SECRETS".to_string(),
+        })?
+        .as_ref()
+        .ok_or_else(|| secret_error("Secrets are locked".to_string()))?
+        .values
+        .get(name)
+        .cloned())
+}
+
+/// 写入一项密钥，`value` 为 `None` 时删除，密钥库未解锁时返回错误。
+pub fn set_secret(name: &str, value: Option<String>) -> Result<(), CustomError> {
+    let dir = storage_root()?;
+    let mut secrets = SECRETS.write().map_err(|_| CustomError {
+        error_code: CustomErrorType::LockError,
+        error_message: "Failed to acquire write lock on SECRETS".to_string(),
+    })?;
+    let store = secrets
+        .as_mut()
+        .ok_or_else(|| secret_error("Secrets are locked".to_string()))?;
+
+    let previous = match value {
+        Some(value) => store.values.insert(name.to_string(), value),
+        None => store.values.remove(name),
+    };
+
+    if let Err(e) = store.save(dir) {
+        match previous {
+            Some(previous) => store.values.insert(name.to_string(), previous),
+            None => store.values.remove(name),
+        };
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+/// 保存登录账号和密码，用于登录失效时自动重新登录。
+#[frb(sync)]
+pub fn picacg_credentials_save(credentials: Credentials) -> Result<(), CustomError> {
+    let credentials = serde_json::to_string(&credentials).map_err(|e| CustomError {
+        error_code: CustomErrorType::SerializeJsonError,
+        error_message: format!("Failed to serialize credentials: {}", e),
+    })?;
+    set_secret(CREDENTIALS_SECRET, Some(credentials))
+}
+
+/// 获取保存的登录账号和密码。
+#[frb(sync)]
+pub fn picacg_credentials() -> Result<Option<Credentials>, CustomError> {
+    get_secret(CREDENTIALS_SECRET)?
+        .map(|credentials| {
+            serde_json::from_str(&credentials).map_err(|e| CustomError {
+                error_code: CustomErrorType::ParseJsonError,
+                error_message: format!("Failed to parse credentials: {}", e),
+            })
+        })
+        .transpose()
+}
+
+/// 删除保存的登录账号和密码。
+#[frb(sync)]
+pub fn picacg_credentials_clear() -> Result<(), CustomError> {
+    set_secret(CREDENTIALS_SECRET, None)
+}
+
+/// 测试使用固定密钥解锁密钥库。
+#[cfg(test)]
+pub fn unlock_test_secrets() {
+    crate::api::storage::root::init_test_storage();
+    if !picacg_secrets_is_unlocked() {
+        picacg_secrets_unlock_with_key(vec![7; SECRET_KEY_LENGTH]).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::{
+        storage::secret_data::{
+            picacg_credentials, picacg_credentials_clear, picacg_credentials_save,
+            unlock_test_secrets, Credentials, SecretKeySource, SecretStore, SECRET_FILE_PATH,
+        },
+        utils::crypto::derive_key,
+    };
+
+    #[test]
+    fn test_secret_store() {
+        let dir = std::env::temp_dir().join("picacg_test_secret_store");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let mut store =
+            SecretStore::open(&dir, SecretKeySource::Platform, |_| Ok([1; 32])).unwrap();
+        store
+            .values
+            .insert("token".to_string(), "plaintext_token".to_string());
+        store.save(&dir).unwrap();
+
+        let content = std::fs::read_to_string(dir.join(SECRET_FILE_PATH)).unwrap();
+        assert!(!content.contains("plaintext_token"));
+
+        let store = SecretStore::open(&dir, SecretKeySource::Platform, |_| Ok([1; 32])).unwrap();
+        assert_eq!(store.values["token"], "plaintext_token");
+
+        assert!(SecretStore::open(&dir, SecretKeySource::Platform, |_| Ok([2; 32])).is_err());
+        assert!(SecretStore::open(&dir, SecretKeySource::Passphrase, |_| Ok([1; 32])).is_err());
+
+        std::fs::remove_file(dir.join(SECRET_FILE_PATH)).unwrap();
+        let passphrase = |salt: Option<&[u8]>| Ok(derive_key("passphrase", salt.unwrap()).unwrap());
+        let mut store = SecretStore::open(&dir, SecretKeySource::Passphrase, passphrase).unwrap();
+        store
+            .values
+            .insert("token".to_string(), "token".to_string());
+        store.save(&dir).unwrap();
+        let store = SecretStore::open(&dir, SecretKeySource::Passphrase, passphrase).unwrap();
+        assert_eq!(store.values["token"], "token");
+
+        let result = std::fs::remove_dir_all(&dir);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_credentials() {
+        unlock_test_secrets();
+
+        picacg_credentials_save(Credentials {
+            username: "username".to_string(),
+            password: "password".to_string(),
+        })
+        .unwrap();
+        let credentials = picacg_credentials().unwrap().unwrap();
+        assert_eq!(credentials.username, "username");
+        assert_eq!(credentials.password, "password");
+
+        picacg_credentials_clear().unwrap();
+        assert!(picacg_credentials().unwrap().is_none());
+    }
+}
@@ -0,0 +1,203 @@
+use crate::api::{
+    error::custom_error::{CustomError, CustomErrorType},
+    storage::{blocklist_data::BLOCKLIST_SETTING_KEY, database::with_connection},
+};
+use chrono::Utc;
+use flutter_rust_bridge::frb;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+
+/// `config.json` 中的每个配置项保存为一条 `config.<name>` 设置。
+const CONFIG_KEY_PREFIX: &str = "config.";
+
+/// 读取设置的原始 JSON 文本。
+pub fn get_raw(connection: &Connection, key: &str) -> Result<Option<String>, CustomError> {
+    Ok(connection
+        .query_row(
+            "SELECT value FROM setting WHERE key = ?1",
+            params![key],
+            |row| row.get(0),
+        )
+        .optional()?)
+}
+
+/// 写入设置的原始 JSON 文本，已存在时覆盖。
+pub fn set_raw(connection: &Connection, key: &str, value: &str) -> Result<(), CustomError> {
+    connection.execute(
+        "INSERT INTO setting (key, value, updated_at) VALUES (?1, ?2, ?3)
+         ON CONFLICT (key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+        params![key, value, Utc::now()],
+    )?;
+    Ok(())
+}
+
+/// 读取保存在设置中的配置项，键为配置项名称，没有保存配置时为空。
+pub fn get_config_sections(connection: &Connection) -> Result<Map<String, Value>, CustomError> {
+    let mut statement = connection
+        .prepare("SELECT key, value FROM setting WHERE substr(key, 1, length(?1)) = ?1")?;
+    let rows = statement.query_map(params![CONFIG_KEY_PREFIX], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    })?;
+
+    let mut sections = Map::new();
+    for row in rows {
+        let (key, value) = row?;
+        let value = serde_json::from_str(&value).map_err(|e| CustomError {
+            error_code: CustomErrorType::ParseJsonError,
+            error_message: format!("Failed to parse config setting {}: {}", key, e),
+        })?;
+        sections.insert(key[CONFIG_KEY_PREFIX.len()..].to_string(), value);
+    }
+    Ok(sections)
+}
+
+/// 把配置项逐项写入设置，已存在的配置项会被覆盖。
+pub fn set_config_sections(
+    connection: &Connection,
+    sections: &Map<String, Value>,
+) -> Result<(), CustomError> {
+    for (name, value) in sections {
+        set_raw(
+            connection,
+            &format!("{}{}", CONFIG_KEY_PREFIX, name),
+            &value.to_string(),
+        )?;
+    }
+    Ok(())
+}
+
+/// 检查设置项能否通过通用接口修改。
+///
+/// 配置和屏蔽列表有自己的接口和内存中的副本，通过通用接口修改会导致两者不一致。
+fn check_writable_key(key: &str) -> Result<(), CustomError> {
+    if key.starts_with(CONFIG_KEY_PREFIX) || key == BLOCKLIST_SETTING_KEY {
+        return Err(CustomError {
+            error_code: CustomErrorType::ParameterError,
+            error_message: format!("Setting {} can only be changed through its own api", key),
+        });
+    }
+    Ok(())
+}
+
+/// 读取设置并反序列化为 `T`，不存在时返回 `None`。
+pub async fn get_setting<T>(key: &str) -> Result<Option<T>, CustomError>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    let key = key.to_string();
+    let value = with_connection(move |connection| get_raw(connection, &key)).await?;
+
+    value
+        .map(|value| {
+            serde_json::from_str(&value).map_err(|e| CustomError {
+                error_code: CustomErrorType::ParseJsonError,
+                error_message: format!("Failed to parse setting: {}", e),
+            })
+        })
+        .transpose()
+}
+
+/// 序列化 `value` 并写入设置。
+pub async fn set_setting<T>(key: &str, value: &T) -> Result<(), CustomError>
+where
+    T: Serialize,
+{
+    let key = key.to_string();
+    let value = serde_json::to_string(value).map_err(|e| CustomError {
+        error_code: CustomErrorType::SerializeJsonError,
+        error_message: format!("Failed to serialize setting: {}", e),
+    })?;
+
+    with_connection(move |connection| set_raw(connection, &key, &value)).await
+}
+
+/// 读取设置。
+///
+/// # 参数
+/// - `key`: 设置项名称。
+///
+/// # 返回
+/// - `Ok(Some(String))`：设置项的 JSON 文本。
+/// - `Ok(None)`：设置项不存在。
+#[frb]
+pub async fn picacg_setting_get(key: String) -> Result<Option<String>, CustomError> {
+    with_connection(move |connection| get_raw(connection, &key)).await
+}
+
+/// 写入设置。
+///
+/// # 参数
+/// - `key`: 设置项名称。
+/// - `value`: 设置项的 JSON 文本。
+///
+/// # 错误
+/// - 当 `value` 不是合法的 JSON，或设置项只能通过专门的接口修改时，返回参数错误。
+#[frb]
+pub async fn picacg_setting_set(key: String, value: String) -> Result<(), CustomError> {
+    check_writable_key(&key)?;
+    serde_json::from_str::<serde_json::Value>(&value).map_err(|e| CustomError {
+        error_code: CustomErrorType::ParameterError,
+        error_message: format!("Setting value is not valid json: {}", e),
+    })?;
+
+    with_connection(move |connection| set_raw(connection, &key, &value)).await
+}
+
+/// 删除设置，只能通过专门的接口修改的设置项返回参数错误。
+#[frb]
+pub async fn picacg_setting_delete(key: String) -> Result<(), CustomError> {
+    check_writable_key(&key)?;
+    with_connection(move |connection| {
+        connection.execute("DELETE FROM setting WHERE key = ?1", params![key])?;
+        Ok(())
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::storage::{
+        database::open_test_database,
+        setting_data::{
+            get_setting, picacg_setting_delete, picacg_setting_get, picacg_setting_set, set_setting,
+        },
+    };
+
+    #[tokio::test]
+    async fn test_setting() {
+        open_test_database().await;
+
+        set_setting("test.setting", &vec![1, 2, 3]).await.unwrap();
+        let value: Option<Vec<i32>> = get_setting("test.setting").await.unwrap();
+        assert_eq!(value, Some(vec![1, 2, 3]));
+
+        let result = picacg_setting_set("test.setting".to_string(), "{".to_string()).await;
+        assert!(result.is_err());
+        let result = picacg_setting_set("config.net_data".to_string(), "{}".to_string()).await;
+        assert!(result.is_err());
+        assert!(picacg_setting_delete("config.net_data".to_string())
+            .await
+            .is_err());
+        let result = picacg_setting_set("blocklist".to_string(), "{}".to_string()).await;
+        assert!(result.is_err());
+
+        picacg_setting_set("test.setting".to_string(), "true".to_string())
+            .await
+            .unwrap();
+        assert_eq!(
+            picacg_setting_get("test.setting".to_string())
+                .await
+                .unwrap(),
+            Some("true".to_string())
+        );
+
+        picacg_setting_delete("test.setting".to_string())
+            .await
+            .unwrap();
+        assert!(picacg_setting_get("test.setting".to_string())
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
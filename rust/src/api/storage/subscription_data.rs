@@ -0,0 +1,364 @@
+use crate::api::{
+    error::custom_error::CustomError,
+    storage::database::{json_column, to_json, with_connection},
+    types::{
+        comic_ep_entity::ComicEpEntity, comic_info_entity::ComicInfoEntity,
+        image_entity::ImageEntity,
+    },
+};
+use chrono::{DateTime, Utc};
+use flutter_rust_bridge::frb;
+use rusqlite::{params, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+
+const SUBSCRIPTION_COLUMNS: &str = "comic_id, title, thumb, comic_updated_at, last_ep_order,
+    last_ep_updated_at, created_at, last_checked_at,
+    (SELECT COUNT(*) FROM subscription_episode
+     WHERE subscription_episode.comic_id = subscription.comic_id AND seen = 0)";
+
+const SUBSCRIPTION_EPISODE_COLUMNS: &str = "subscription_episode.comic_id, subscription.title,
+    ep_order, ep_title, ep_updated_at, detected_at, seen";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct Subscription {
+    pub comic_id: String,
+    pub title: String,
+    pub thumb: ImageEntity,
+    /// 最后一次看到的漫画更新时间。
+    pub comic_updated_at: DateTime<Utc>,
+    /// 已知的最新章节序号。
+    pub last_ep_order: i32,
+    /// 已知章节中最晚的更新时间，没有章节时为空。
+    pub last_ep_updated_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub last_checked_at: Option<DateTime<Utc>>,
+    /// 未查看的新章节数量。
+    pub unseen_count: i32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct SubscriptionEpisode {
+    pub comic_id: String,
+    pub comic_title: String,
+    pub ep_order: i32,
+    pub ep_title: String,
+    /// 章节在服务器上的更新时间。
+    pub ep_updated_at: DateTime<Utc>,
+    /// 检查到新章节的时间。
+    pub detected_at: DateTime<Utc>,
+    pub seen: bool,
+}
+
+fn subscription_from_row(row: &Row<'_>) -> rusqlite::Result<Subscription> {
+    Ok(Subscription {
+        comic_id: row.get(0)?,
+        title: row.get(1)?,
+        thumb: json_column(row, 2)?,
+        comic_updated_at: row.get(3)?,
+        last_ep_order: row.get(4)?,
+        last_ep_updated_at: row.get(5)?,
+        created_at: row.get(6)?,
+        last_checked_at: row.get(7)?,
+        unseen_count: row.get(8)?,
+    })
+}
+
+fn subscription_episode_from_row(row: &Row<'_>) -> rusqlite::Result<SubscriptionEpisode> {
+    Ok(SubscriptionEpisode {
+        comic_id: row.get(0)?,
+        comic_title: row.get(1)?,
+        ep_order: row.get(2)?,
+        ep_title: row.get(3)?,
+        ep_updated_at: row.get(4)?,
+        detected_at: row.get(5)?,
+        seen: row.get(6)?,
+    })
+}
+
+/// 章节中最新的序号和最晚的更新时间。
+fn latest_episode(eps: &[ComicEpEntity]) -> (Option<i32>, Option<DateTime<Utc>>) {
+    (
+        eps.iter().map(|ep| ep.order).max(),
+        eps.iter().map(|ep| ep.updated_at).max(),
+    )
+}
+
+/// 保存订阅，以当前的全部章节作为之后检查的起点。已订阅时会重置检查起点。
+#[frb(ignore)]
+pub async fn save_subscription(
+    info: &ComicInfoEntity,
+    eps: &[ComicEpEntity],
+) -> Result<Subscription, CustomError> {
+    let comic_id = info.id.clone();
+    let title = info.title.clone();
+    let thumb = to_json(&info.thumb)?;
+    let comic_updated_at = info.updated_at;
+    let (last_ep_order, last_ep_updated_at) = latest_episode(eps);
+
+    with_connection(move |connection| {
+        let now = Utc::now();
+        connection.execute(
+            "INSERT INTO subscription (comic_id, title, thumb, comic_updated_at, last_ep_order,
+                last_ep_updated_at, created_at, last_checked_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)
+             ON CONFLICT (comic_id) DO UPDATE SET
+                title = excluded.title,
+                thumb = excluded.thumb,
+                comic_updated_at = excluded.comic_updated_at,
+                last_ep_order = excluded.last_ep_order,
+                last_ep_updated_at = excluded.last_ep_updated_at,
+                last_checked_at = excluded.last_checked_at",
+            params![
+                comic_id,
+                title,
+                thumb,
+                comic_updated_at,
+                last_ep_order.unwrap_or_default(),
+                last_ep_updated_at,
+                now
+            ],
+        )?;
+
+        Ok(connection.query_row(
+            &format!(
+                "SELECT {} FROM subscription WHERE comic_id = ?1",
+                SUBSCRIPTION_COLUMNS
+            ),
+            params![comic_id],
+            subscription_from_row,
+        )?)
+    })
+    .await
+}
+
+/// 记录一次检查的结果，并保存检查到的新章节。
+///
+/// `info` 为空时表示漫画没有更新，只刷新检查时间。
+#[frb(ignore)]
+pub async fn record_subscription_check(
+    comic_id: String,
+    info: Option<&ComicInfoEntity>,
+    new_eps: &[ComicEpEntity],
+) -> Result<Vec<SubscriptionEpisode>, CustomError> {
+    let info = info
+        .map(|info| to_json(&info.thumb).map(|thumb| (info.title.clone(), thumb, info.updated_at)))
+        .transpose()?;
+    let (last_ep_order, last_ep_updated_at) = latest_episode(new_eps);
+    let new_eps = new_eps
+        .iter()
+        .map(|ep| (ep.order, ep.title.clone(), ep.updated_at))
+        .collect::<Vec<_>>();
+
+    with_connection(move |connection| {
+        let now = Utc::now();
+        let transaction = connection.transaction()?;
+
+        transaction.execute(
+            "UPDATE subscription SET last_checked_at = ?2 WHERE comic_id = ?1",
+            params![comic_id, now],
+        )?;
+        if let Some((title, thumb, comic_updated_at)) = info {
+            let (previous_ep_order, previous_ep_updated_at): (i32, Option<DateTime<Utc>>) =
+                transaction.query_row(
+                    "SELECT last_ep_order, last_ep_updated_at FROM subscription
+                     WHERE comic_id = ?1",
+                    params![comic_id],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )?;
+
+            transaction.execute(
+                "UPDATE subscription SET title = ?2, thumb = ?3, comic_updated_at = ?4,
+                    last_ep_order = ?5, last_ep_updated_at = ?6
+                 WHERE comic_id = ?1",
+                params![
+                    comic_id,
+                    title,
+                    thumb,
+                    comic_updated_at,
+                    last_ep_order.map_or(previous_ep_order, |o| o.max(previous_ep_order)),
+                    last_ep_updated_at.max(previous_ep_updated_at)
+                ],
+            )?;
+        }
+
+        for (ep_order, ep_title, ep_updated_at) in &new_eps {
+            transaction.execute(
+                "INSERT OR IGNORE INTO subscription_episode
+                    (comic_id, ep_order, ep_title, ep_updated_at, detected_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![comic_id, ep_order, ep_title, ep_updated_at, now],
+            )?;
+        }
+
+        let episodes = transaction
+            .prepare(&format!(
+                "SELECT {} FROM subscription_episode JOIN subscription USING (comic_id)
+                 WHERE subscription_episode.comic_id = ?1 AND detected_at = ?2
+                 ORDER BY ep_order DESC",
+                SUBSCRIPTION_EPISODE_COLUMNS
+            ))?
+            .query_map(params![comic_id, now], subscription_episode_from_row)?
+            .collect::<Result<Vec<SubscriptionEpisode>, _>>()?;
+        transaction.commit()?;
+
+        Ok(episodes)
+    })
+    .await
+}
+
+/// 获取所有订阅，按订阅时间从新到旧排列。
+#[frb]
+pub async fn picacg_subscriptions() -> Result<Vec<Subscription>, CustomError> {
+    with_connection(|connection| {
+        Ok(connection
+            .prepare(&format!(
+                "SELECT {} FROM subscription ORDER BY created_at DESC, comic_id",
+                SUBSCRIPTION_COLUMNS
+            ))?
+            .query_map([], subscription_from_row)?
+            .collect::<Result<Vec<Subscription>, _>>()?)
+    })
+    .await
+}
+
+/// 获取一部漫画的订阅，未订阅时返回 `None`。
+#[frb]
+pub async fn picacg_subscription(comic_id: String) -> Result<Option<Subscription>, CustomError> {
+    with_connection(move |connection| {
+        Ok(connection
+            .query_row(
+                &format!(
+                    "SELECT {} FROM subscription WHERE comic_id = ?1",
+                    SUBSCRIPTION_COLUMNS
+                ),
+                params![comic_id],
+                subscription_from_row,
+            )
+            .optional()?)
+    })
+    .await
+}
+
+/// 取消订阅，同时删除检查到的新章节记录。
+#[frb]
+pub async fn picacg_subscription_remove(comic_id: String) -> Result<(), CustomError> {
+    with_connection(move |connection| {
+        connection.execute(
+            "DELETE FROM subscription WHERE comic_id = ?1",
+            params![comic_id],
+        )?;
+        Ok(())
+    })
+    .await
+}
+
+/// 获取检查到的新章节，按检查到的时间从新到旧排列。
+///
+/// # 参数
+/// - `unseen_only`: 为 `true` 时只返回未查看的章节。
+#[frb]
+pub async fn picacg_subscription_episodes(
+    unseen_only: bool,
+) -> Result<Vec<SubscriptionEpisode>, CustomError> {
+    with_connection(move |connection| {
+        Ok(connection
+            .prepare(&format!(
+                "SELECT {} FROM subscription_episode JOIN subscription USING (comic_id)
+                 WHERE ?1 = 0 OR seen = 0
+                 ORDER BY detected_at DESC, subscription_episode.comic_id, ep_order DESC",
+                SUBSCRIPTION_EPISODE_COLUMNS
+            ))?
+            .query_map(params![unseen_only], subscription_episode_from_row)?
+            .collect::<Result<Vec<SubscriptionEpisode>, _>>()?)
+    })
+    .await
+}
+
+/// 将新章节标记为已查看。
+///
+/// # 参数
+/// - `comic_id`: 只标记这部漫画的新章节，为空时标记全部。
+#[frb]
+pub async fn picacg_subscription_mark_seen(comic_id: Option<String>) -> Result<(), CustomError> {
+    with_connection(move |connection| {
+        connection.execute(
+            "UPDATE subscription_episode SET seen = 1 WHERE ?1 IS NULL OR comic_id = ?1",
+            params![comic_id],
+        )?;
+        Ok(())
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::{
+        storage::{
+            database::open_test_database,
+            subscription_data::{
+                picacg_subscription, picacg_subscription_episodes, picacg_subscription_mark_seen,
+                picacg_subscription_remove, record_subscription_check, save_subscription,
+            },
+        },
+        test_support::comic_info_fixture,
+        types::comic_ep_entity::ComicEpEntity,
+    };
+    use chrono::{Duration, Utc};
+
+    #[tokio::test]
+    async fn test_subscription() {
+        open_test_database().await;
+        let comic_id = "subscription".to_string();
+        picacg_subscription_remove(comic_id.clone()).await.unwrap();
+
+        let ep = |order: i32, days: i64| ComicEpEntity {
+            id: order.to_string(),
+            title: format!("ep {}", order),
+            order,
+            updated_at: Utc::now() - Duration::days(days),
+        };
+
+        let info = comic_info_fixture(&comic_id);
+        let subscription = save_subscription(&info, &[ep(1, 3), ep(2, 2)])
+            .await
+            .unwrap();
+        assert_eq!(subscription.last_ep_order, 2);
+        assert_eq!(subscription.unseen_count, 0);
+
+        let episodes = record_subscription_check(comic_id.clone(), None, &[])
+            .await
+            .unwrap();
+        assert!(episodes.is_empty());
+
+        let mut info = comic_info_fixture(&comic_id);
+        info.updated_at = Utc::now();
+        let episodes = record_subscription_check(comic_id.clone(), Some(&info), &[ep(3, 0)])
+            .await
+            .unwrap();
+        assert_eq!(episodes.len(), 1);
+        assert_eq!(episodes[0].ep_title, "ep 3");
+
+        let subscription = picacg_subscription(comic_id.clone())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(subscription.last_ep_order, 3);
+        assert_eq!(subscription.unseen_count, 1);
+
+        picacg_subscription_mark_seen(Some(comic_id.clone()))
+            .await
+            .unwrap();
+        assert!(picacg_subscription_episodes(true)
+            .await
+            .unwrap()
+            .iter()
+            .all(|e| e.comic_id != comic_id));
+
+        picacg_subscription_remove(comic_id.clone()).await.unwrap();
+        assert!(picacg_subscription(comic_id).await.unwrap().is_none());
+    }
+}
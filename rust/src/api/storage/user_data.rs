@@ -2,7 +2,8 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct UserData {
-    #[serde(default = "default_string")]
+    /// 登录令牌，保存在加密的密钥库中，不会写入配置文件。
+    #[serde(default = "default_string", skip_serializing)]
     pub token: String,
 }
 
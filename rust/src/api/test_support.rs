@@ -0,0 +1,89 @@
+//! 测试共用的实体构造函数，构造的实体除 `id` 和 `title` 外均为空值。
+
+use crate::api::types::{
+    comic_entity::ComicEntity, comic_info_entity::ComicInfoEntity,
+    comic_search_entity::ComicSearchEntity, creator_entity::CreatorEntity,
+    image_entity::avatar_default,
+};
+use chrono::Utc;
+
+/// 漫画列表中的漫画。
+pub fn comic_fixture(id: &str) -> ComicEntity {
+    ComicEntity {
+        id: id.to_string(),
+        title: format!("title {}", id),
+        author: String::new(),
+        pages_count: 0,
+        eps_count: 0,
+        finished: false,
+        categories: vec![],
+        thumb: avatar_default(),
+        likes_count: 0,
+        tags: vec![],
+        total_likes: 0,
+        total_views: 0,
+    }
+}
+
+/// 漫画详情，允许下载。
+pub fn comic_info_fixture(id: &str) -> ComicInfoEntity {
+    ComicInfoEntity {
+        id: id.to_string(),
+        title: format!("title {}", id),
+        author: String::new(),
+        pages_count: 0,
+        eps_count: 0,
+        finished: false,
+        categories: vec![],
+        thumb: avatar_default(),
+        likes_count: 0,
+        creator: creator_fixture(),
+        description: String::new(),
+        chinese_team: String::new(),
+        tags: vec![],
+        updated_at: Utc::now(),
+        created_at: String::new(),
+        allow_download: true,
+        views_count: 0,
+        is_liked: false,
+        is_favourite: false,
+        comments_count: 0,
+    }
+}
+
+/// 搜索结果中的漫画。
+pub fn comic_search_fixture(id: &str) -> ComicSearchEntity {
+    ComicSearchEntity {
+        id: id.to_string(),
+        author: String::new(),
+        categories: vec![],
+        chinese_team: String::new(),
+        created_at: String::new(),
+        description: String::new(),
+        finished: false,
+        likes_count: 0,
+        tags: vec![],
+        thumb: avatar_default(),
+        title: format!("title {}", id),
+        total_likes: None,
+        total_views: None,
+        updated_at: String::new(),
+    }
+}
+
+fn creator_fixture() -> CreatorEntity {
+    CreatorEntity {
+        id: String::new(),
+        gender: String::new(),
+        name: String::new(),
+        title: String::new(),
+        verified: None,
+        exp: 0,
+        level: 0,
+        characters: vec![],
+        avatar: avatar_default(),
+        slogan: String::new(),
+        role: String::new(),
+        character: String::new(),
+    }
+}
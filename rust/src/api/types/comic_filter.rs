@@ -0,0 +1,347 @@
+use crate::api::{
+    error::custom_error::{CustomError, CustomErrorType},
+    types::{comic_entity::ComicEntity, comic_search_entity::ComicSearchEntity},
+};
+use chrono::{DateTime, Utc};
+use flutter_rust_bridge::frb;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// 客户端筛选条件，服务器不支持的条件在本地对已请求的结果进行筛选。
+///
+/// 通过链式调用构建，例如 `ComicFilter::new().finished(true).min_likes(100)`。
+/// 漫画列表和搜索结果提供的字段不同，使用来源不提供的条件时返回 `ValidationError`：
+/// 搜索结果没有页数和章节数，漫画列表没有更新时间。
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ComicFilter {
+    pub finished: Option<bool>,
+    pub min_pages: Option<i32>,
+    pub max_pages: Option<i32>,
+    pub min_eps: Option<i32>,
+    pub max_eps: Option<i32>,
+    pub min_likes: Option<i32>,
+    pub min_views: Option<i32>,
+    /// 必须包含全部标签。
+    pub required_tags: Vec<String>,
+    /// 不能包含任意一个标签。
+    pub excluded_tags: Vec<String>,
+    pub updated_after: Option<DateTime<Utc>>,
+}
+
+/// 可以被 `ComicFilter` 筛选的漫画，个别漫画缺少的字段返回 `None`。
+pub trait Filterable {
+    /// 这一类来源完全不提供的条件，使用 `ComicFilter` 中的字段名。
+    const UNSUPPORTED_FILTERS: &'static [&'static str];
+
+    fn filter_finished(&self) -> bool;
+    fn filter_pages(&self) -> Option<i32>;
+    fn filter_eps(&self) -> Option<i32>;
+    fn filter_likes(&self) -> Option<i32>;
+    fn filter_views(&self) -> Option<i32>;
+    fn filter_tags(&self) -> &[String];
+    fn filter_updated_at(&self) -> Option<DateTime<Utc>>;
+}
+
+impl Filterable for ComicEntity {
+    const UNSUPPORTED_FILTERS: &'static [&'static str] = &["updatedAfter"];
+
+    fn filter_finished(&self) -> bool {
+        self.finished
+    }
+
+    fn filter_pages(&self) -> Option<i32> {
+        Some(self.pages_count)
+    }
+
+    fn filter_eps(&self) -> Option<i32> {
+        Some(self.eps_count)
+    }
+
+    fn filter_likes(&self) -> Option<i32> {
+        Some(self.total_likes.max(self.likes_count))
+    }
+
+    fn filter_views(&self) -> Option<i32> {
+        Some(self.total_views)
+    }
+
+    fn filter_tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    fn filter_updated_at(&self) -> Option<DateTime<Utc>> {
+        None
+    }
+}
+
+impl Filterable for ComicSearchEntity {
+    const UNSUPPORTED_FILTERS: &'static [&'static str] = &["pages", "eps"];
+
+    fn filter_finished(&self) -> bool {
+        self.finished
+    }
+
+    fn filter_pages(&self) -> Option<i32> {
+        None
+    }
+
+    fn filter_eps(&self) -> Option<i32> {
+        None
+    }
+
+    fn filter_likes(&self) -> Option<i32> {
+        Some(self.total_likes.unwrap_or(0).max(self.likes_count) as i32)
+    }
+
+    fn filter_views(&self) -> Option<i32> {
+        self.total_views.map(|views| views as i32)
+    }
+
+    fn filter_tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    fn filter_updated_at(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.updated_at)
+            .ok()
+            .map(|updated_at| updated_at.with_timezone(&Utc))
+    }
+}
+
+impl ComicFilter {
+    #[frb(sync)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[frb(sync)]
+    pub fn finished(mut self, finished: bool) -> Self {
+        self.finished = Some(finished);
+        self
+    }
+
+    #[frb(sync)]
+    pub fn pages(mut self, min: Option<i32>, max: Option<i32>) -> Self {
+        self.min_pages = min;
+        self.max_pages = max;
+        self
+    }
+
+    #[frb(sync)]
+    pub fn eps(mut self, min: Option<i32>, max: Option<i32>) -> Self {
+        self.min_eps = min;
+        self.max_eps = max;
+        self
+    }
+
+    #[frb(sync)]
+    pub fn min_likes(mut self, min_likes: i32) -> Self {
+        self.min_likes = Some(min_likes);
+        self
+    }
+
+    #[frb(sync)]
+    pub fn min_views(mut self, min_views: i32) -> Self {
+        self.min_views = Some(min_views);
+        self
+    }
+
+    #[frb(sync)]
+    pub fn required_tags(mut self, tags: Vec<String>) -> Self {
+        self.required_tags = tags;
+        self
+    }
+
+    #[frb(sync)]
+    pub fn excluded_tags(mut self, tags: Vec<String>) -> Self {
+        self.excluded_tags = tags;
+        self
+    }
+
+    #[frb(sync)]
+    pub fn updated_after(mut self, updated_after: DateTime<Utc>) -> Self {
+        self.updated_after = Some(updated_after);
+        self
+    }
+
+    /// 检查 `T` 类型的来源能否判断全部已设置的条件，不能判断时返回 `ValidationError`。
+    #[frb(ignore)]
+    pub fn validate_for<T: Filterable>(&self) -> Result<(), CustomError> {
+        let used = [
+            (
+                "pages",
+                self.min_pages.is_some() || self.max_pages.is_some(),
+            ),
+            ("eps", self.min_eps.is_some() || self.max_eps.is_some()),
+            ("updatedAfter", self.updated_after.is_some()),
+        ];
+        let unsupported = used
+            .into_iter()
+            .filter(|(name, used)| *used && T::UNSUPPORTED_FILTERS.contains(name))
+            .map(|(name, _)| name)
+            .collect::<Vec<&str>>();
+        if unsupported.is_empty() {
+            return Ok(());
+        }
+
+        Err(CustomError {
+            error_code: CustomErrorType::ValidationError,
+            error_message: format!(
+                "Filter {} is not supported for this source",
+                unsupported.join(", ")
+            ),
+        })
+    }
+
+    /// 判断漫画是否满足全部筛选条件，标签不区分大小写。
+    ///
+    /// 设置了条件而漫画缺少对应字段时视为不匹配，调用前应当先用 `validate_for` 检查来源。
+    #[frb(ignore)]
+    pub fn matches<T: Filterable>(&self, comic: &T) -> bool {
+        let in_range = |value: Option<i32>, min: Option<i32>, max: Option<i32>| {
+            (min.is_none() && max.is_none())
+                || value.is_some_and(|value| {
+                    min.is_none_or(|min| value >= min) && max.is_none_or(|max| value <= max)
+                })
+        };
+
+        if self
+            .finished
+            .is_some_and(|finished| comic.filter_finished() != finished)
+        {
+            return false;
+        }
+
+        if !in_range(comic.filter_pages(), self.min_pages, self.max_pages)
+            || !in_range(comic.filter_eps(), self.min_eps, self.max_eps)
+            || !in_range(comic.filter_likes(), self.min_likes, None)
+            || !in_range(comic.filter_views(), self.min_views, None)
+        {
+            return false;
+        }
+
+        if let Some(after) = self.updated_after {
+            if comic
+                .filter_updated_at()
+                .is_none_or(|updated_at| updated_at < after)
+            {
+                return false;
+            }
+        }
+
+        let tags = comic
+            .filter_tags()
+            .iter()
+            .map(|tag| tag.trim().to_lowercase())
+            .collect::<HashSet<String>>();
+        self.required_tags
+            .iter()
+            .all(|tag| tags.contains(&tag.trim().to_lowercase()))
+            && !self
+                .excluded_tags
+                .iter()
+                .any(|tag| tags.contains(&tag.trim().to_lowercase()))
+    }
+}
+
+/// 客户端筛选后的漫画列表。
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct ComicFilterPageData {
+    pub docs: Vec<ComicEntity>,
+    /// 本次扫描的页数。
+    pub scanned_pages: i32,
+    /// 本次扫描的漫画数量（不含被屏蔽列表隐藏的漫画）。
+    pub scanned: i32,
+    /// 被屏蔽列表隐藏的数量。
+    pub hidden: i32,
+    /// 下一次继续扫描的页码，没有更多页面时为 `None`。
+    pub next_page: Option<i32>,
+}
+
+/// 客户端筛选后的搜索结果。
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct ComicSearchFilterPageData {
+    pub docs: Vec<ComicSearchEntity>,
+    /// 本次扫描的页数。
+    pub scanned_pages: i32,
+    /// 本次扫描的漫画数量（不含被屏蔽列表隐藏的漫画）。
+    pub scanned: i32,
+    /// 被屏蔽列表隐藏的数量。
+    pub hidden: i32,
+    /// 下一次继续扫描的页码，没有更多页面时为 `None`。
+    pub next_page: Option<i32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::{
+        error::custom_error::CustomErrorType,
+        test_support::comic_fixture,
+        types::{
+            comic_entity::ComicEntity, comic_filter::ComicFilter,
+            comic_search_entity::ComicSearchEntity,
+        },
+    };
+    use chrono::Utc;
+
+    #[test]
+    fn test_comic_filter() {
+        let mut comic = comic_fixture("comic");
+        comic.finished = true;
+        comic.pages_count = 50;
+        comic.eps_count = 2;
+        comic.total_likes = 300;
+        comic.total_views = 1000;
+        comic.tags = vec!["全彩".to_string(), "Tag".to_string()];
+
+        assert!(ComicFilter::new().matches(&comic));
+        assert!(ComicFilter::new()
+            .finished(true)
+            .pages(Some(10), Some(50))
+            .eps(None, Some(3))
+            .min_likes(300)
+            .min_views(1000)
+            .required_tags(vec!["全彩".to_string(), "tag".to_string()])
+            .excluded_tags(vec!["NTR".to_string()])
+            .matches(&comic));
+
+        assert!(!ComicFilter::new().finished(false).matches(&comic));
+        assert!(!ComicFilter::new().pages(Some(51), None).matches(&comic));
+        assert!(!ComicFilter::new().eps(None, Some(1)).matches(&comic));
+        assert!(!ComicFilter::new().min_likes(301).matches(&comic));
+        assert!(!ComicFilter::new()
+            .required_tags(vec!["missing".to_string()])
+            .matches(&comic));
+        assert!(!ComicFilter::new()
+            .excluded_tags(vec!["TAG".to_string()])
+            .matches(&comic));
+    }
+
+    #[test]
+    fn test_comic_filter_unsupported() {
+        let filter = ComicFilter::new().updated_after(Utc::now());
+        assert!(matches!(
+            filter
+                .validate_for::<ComicEntity>()
+                .map_err(|e| e.error_code),
+            Err(CustomErrorType::ValidationError)
+        ));
+        assert!(filter.validate_for::<ComicSearchEntity>().is_ok());
+        // 缺少更新时间的漫画不满足条件
+        assert!(!filter.matches(&comic_fixture("comic")));
+
+        let filter = ComicFilter::new().pages(Some(10), None).min_likes(1);
+        assert!(filter.validate_for::<ComicEntity>().is_ok());
+        let error = filter.validate_for::<ComicSearchEntity>().unwrap_err();
+        assert!(error.error_message.contains("pages"));
+        assert!(ComicFilter::new()
+            .min_views(1)
+            .validate_for::<ComicSearchEntity>()
+            .is_ok());
+    }
+}
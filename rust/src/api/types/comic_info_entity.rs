@@ -30,5 +30,17 @@ pub struct ComicInfoEntity {
     pub allow_download: bool,
     pub views_count: i32,
     pub is_liked: bool,
+    #[serde(default)]
+    pub is_favourite: bool,
     pub comments_count: i32,
 }
+
+impl ComicInfoEntity {
+    /// 漫画的上传时间，`created_at` 无法解析时使用更新时间。
+    #[frb(ignore)]
+    pub fn uploaded_at(&self) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(&self.created_at)
+            .map(|time| time.with_timezone(&Utc))
+            .unwrap_or(self.updated_at)
+    }
+}
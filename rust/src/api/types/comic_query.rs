@@ -0,0 +1,130 @@
+use crate::api::types::sort::Sort;
+use flutter_rust_bridge::frb;
+use serde::{Deserialize, Serialize};
+
+/// `/comics` 接口的筛选条件。
+///
+/// 通过链式调用构建，例如 `ComicQuery::new().category("全彩".to_string()).sort(Sort::TimeNewest)`。
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ComicQuery {
+    pub category: Option<String>,
+    pub tag: Option<String>,
+    pub creator_id: Option<String>,
+    pub chinese_team: Option<String>,
+    pub sort: Sort,
+    pub page: i32,
+}
+
+impl Default for ComicQuery {
+    fn default() -> Self {
+        ComicQuery {
+            category: None,
+            tag: None,
+            creator_id: None,
+            chinese_team: None,
+            sort: Sort::Default,
+            page: 1,
+        }
+    }
+}
+
+impl ComicQuery {
+    #[frb(sync)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[frb(sync)]
+    pub fn category(mut self, category: String) -> Self {
+        self.category = Some(category);
+        self
+    }
+
+    #[frb(sync)]
+    pub fn tag(mut self, tag: String) -> Self {
+        self.tag = Some(tag);
+        self
+    }
+
+    #[frb(sync)]
+    pub fn creator_id(mut self, creator_id: String) -> Self {
+        self.creator_id = Some(creator_id);
+        self
+    }
+
+    #[frb(sync)]
+    pub fn chinese_team(mut self, chinese_team: String) -> Self {
+        self.chinese_team = Some(chinese_team);
+        self
+    }
+
+    #[frb(sync)]
+    pub fn sort(mut self, sort: Sort) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    #[frb(sync)]
+    pub fn page(mut self, page: i32) -> Self {
+        self.page = page;
+        self
+    }
+
+    /// 生成 URL 编码后的请求路径，例如 `/comics?c=%E5%85%A8%E5%BD%A9&s=ua&page=1`。
+    ///
+    /// 签名是基于请求路径计算的，所以参数必须在这里编码好，而不能交给 HTTP 客户端处理。
+    #[frb(sync)]
+    pub fn to_path(&self) -> String {
+        let mut serializer = form_urlencoded::Serializer::new(String::new());
+
+        if let Some(category) = &self.category {
+            serializer.append_pair("c", category);
+        }
+
+        if let Some(tag) = &self.tag {
+            serializer.append_pair("t", tag);
+        }
+
+        if let Some(creator_id) = &self.creator_id {
+            serializer.append_pair("ca", creator_id);
+        }
+
+        if let Some(chinese_team) = &self.chinese_team {
+            serializer.append_pair("ct", chinese_team);
+        }
+
+        serializer.append_pair("s", self.sort.as_str());
+        serializer.append_pair("page", &self.page.to_string());
+
+        format!("/comics?{}", serializer.finish())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::types::{comic_query::ComicQuery, sort::Sort};
+
+    #[test]
+    fn test_comic_query_default() {
+        assert_eq!(ComicQuery::new().to_path(), "/comics?s=ua&page=1");
+    }
+
+    #[test]
+    fn test_comic_query_encode() {
+        let query = ComicQuery::new()
+            .category("Cosplay & 同人".to_string())
+            .tag("全彩，无修".to_string())
+            .creator_id("58fd7d2d8e1b0b4c0c6e7c5f".to_string())
+            .chinese_team("a=b".to_string())
+            .sort(Sort::LikeMost)
+            .page(3);
+
+        assert_eq!(
+            query.to_path(),
+            "/comics?c=Cosplay+%26+%E5%90%8C%E4%BA%BA\
+             &t=%E5%85%A8%E5%BD%A9%EF%BC%8C%E6%97%A0%E4%BF%AE\
+             &ca=58fd7d2d8e1b0b4c0c6e7c5f&ct=a%3Db&s=ld&page=3"
+        );
+    }
+}
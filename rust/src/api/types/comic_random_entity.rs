@@ -0,0 +1,12 @@
+use crate::api::types::comic_entity::ComicEntity;
+use flutter_rust_bridge::frb;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct ComicRandomEntity {
+    pub comics: Vec<ComicEntity>,
+    /// 被屏蔽列表隐藏的数量。
+    pub hidden: i32,
+}
@@ -2,7 +2,7 @@ use crate::api::types::image_entity::ImageEntity;
 use flutter_rust_bridge::frb;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[frb(dart_metadata=("freezed"))]
 pub struct ComicSearchEntity {
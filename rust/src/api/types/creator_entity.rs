@@ -2,7 +2,7 @@ use crate::api::types::image_entity::{avatar_default, ImageEntity};
 use flutter_rust_bridge::frb;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 #[frb(dart_metadata=("freezed"))]
 pub struct CreatorEntity {
@@ -0,0 +1,47 @@
+use crate::api::{
+    storage::follow_data::FollowedCreator,
+    types::{comic_entity::ComicEntity, creator_entity::CreatorEntity, page_data::ComicPageData},
+};
+use flutter_rust_bridge::frb;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct CreatorPageEntity {
+    /// 创作者资料，创作者没有任何上传时为 `None`。
+    pub creator: Option<CreatorEntity>,
+    /// 创作者上传的漫画总数。
+    pub total: i32,
+    pub comics: ComicPageData,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct CreatorUpdateEntity {
+    pub creator: FollowedCreator,
+    /// 自上次检查以来的新上传，按上传时间从新到旧排列。
+    pub comics: Vec<ComicEntity>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct CreatorUpdateCheckEntity {
+    /// 有新上传的创作者及其新上传。
+    pub updates: Vec<CreatorUpdateEntity>,
+    /// 检查失败的创作者，检查进度不会更新，下次检查时重新获取。
+    pub failures: Vec<CreatorFailureEntity>,
+    /// 检查的创作者数量。
+    pub checked: i32,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct CreatorFailureEntity {
+    pub creator_id: String,
+    pub name: String,
+    pub error_message: String,
+}
@@ -1,7 +1,7 @@
 use flutter_rust_bridge::frb;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 #[frb(dart_metadata=("freezed"))]
 pub struct ImageEntity {
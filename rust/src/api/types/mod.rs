@@ -6,13 +6,23 @@ pub mod comic_comment_entity;
 pub mod comic_entity;
 pub mod comic_ep_entity;
 pub mod comic_ep_picture_entity;
+pub mod comic_filter;
 pub mod comic_info_entity;
+pub mod comic_query;
+pub mod comic_random_entity;
 pub mod comic_search_entity;
 pub mod comment_user_entity;
 pub mod creator_entity;
+pub mod creator_page_entity;
+pub mod forgot_password_entity;
 pub mod image_entity;
 pub mod init_entity;
 pub mod login_entity;
 pub mod page_data;
 pub mod profile_entity;
+pub mod reader_entity;
+pub mod register_entity;
+pub mod reset_password_entity;
+pub mod search_query;
 pub mod sort;
+pub mod subscription_check_entity;
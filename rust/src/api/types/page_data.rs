@@ -1,7 +1,13 @@
-use crate::api::types::{
-    announcement_entity::AnnouncementEntity, comic_comment_entity::ComicCommentEntity,
-    comic_entity::ComicEntity, comic_ep_entity::ComicEpEntity,
-    comic_ep_picture_entity::ComicEpPictureEntity, comic_search_entity::ComicSearchEntity,
+use crate::api::{
+    storage::{
+        comic_index_data::IndexedComic, favourite_data::FavouriteSnapshot,
+        folder_data::FolderComic, history_data::HistoryEntry,
+    },
+    types::{
+        announcement_entity::AnnouncementEntity, comic_comment_entity::ComicCommentEntity,
+        comic_entity::ComicEntity, comic_ep_entity::ComicEpEntity,
+        comic_ep_picture_entity::ComicEpPictureEntity, comic_search_entity::ComicSearchEntity,
+    },
 };
 use flutter_rust_bridge::frb;
 use serde::{Deserialize, Serialize};
@@ -22,6 +28,30 @@ pub struct PageData<T> {
     pub docs: Vec<T>,
 }
 
+impl<T> PageData<T> {
+    /// 对本地数据进行分页，`page` 从 1 开始，`limit` 小于 1 时视为 1。
+    #[frb(ignore)]
+    pub fn paginate(items: Vec<T>, page: i32, limit: i32) -> Self {
+        let limit = limit.max(1);
+        let page = page.max(1);
+        let total = items.len() as i32;
+        let pages = (total + limit - 1) / limit;
+        let docs = items
+            .into_iter()
+            .skip(((page - 1) * limit) as usize)
+            .take(limit as usize)
+            .collect();
+
+        PageData {
+            total,
+            limit,
+            page,
+            pages,
+            docs,
+        }
+    }
+}
+
 fn fuzzy_i32<'de, D>(d: D) -> Result<i32, D::Error>
 where
     D: serde::Deserializer<'de>,
@@ -49,9 +79,13 @@ where
 pub struct ComicPageData {
     pub total: i32,
     pub limit: i32,
+    /// 屏蔽后补足数量时为最后一次请求的页码。
     pub page: i32,
     pub pages: i32,
     pub docs: Vec<ComicEntity>,
+    /// 被屏蔽列表隐藏的数量。
+    #[serde(default)]
+    pub hidden: i32,
 }
 
 impl From<PageData<ComicEntity>> for ComicPageData {
@@ -62,6 +96,7 @@ impl From<PageData<ComicEntity>> for ComicPageData {
             page: page_data.page,
             pages: page_data.pages,
             docs: page_data.docs,
+            hidden: 0,
         }
     }
 }
@@ -141,9 +176,16 @@ impl From<PageData<ComicCommentEntity>> for ComicCommentPageData {
 pub struct ComicSearchPageData {
     pub total: i32,
     pub limit: i32,
+    /// 屏蔽后补足数量时为最后一次请求的页码。
     pub page: i32,
     pub pages: i32,
     pub docs: Vec<ComicSearchEntity>,
+    /// 被屏蔽列表隐藏的数量。
+    #[serde(default)]
+    pub hidden: i32,
+    /// 搜索语句的结果超过请求的页数上限，只包含部分结果。
+    #[serde(default)]
+    pub truncated: bool,
 }
 
 impl From<PageData<ComicSearchEntity>> for ComicSearchPageData {
@@ -154,6 +196,8 @@ impl From<PageData<ComicSearchEntity>> for ComicSearchPageData {
             page: page_data.page,
             pages: page_data.pages,
             docs: page_data.docs,
+            hidden: 0,
+            truncated: false,
         }
     }
 }
@@ -180,3 +224,98 @@ impl From<PageData<AnnouncementEntity>> for AnnouncementPageData {
         }
     }
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct HistoryPageData {
+    pub total: i32,
+    pub limit: i32,
+    pub page: i32,
+    pub pages: i32,
+    pub docs: Vec<HistoryEntry>,
+}
+
+impl From<PageData<HistoryEntry>> for HistoryPageData {
+    fn from(page_data: PageData<HistoryEntry>) -> Self {
+        HistoryPageData {
+            total: page_data.total,
+            limit: page_data.limit,
+            page: page_data.page,
+            pages: page_data.pages,
+            docs: page_data.docs,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct FavouriteSnapshotPageData {
+    pub total: i32,
+    pub limit: i32,
+    pub page: i32,
+    pub pages: i32,
+    pub docs: Vec<FavouriteSnapshot>,
+}
+
+impl From<PageData<FavouriteSnapshot>> for FavouriteSnapshotPageData {
+    fn from(page_data: PageData<FavouriteSnapshot>) -> Self {
+        FavouriteSnapshotPageData {
+            total: page_data.total,
+            limit: page_data.limit,
+            page: page_data.page,
+            pages: page_data.pages,
+            docs: page_data.docs,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct FolderComicPageData {
+    pub total: i32,
+    pub limit: i32,
+    pub page: i32,
+    pub pages: i32,
+    pub docs: Vec<FolderComic>,
+}
+
+impl From<PageData<FolderComic>> for FolderComicPageData {
+    fn from(page_data: PageData<FolderComic>) -> Self {
+        FolderComicPageData {
+            total: page_data.total,
+            limit: page_data.limit,
+            page: page_data.page,
+            pages: page_data.pages,
+            docs: page_data.docs,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct IndexedComicPageData {
+    pub total: i32,
+    pub limit: i32,
+    pub page: i32,
+    pub pages: i32,
+    pub docs: Vec<IndexedComic>,
+    /// 被屏蔽列表隐藏的数量。
+    pub hidden: i32,
+}
+
+impl From<PageData<IndexedComic>> for IndexedComicPageData {
+    fn from(page_data: PageData<IndexedComic>) -> Self {
+        IndexedComicPageData {
+            total: page_data.total,
+            limit: page_data.limit,
+            page: page_data.page,
+            pages: page_data.pages,
+            docs: page_data.docs,
+            hidden: 0,
+        }
+    }
+}
@@ -3,7 +3,7 @@ use chrono::{DateTime, Utc};
 use flutter_rust_bridge::frb;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 #[frb(dart_metadata=("freezed"))]
 pub struct ProfileEntity {
@@ -18,6 +18,8 @@ pub struct ProfileEntity {
     pub characters: Vec<String>,
     #[serde(default = "avatar_default")]
     pub avatar: ImageEntity,
+    #[serde(default)]
+    pub slogan: String,
     pub birthday: String,
     pub email: String,
     #[serde(rename = "created_at")]
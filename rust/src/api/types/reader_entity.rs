@@ -0,0 +1,39 @@
+use crate::api::types::image_entity::ImageEntity;
+use flutter_rust_bridge::frb;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct ReaderPage {
+    /// 页码（从 0 开始）。
+    pub index: i32,
+    pub id: String,
+    pub media: ImageEntity,
+    pub url: String,
+    /// 已缓存时为本地文件路径。
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct ReaderManifest {
+    pub comic_id: String,
+    pub ep_order: i32,
+    pub pages: Vec<ReaderPage>,
+    /// 下一章节的序号，已是最后一章时为空。
+    pub next_ep_order: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct ReaderPageReady {
+    pub comic_id: String,
+    pub ep_order: i32,
+    /// 页码（从 0 开始）。
+    pub index: i32,
+    /// 本地文件路径。
+    pub path: String,
+}
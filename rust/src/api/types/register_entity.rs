@@ -0,0 +1,61 @@
+use chrono::NaiveDate;
+use flutter_rust_bridge::frb;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gender {
+    #[serde(rename = "m")]
+    Male,
+    #[serde(rename = "f")]
+    Female,
+    #[serde(rename = "bot")]
+    Bot,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct RegisterRequest {
+    pub email: String,
+    pub password: String,
+    pub name: String,
+    pub birthday: NaiveDate,
+    pub gender: Gender,
+    pub question1: String,
+    pub question2: String,
+    pub question3: String,
+    pub answer1: String,
+    pub answer2: String,
+    pub answer3: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegisterField {
+    Email,
+    Password,
+    Name,
+    Birthday,
+    Question1,
+    Question2,
+    Question3,
+    Answer1,
+    Answer2,
+    Answer3,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct RegisterFieldError {
+    pub field: RegisterField,
+    pub message: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct RegisterEntity {
+    /// 新建账号的 ID。
+    #[serde(rename = "_id")]
+    pub id: String,
+}
@@ -0,0 +1,19 @@
+use flutter_rust_bridge::frb;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct ResetPasswordRequest {
+    pub email: String,
+    /// 安全问题序号，取值为 1、2、3。
+    pub question_no: i32,
+    pub answer: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct ResetPasswordEntity {
+    pub password: String,
+}
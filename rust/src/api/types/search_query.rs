@@ -0,0 +1,353 @@
+use crate::api::{
+    error::custom_error::{CustomError, CustomErrorType},
+    types::{comic_filter::ComicFilter, comic_search_entity::ComicSearchEntity},
+    utils::chinese::normalize_chinese,
+};
+use flutter_rust_bridge::frb;
+use serde::{Deserialize, Serialize};
+
+/// 搜索条件匹配的字段。
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchField {
+    /// 不带前缀的关键词。
+    Keyword,
+    /// 引号中的短语，必须完整出现。
+    Phrase,
+    /// `title:`
+    Title,
+    /// `tag:`
+    Tag,
+    /// `author:`
+    Author,
+    /// `category:`，服务器不能按分类搜索关键词，只在本地匹配。
+    Category,
+    /// `team:`
+    ChineseTeam,
+}
+
+impl SearchField {
+    fn from_prefix(prefix: &str) -> Option<Self> {
+        match prefix {
+            "title" => Some(SearchField::Title),
+            "tag" => Some(SearchField::Tag),
+            "author" => Some(SearchField::Author),
+            "category" | "cat" => Some(SearchField::Category),
+            "team" => Some(SearchField::ChineseTeam),
+            _ => None,
+        }
+    }
+
+    /// 是否可以作为关键词请求服务器搜索。
+    #[frb(ignore)]
+    pub fn searchable(self) -> bool {
+        self != SearchField::Category
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct SearchTerm {
+    pub field: SearchField,
+    pub value: String,
+}
+
+impl SearchTerm {
+    /// 判断漫画是否匹配该条件，忽略大小写和简繁写法。
+    ///
+    /// 标签和分类需要完全相同，其他字段包含即可；关键词和短语匹配标题、作者、简介、标签和汉化组。
+    #[frb(ignore)]
+    pub fn matches(&self, comic: &ComicSearchEntity) -> bool {
+        let value = normalize_chinese(&self.value);
+        let contains = |text: &str| normalize_chinese(text).contains(&value);
+        let equals = |texts: &[String]| {
+            texts
+                .iter()
+                .any(|text| normalize_chinese(text.trim()) == value)
+        };
+
+        match self.field {
+            SearchField::Keyword | SearchField::Phrase => {
+                contains(&comic.title)
+                    || contains(&comic.author)
+                    || contains(&comic.description)
+                    || contains(&comic.chinese_team)
+                    || comic.tags.iter().any(|tag| contains(tag))
+            }
+            SearchField::Title => contains(&comic.title),
+            SearchField::Tag => equals(&comic.tags),
+            SearchField::Author => contains(&comic.author),
+            SearchField::Category => equals(&comic.categories),
+            SearchField::ChineseTeam => contains(&comic.chinese_team),
+        }
+    }
+}
+
+/// 解析后的搜索语句。
+///
+/// 语法示例：`tag:全彩 -tag:NTR author:xxx "exact phrase" finished:true likes:>=100`。
+/// - 以空白分隔的条件都要匹配，`OR` 或 `|` 连接的条件匹配任意一个即可。
+/// - `-` 开头的条件为排除条件。
+/// - 支持的前缀：`title:`、`tag:`、`author:`、`category:`（`cat:`）、`team:`，
+///   以及筛选条件 `finished:`、`likes:`、`views:`。不认识的前缀按普通关键词处理。
+/// - 引号中的内容作为一个整体，也可以和前缀一起使用，如 `tag:"a b"`。
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchQuery {
+    /// 条件组，组内的条件匹配任意一个即可，所有组都要匹配。
+    pub groups: Vec<Vec<SearchTerm>>,
+    /// 匹配任意一个即排除。
+    pub excluded: Vec<SearchTerm>,
+    pub filter: ComicFilter,
+}
+
+enum Token {
+    Or,
+    Term {
+        negated: bool,
+        prefix: Option<String>,
+        text: String,
+        quoted: bool,
+    },
+}
+
+fn tokenize(query: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = query.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        if chars.peek().is_none() {
+            return tokens;
+        }
+
+        let negated = chars.next_if_eq(&'-').is_some();
+        let mut prefix = None;
+        let mut text = String::new();
+        let mut quoted = false;
+        while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+            match c {
+                // 没有结束的引号包含到末尾
+                '"' => {
+                    quoted = true;
+                    text.extend(chars.by_ref().take_while(|c| *c != '"'));
+                }
+                ':' if prefix.is_none() && !quoted && !text.is_empty() => {
+                    prefix = Some(std::mem::take(&mut text));
+                }
+                c => text.push(c),
+            }
+        }
+
+        if !negated && !quoted && prefix.is_none() && (text == "OR" || text == "|") {
+            tokens.push(Token::Or);
+        } else {
+            tokens.push(Token::Term {
+                negated,
+                prefix,
+                text,
+                quoted,
+            });
+        }
+    }
+}
+
+fn parameter_error(message: String) -> CustomError {
+    CustomError {
+        error_code: CustomErrorType::ParameterError,
+        error_message: message,
+    }
+}
+
+fn parse_bool(value: &str) -> Result<bool, CustomError> {
+    match value.to_lowercase().as_str() {
+        "true" | "yes" | "1" => Ok(true),
+        "false" | "no" | "0" => Ok(false),
+        _ => Err(parameter_error(format!("Invalid boolean value: {}", value))),
+    }
+}
+
+/// 解析 `N`、`>=N` 或 `>N` 形式的下限。
+fn parse_min(value: &str) -> Result<i32, CustomError> {
+    let (number, offset) = match value.strip_prefix(">=") {
+        Some(number) => (number, 0),
+        None => match value.strip_prefix('>') {
+            Some(number) => (number, 1),
+            None => (value, 0),
+        },
+    };
+    number
+        .parse::<i32>()
+        .ok()
+        .and_then(|min| min.checked_add(offset))
+        .ok_or_else(|| parameter_error(format!("Invalid number value: {}", value)))
+}
+
+impl SearchQuery {
+    /// 解析搜索语句，筛选条件的值无效时返回 `ParameterError`。
+    #[frb(ignore)]
+    pub fn parse(query: &str) -> Result<Self, CustomError> {
+        let mut search_query = SearchQuery::default();
+        let mut pending_or = false;
+
+        for token in tokenize(query) {
+            let (negated, prefix, text, quoted) = match token {
+                Token::Or => {
+                    pending_or = !search_query.groups.is_empty();
+                    continue;
+                }
+                Token::Term {
+                    negated,
+                    prefix,
+                    text,
+                    quoted,
+                } => (negated, prefix, text, quoted),
+            };
+            let value = text.trim();
+            if value.is_empty() {
+                continue;
+            }
+
+            let field = match prefix.as_deref().map(str::to_lowercase).as_deref() {
+                Some("finished") => {
+                    search_query.filter.finished = Some(parse_bool(value)? != negated);
+                    pending_or = false;
+                    continue;
+                }
+                Some(key @ ("likes" | "views")) => {
+                    if negated {
+                        return Err(parameter_error(format!("Cannot negate {}:", key)));
+                    }
+                    let min = Some(parse_min(value)?);
+                    match key {
+                        "likes" => search_query.filter.min_likes = min,
+                        _ => search_query.filter.min_views = min,
+                    }
+                    pending_or = false;
+                    continue;
+                }
+                Some(key) => SearchField::from_prefix(key),
+                None if quoted => Some(SearchField::Phrase),
+                None => Some(SearchField::Keyword),
+            };
+            let term = match (field, prefix) {
+                (Some(field), _) => SearchTerm {
+                    field,
+                    value: value.to_string(),
+                },
+                (None, prefix) => SearchTerm {
+                    field: SearchField::Keyword,
+                    value: format!("{}:{}", prefix.unwrap_or_default(), value),
+                },
+            };
+
+            if negated {
+                search_query.excluded.push(term);
+            } else if pending_or {
+                if let Some(group) = search_query.groups.last_mut() {
+                    group.push(term);
+                }
+            } else {
+                search_query.groups.push(vec![term]);
+            }
+            pending_or = false;
+        }
+
+        Ok(search_query)
+    }
+
+    /// 可以请求服务器搜索的条件组，包含分类条件的组只能在本地匹配。
+    #[frb(ignore)]
+    pub fn searched_groups(&self) -> impl Iterator<Item = &Vec<SearchTerm>> {
+        self.groups
+            .iter()
+            .filter(|group| group.iter().all(|term| term.field.searchable()))
+    }
+
+    /// 判断漫画是否满足服务器搜索以外的条件：只能在本地匹配的条件组、排除条件和筛选条件。
+    #[frb(ignore)]
+    pub fn matches_local(&self, comic: &ComicSearchEntity) -> bool {
+        self.groups
+            .iter()
+            .filter(|group| !group.iter().all(|term| term.field.searchable()))
+            .all(|group| group.iter().any(|term| term.matches(comic)))
+            && !self.excluded.iter().any(|term| term.matches(comic))
+            && self.filter.matches(comic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::{
+        test_support::comic_search_fixture,
+        types::search_query::{SearchField, SearchQuery, SearchTerm},
+    };
+
+    fn term(field: SearchField, value: &str) -> SearchTerm {
+        SearchTerm {
+            field,
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_search_query() {
+        let query = SearchQuery::parse(
+            "tag:全彩 -tag:NTR author:xxx \"exact phrase\" finished:true foo OR tag:\"a b\" \
+             likes:>99 url:x -\"bad word\"",
+        )
+        .unwrap();
+
+        assert_eq!(
+            query.groups,
+            vec![
+                vec![term(SearchField::Tag, "全彩")],
+                vec![term(SearchField::Author, "xxx")],
+                vec![term(SearchField::Phrase, "exact phrase")],
+                vec![
+                    term(SearchField::Keyword, "foo"),
+                    term(SearchField::Tag, "a b")
+                ],
+                vec![term(SearchField::Keyword, "url:x")],
+            ]
+        );
+        assert_eq!(
+            query.excluded,
+            vec![
+                term(SearchField::Tag, "NTR"),
+                term(SearchField::Phrase, "bad word")
+            ]
+        );
+        assert_eq!(query.filter.finished, Some(true));
+        assert_eq!(query.filter.min_likes, Some(100));
+
+        assert_eq!(
+            SearchQuery::parse("-finished:yes").unwrap().filter.finished,
+            Some(false)
+        );
+        assert!(SearchQuery::parse("finished:maybe").is_err());
+        assert!(SearchQuery::parse("-likes:10").is_err());
+        assert_eq!(
+            SearchQuery::parse(" OR | ").unwrap(),
+            SearchQuery::default()
+        );
+    }
+
+    #[test]
+    fn test_search_query_matches_local() {
+        let mut comic = comic_search_fixture("query");
+        comic.tags = vec!["全彩".to_string()];
+        comic.categories = vec!["長篇".to_string()];
+        comic.description = "Exact Phrase here".to_string();
+
+        let query = SearchQuery::parse("category:长篇 \"exact phrase\" -tag:ntr").unwrap();
+        assert_eq!(query.searched_groups().count(), 1);
+        assert!(query.matches_local(&comic));
+
+        comic.tags.push("NTR".to_string());
+        assert!(!query.matches_local(&comic));
+        assert!(!SearchQuery::parse("cat:短篇 OR x")
+            .unwrap()
+            .matches_local(&comic_search_fixture("query")));
+    }
+}
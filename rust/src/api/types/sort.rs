@@ -1,13 +1,68 @@
-pub struct Sort(&'static str);
+use flutter_rust_bridge::frb;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Sort {
+    /// 默认排序
+    #[default]
+    #[serde(rename = "ua")]
+    Default,
+    /// 新到旧
+    #[serde(rename = "dd")]
+    TimeNewest,
+    /// 旧到新
+    #[serde(rename = "da")]
+    TimeOldest,
+    /// 最多点赞
+    #[serde(rename = "ld")]
+    LikeMost,
+    /// 最多观看
+    #[serde(rename = "vd")]
+    ViewMost,
+}
 
 impl Sort {
-    pub const SORT_DEFAULT: Sort = Sort("ua");
-    pub const SORT_TIME_NEWEST: Sort = Sort("dd");
-    pub const SORT_TIME_OLDEST: Sort = Sort("da");
-    pub const SORT_LIKE_MOST: Sort = Sort("ld");
-    pub const SORT_VIVE_MOST: Sort = Sort("vd");
+    pub const ALL: [Sort; 5] = [
+        Sort::Default,
+        Sort::TimeNewest,
+        Sort::TimeOldest,
+        Sort::LikeMost,
+        Sort::ViewMost,
+    ];
 
+    /// 服务器使用的排序参数。
+    #[frb(ignore)]
     pub fn as_str(&self) -> &'static str {
-        self.0
+        match self {
+            Sort::Default => "ua",
+            Sort::TimeNewest => "dd",
+            Sort::TimeOldest => "da",
+            Sort::LikeMost => "ld",
+            Sort::ViewMost => "vd",
+        }
+    }
+}
+
+impl Display for Sort {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::types::sort::Sort;
+
+    #[test]
+    fn test_sort_serde_display() {
+        for sort in Sort::ALL {
+            let json = serde_json::to_string(&sort).unwrap();
+            assert_eq!(json, format!("\"{}\"", sort));
+            assert_eq!(serde_json::from_str::<Sort>(&json).unwrap(), sort);
+        }
+
+        assert_eq!(Sort::default().to_string(), "ua");
+        assert_eq!(Sort::TimeNewest.to_string(), "dd");
     }
 }
@@ -0,0 +1,24 @@
+use crate::api::storage::subscription_data::SubscriptionEpisode;
+use flutter_rust_bridge::frb;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct SubscriptionCheckEntity {
+    /// 本次检查到的新章节。
+    pub episodes: Vec<SubscriptionEpisode>,
+    /// 检查失败的订阅。
+    pub failures: Vec<SubscriptionFailureEntity>,
+    /// 检查的订阅数量。
+    pub checked: i32,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+#[frb(dart_metadata=("freezed"))]
+pub struct SubscriptionFailureEntity {
+    pub comic_id: String,
+    pub title: String,
+    pub error_message: String,
+}
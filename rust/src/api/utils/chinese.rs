@@ -0,0 +1,106 @@
+use flutter_rust_bridge::frb;
+use std::{collections::HashMap, sync::LazyLock};
+
+/// 内置的简繁字对照表，格式见文件开头的说明。
+const CHINESE_VARIANTS: &str = include_str!("chinese_variants.txt");
+
+struct ChineseVariants {
+    to_simplified: HashMap<char, char>,
+    to_traditional: HashMap<char, char>,
+}
+
+static VARIANTS: LazyLock<ChineseVariants> = LazyLock::new(|| {
+    let mut to_simplified = HashMap::new();
+    let mut to_traditional = HashMap::new();
+
+    for pair in CHINESE_VARIANTS
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .flat_map(str::split_whitespace)
+    {
+        let mut chars = pair.chars();
+        let (Some(simplified), Some(traditional)) = (chars.next(), chars.next()) else {
+            continue;
+        };
+        to_traditional.entry(simplified).or_insert(traditional);
+        if simplified != traditional {
+            to_simplified.insert(traditional, simplified);
+        }
+    }
+
+    ChineseVariants {
+        to_simplified,
+        to_traditional,
+    }
+});
+
+fn convert(text: &str, table: &HashMap<char, char>) -> String {
+    text.chars()
+        .map(|c| table.get(&c).copied().unwrap_or(c))
+        .collect()
+}
+
+/// 将繁体字逐字转换为简体字，不在对照表中的字符保持不变。
+#[frb(sync)]
+pub fn picacg_to_simplified(text: String) -> String {
+    to_simplified(&text)
+}
+
+/// 将简体字逐字转换为繁体字，不在对照表中的字符保持不变。
+///
+/// 一个简体字对应多个繁体字时使用最常用的写法，结果不一定符合词语的用法。
+#[frb(sync)]
+pub fn picacg_to_traditional(text: String) -> String {
+    to_traditional(&text)
+}
+
+#[frb(ignore)]
+pub fn to_simplified(text: &str) -> String {
+    convert(text, &VARIANTS.to_simplified)
+}
+
+#[frb(ignore)]
+pub fn to_traditional(text: &str) -> String {
+    convert(text, &VARIANTS.to_traditional)
+}
+
+/// 用于本地匹配的文本：转为简体并忽略大小写，简体和繁体的写法得到相同的结果。
+#[frb(ignore)]
+pub fn normalize_chinese(text: &str) -> String {
+    to_simplified(&text.to_lowercase())
+}
+
+/// 搜索时需要请求的写法：原文、简体和繁体，去掉重复的写法。
+#[frb(ignore)]
+pub fn chinese_variants(text: &str) -> Vec<String> {
+    let mut variants = vec![text.to_string()];
+    for variant in [to_simplified(text), to_traditional(text)] {
+        if !variants.contains(&variant) {
+            variants.push(variant);
+        }
+    }
+    variants
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::utils::chinese::{
+        chinese_variants, normalize_chinese, to_simplified, to_traditional,
+    };
+
+    #[test]
+    fn test_chinese_variants() {
+        assert_eq!(to_simplified("進擊的巨人"), "进击的巨人");
+        assert_eq!(to_traditional("进击的巨人"), "進擊的巨人");
+        assert_eq!(to_simplified("頭髮"), "头发");
+        assert_eq!(to_traditional("头发"), "頭發");
+        // 简体字本身也是常用繁体字时不转换
+        assert_eq!(to_traditional("干后"), "干后");
+        assert_eq!(to_simplified("乾燥 後來"), "干燥 后来");
+
+        assert_eq!(normalize_chinese("ABC 戀愛"), normalize_chinese("abc 恋爱"));
+        assert_eq!(chinese_variants("进击"), vec!["进击", "進擊"]);
+        assert_eq!(chinese_variants("進擊"), vec!["進擊", "进击"]);
+        assert_eq!(chinese_variants("abc"), vec!["abc"]);
+    }
+}
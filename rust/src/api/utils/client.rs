@@ -14,19 +14,43 @@ const API_KEY: &str = "C69BAF41DA5ABD1FFEDC6D2FEA56B";
 const NONCE: &str = "b1ab87b4800d4d4590a11701b8551afa";
 const DIGEST_KEY: &str = "~d}$Q7$eIni=V)9\\RK/P.RM4;9[7|@/CA}b~OW!3?EV`:<>M7pddUBL5n|0/*Cn";
 
+tokio::task_local! {
+    /// 为其他账号发送请求时使用的令牌，优先于配置中的令牌。
+    static REQUEST_TOKEN: String;
+    /// 设置时请求只发送一次，不自动重试。
+    static SINGLE_ATTEMPT: ();
+}
+
+/// 使用 `token` 代替当前登录的令牌执行 `future` 中的请求，不修改配置。
+#[frb(ignore)]
+pub async fn with_request_token<F: std::future::Future>(token: String, future: F) -> F::Output {
+    REQUEST_TOKEN.scope(token, future).await
+}
+
+/// 执行 `future` 中的请求时不自动重试，用于重复发送会产生副作用的请求，例如发表评论。
+#[frb(ignore)]
+pub async fn without_retry<F: std::future::Future>(future: F) -> F::Output {
+    SINGLE_ATTEMPT.scope((), future).await
+}
+
+static HTTP_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .danger_accept_invalid_certs(true)
+        .build()
+        .unwrap()
+});
+
 static CLIENT: LazyLock<ClientWithMiddleware> = LazyLock::new(|| {
-    ClientBuilder::new(
-        reqwest::Client::builder()
-            .danger_accept_invalid_certs(true)
-            .build()
-            .unwrap(),
-    )
-    .with(RetryTransientMiddleware::new_with_policy(
-        ExponentialBackoff::builder().build_with_max_retries(2),
-    ))
-    .build()
+    ClientBuilder::new(HTTP_CLIENT.clone())
+        .with(RetryTransientMiddleware::new_with_policy(
+            ExponentialBackoff::builder().build_with_max_retries(2),
+        ))
+        .build()
 });
 
+static SINGLE_ATTEMPT_CLIENT: LazyLock<ClientWithMiddleware> =
+    LazyLock::new(|| ClientBuilder::new(HTTP_CLIENT.clone()).build());
+
 #[derive(Debug)]
 pub enum HttpResponseBody {
     Text(String),
@@ -61,7 +85,11 @@ pub async fn send_request(
         _ => Method::GET,
     };
 
-    let mut request = CLIENT.request(method, url).timeout(Duration::from_secs(5));
+    let client = match SINGLE_ATTEMPT.try_with(|_| ()) {
+        Ok(()) => &SINGLE_ATTEMPT_CLIENT,
+        Err(_) => &CLIENT,
+    };
+    let mut request = client.request(method, url).timeout(Duration::from_secs(5));
     if let Some(headers) = headers {
         for (k, v) in headers {
             let header_name =
@@ -174,10 +202,12 @@ pub async fn picacg_request(
         ),
     ];
 
-    let token = if let Ok(config) = CONFIG.read() {
-        config.user_data.token.clone()
-    } else {
-        String::new()
+    let token = match REQUEST_TOKEN.try_with(Clone::clone) {
+        Ok(token) => token,
+        Err(_) => CONFIG
+            .read()
+            .map(|config| config.user_data.token.clone())
+            .unwrap_or_default(),
     };
 
     if !token.is_empty() {
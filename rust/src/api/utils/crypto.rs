@@ -1,11 +1,114 @@
+use argon2::Argon2;
+use chacha20poly1305::{
+    aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng, Payload},
+    XChaCha20Poly1305, XNonce,
+};
 use hmac::{Hmac, Mac};
-use sha2::Sha256;
+use sha2::{Digest, Sha256};
 
 type HmacSha256 = Hmac<Sha256>;
 
 pub fn hmac_hex(key: &str, str: &str) -> String {
-    let mac = HmacSha256::new_from_slice(key.as_bytes());
+    let mac = <HmacSha256 as Mac>::new_from_slice(key.as_bytes());
     let mut mac = mac.expect("HMAC can take key of any size");
     mac.update(str.as_bytes());
     hex::encode(mac.finalize().into_bytes().as_slice())
 }
+
+pub fn sha256_hex(str: &str) -> String {
+    hex::encode(Sha256::digest(str.as_bytes()))
+}
+
+/// 对称加密密钥长度（字节）。
+pub const SECRET_KEY_LENGTH: usize = 32;
+/// 口令派生密钥使用的盐长度（字节）。
+pub const SECRET_SALT_LENGTH: usize = 16;
+
+/// 生成 `N` 字节的安全随机数。
+pub fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+    OsRng.fill_bytes(&mut bytes);
+    bytes
+}
+
+/// 使用 Argon2id 从口令派生加密密钥。
+pub fn derive_key(passphrase: &str, salt: &[u8]) -> Result<[u8; SECRET_KEY_LENGTH], String> {
+    let mut key = [0u8; SECRET_KEY_LENGTH];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| e.to_string())?;
+    Ok(key)
+}
+
+/// 使用 XChaCha20-Poly1305 加密，返回 `(nonce, ciphertext)`。
+///
+/// `aad` 参与认证但不加密，解密时必须提供相同的内容。
+pub fn encrypt(
+    key: &[u8; SECRET_KEY_LENGTH],
+    plaintext: &[u8],
+    aad: &[u8],
+) -> Result<(Vec<u8>, Vec<u8>), String> {
+    let cipher = XChaCha20Poly1305::new(key.into());
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: plaintext,
+                aad,
+            },
+        )
+        .map_err(|e| e.to_string())?;
+    Ok((nonce.to_vec(), ciphertext))
+}
+
+/// 解密 `encrypt` 的结果，密钥错误或数据被篡改时返回错误。
+pub fn decrypt(
+    key: &[u8; SECRET_KEY_LENGTH],
+    nonce: &[u8],
+    ciphertext: &[u8],
+    aad: &[u8],
+) -> Result<Vec<u8>, String> {
+    if nonce.len() != XNonce::default().len() {
+        return Err("Invalid nonce length".to_string());
+    }
+
+    XChaCha20Poly1305::new(key.into())
+        .decrypt(
+            XNonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad,
+            },
+        )
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::utils::crypto::{decrypt, derive_key, encrypt, random_bytes};
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let key = random_bytes::<32>();
+        let (nonce, ciphertext) = encrypt(&key, b"secret", b"aad").unwrap();
+        assert_ne!(ciphertext, b"secret");
+        assert_eq!(
+            decrypt(&key, &nonce, &ciphertext, b"aad").unwrap(),
+            b"secret"
+        );
+
+        assert!(decrypt(&key, &nonce, &ciphertext, b"other").is_err());
+        assert!(decrypt(&random_bytes::<32>(), &nonce, &ciphertext, b"aad").is_err());
+
+        let salt = random_bytes::<16>();
+        assert_eq!(
+            derive_key("passphrase", &salt).unwrap(),
+            derive_key("passphrase", &salt).unwrap()
+        );
+        assert_ne!(
+            derive_key("passphrase", &salt).unwrap(),
+            derive_key("other", &salt).unwrap()
+        );
+    }
+}
@@ -0,0 +1,26 @@
+use crate::api::error::custom_error::{CustomError, CustomErrorType};
+use std::{
+    fs::{self, File},
+    io::Write,
+    path::Path,
+};
+
+/// 先写入临时文件并同步到磁盘，再重命名覆盖 `file_path`，
+/// 保证 `file_path` 要么是旧内容，要么是完整的新内容。
+pub fn write_file_atomic(
+    file_path: &Path,
+    temp_file_path: &Path,
+    content: &[u8],
+) -> Result<(), CustomError> {
+    let write_error = |e: std::io::Error| CustomError {
+        error_code: CustomErrorType::FileWriteError,
+        error_message: format!("Failed to write {}: {}", file_path.display(), e),
+    };
+
+    let mut file = File::create(temp_file_path).map_err(write_error)?;
+    file.write_all(content).map_err(write_error)?;
+    file.sync_all().map_err(write_error)?;
+    drop(file);
+
+    fs::rename(temp_file_path, file_path).map_err(write_error)
+}
@@ -0,0 +1,75 @@
+use crate::api::error::custom_error::{CustomError, CustomErrorType};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use image::{codecs::jpeg::JpegEncoder, imageops::FilterType};
+
+pub const AVATAR_SIZE: u32 = 200;
+const AVATAR_JPEG_QUALITY: u8 = 85;
+
+/// 将任意格式的图片数据转换为服务器要求的头像格式。
+///
+/// 图片会被居中裁剪并缩放为 `AVATAR_SIZE` x `AVATAR_SIZE`，
+/// 重新编码为 JPEG 后以 base64 data URL 的形式返回。
+///
+/// # 参数
+/// - `bytes`: 原始图片数据（支持 JPEG、PNG、GIF、WebP）。
+///
+/// # 返回
+/// - `Ok(String)`：形如 `data:image/jpeg;base64,...` 的字符串。
+/// - `Err(CustomError)`：图片解码或编码失败时返回错误信息。
+pub fn encode_avatar_data_url(bytes: &[u8]) -> Result<String, CustomError> {
+    let image = image::load_from_memory(bytes).map_err(|e| CustomError {
+        error_code: CustomErrorType::ImageProcessError,
+        error_message: format!("Failed to decode avatar image: {}", e),
+    })?;
+
+    let avatar = image
+        .resize_to_fill(AVATAR_SIZE, AVATAR_SIZE, FilterType::Lanczos3)
+        .to_rgb8();
+
+    let mut jpeg = Vec::new();
+    avatar
+        .write_with_encoder(JpegEncoder::new_with_quality(
+            &mut jpeg,
+            AVATAR_JPEG_QUALITY,
+        ))
+        .map_err(|e| CustomError {
+            error_code: CustomErrorType::ImageProcessError,
+            error_message: format!("Failed to encode avatar image: {}", e),
+        })?;
+
+    Ok(format!("data:image/jpeg;base64,{}", STANDARD.encode(jpeg)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::utils::image::{encode_avatar_data_url, AVATAR_SIZE};
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use image::{ImageFormat, RgbaImage};
+    use std::io::Cursor;
+
+    #[test]
+    fn test_encode_avatar_data_url() {
+        let mut png = Vec::new();
+        RgbaImage::new(640, 480)
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .unwrap();
+
+        let data_url = encode_avatar_data_url(&png).unwrap();
+        let encoded = data_url
+            .strip_prefix("data:image/jpeg;base64,")
+            .expect("Expected jpeg data url");
+
+        let jpeg = STANDARD.decode(encoded).unwrap();
+        assert_eq!(image::guess_format(&jpeg).unwrap(), ImageFormat::Jpeg);
+
+        let avatar = image::load_from_memory(&jpeg).unwrap();
+        assert_eq!(avatar.width(), AVATAR_SIZE);
+        assert_eq!(avatar.height(), AVATAR_SIZE);
+    }
+
+    #[test]
+    fn test_encode_avatar_data_url_invalid() {
+        let result = encode_avatar_data_url(b"not an image");
+        assert!(result.is_err());
+    }
+}
@@ -1,3 +1,10 @@
+pub mod chinese;
 pub mod client;
 pub mod crypto;
+pub mod file;
+pub mod image;
+pub mod paginator;
 pub mod parse_json;
+pub mod pinyin;
+pub mod rate_limiter;
+pub mod validator;
@@ -0,0 +1,254 @@
+use crate::api::{
+    error::custom_error::CustomError,
+    types::page_data::{
+        AnnouncementPageData, ComicCommentPageData, ComicEpPageData, ComicEpPicturePageData,
+        ComicPageData, ComicSearchPageData, PageData,
+    },
+};
+use crate::frb_generated::{SseEncode, StreamSink};
+use futures::{stream, Stream, StreamExt, TryStreamExt};
+use std::{future::Future, pin::pin};
+
+/// 同时请求的最大页数。
+pub const MAX_CONCURRENT_PAGES: usize = 4;
+
+/// 分页数据。
+pub trait Paged {
+    type Item;
+
+    fn page(&self) -> i32;
+    fn pages(&self) -> i32;
+    fn into_docs(self) -> Vec<Self::Item>;
+}
+
+macro_rules! impl_paged {
+    ($page_data:ty, $item:ty) => {
+        impl Paged for $page_data {
+            type Item = $item;
+
+            fn page(&self) -> i32 {
+                self.page
+            }
+
+            fn pages(&self) -> i32 {
+                self.pages
+            }
+
+            fn into_docs(self) -> Vec<$item> {
+                self.docs
+            }
+        }
+    };
+}
+
+impl<T> Paged for PageData<T> {
+    type Item = T;
+
+    fn page(&self) -> i32 {
+        self.page
+    }
+
+    fn pages(&self) -> i32 {
+        self.pages
+    }
+
+    fn into_docs(self) -> Vec<T> {
+        self.docs
+    }
+}
+
+impl_paged!(ComicPageData, crate::api::types::comic_entity::ComicEntity);
+impl_paged!(
+    ComicSearchPageData,
+    crate::api::types::comic_search_entity::ComicSearchEntity
+);
+impl_paged!(
+    ComicEpPageData,
+    crate::api::types::comic_ep_entity::ComicEpEntity
+);
+impl_paged!(
+    ComicEpPicturePageData,
+    crate::api::types::comic_ep_picture_entity::ComicEpPictureEntity
+);
+impl_paged!(
+    ComicCommentPageData,
+    crate::api::types::comic_comment_entity::ComicCommentEntity
+);
+impl_paged!(
+    AnnouncementPageData,
+    crate::api::types::announcement_entity::AnnouncementEntity
+);
+
+/// 从 `start_page` 开始逐页请求的异步流，请求失败或没有更多页面时结束。
+///
+/// 下一页的页码由上一页返回的 `page` 决定，所以屏蔽后补足了多页的结果也不会重复。
+pub fn page_stream<P, F, Fut>(
+    start_page: i32,
+    fetch: F,
+) -> impl Stream<Item = Result<P, CustomError>>
+where
+    P: Paged,
+    F: Fn(i32) -> Fut,
+    Fut: Future<Output = Result<P, CustomError>>,
+{
+    stream::unfold(Some(start_page.max(1)), move |page| {
+        let next = page.map(&fetch);
+        async move {
+            let page_data = match next?.await {
+                Ok(page_data) => page_data,
+                Err(e) => return Some((Err(e), None)),
+            };
+            let next_page = (page_data.page() < page_data.pages()).then_some(page_data.page() + 1);
+            Some((Ok(page_data), next_page))
+        }
+    })
+}
+
+/// 获取全部页面的数据并按页码顺序合并。
+///
+/// 先请求第一页得到总页数，再以最多 `concurrency` 个并发请求剩余页面。
+pub async fn fetch_all<P, F, Fut>(concurrency: usize, fetch: F) -> Result<Vec<P::Item>, CustomError>
+where
+    P: Paged,
+    F: Fn(i32) -> Fut,
+    Fut: Future<Output = Result<P, CustomError>>,
+{
+    fetch_pages(concurrency, i32::MAX, fetch).await
+}
+
+/// 获取前 `max_pages` 页的数据并按页码顺序合并，第一页总会被请求。
+pub async fn fetch_pages<P, F, Fut>(
+    concurrency: usize,
+    max_pages: i32,
+    fetch: F,
+) -> Result<Vec<P::Item>, CustomError>
+where
+    P: Paged,
+    F: Fn(i32) -> Fut,
+    Fut: Future<Output = Result<P, CustomError>>,
+{
+    let first = fetch(1).await?;
+    fetch_remaining_pages(first, concurrency, max_pages, fetch).await
+}
+
+/// 已经请求到第一页 `first` 时，获取前 `max_pages` 页中剩余的页面并按页码顺序合并。
+pub async fn fetch_remaining_pages<P, F, Fut>(
+    first: P,
+    concurrency: usize,
+    max_pages: i32,
+    fetch: F,
+) -> Result<Vec<P::Item>, CustomError>
+where
+    P: Paged,
+    F: Fn(i32) -> Fut,
+    Fut: Future<Output = Result<P, CustomError>>,
+{
+    let pages = first.pages().min(max_pages);
+    let mut items = first.into_docs();
+
+    let rest = stream::iter(2..=pages)
+        .map(&fetch)
+        .buffered(concurrency.max(1))
+        .try_collect::<Vec<P>>()
+        .await?;
+    for page_data in rest {
+        items.extend(page_data.into_docs());
+    }
+
+    Ok(items)
+}
+
+/// 把分页流的每一页推送给 Dart，请求失败时推送错误并结束。
+pub async fn forward_pages<P>(
+    pages: impl Stream<Item = Result<P, CustomError>>,
+    sink: StreamSink<P>,
+) -> Result<(), CustomError>
+where
+    P: Paged + SseEncode,
+    CustomError: SseEncode,
+{
+    let mut pages = pin!(pages);
+    while let Some(page_data) = pages.next().await {
+        let sent = match page_data {
+            Ok(page_data) => sink.add(page_data),
+            Err(e) => {
+                let _ = sink.add_error(e);
+                break;
+            }
+        };
+        // Dart 端已取消订阅
+        if sent.is_err() {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::{
+        error::custom_error::{CustomError, CustomErrorType},
+        types::page_data::PageData,
+        utils::paginator::{fetch_all, page_stream},
+    };
+    use futures::StreamExt;
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    async fn fetch(page: i32) -> Result<PageData<i32>, CustomError> {
+        // 页码越小越晚返回，用于验证结果按页码排序
+        tokio::time::sleep(Duration::from_millis((10 - page) as u64 * 5)).await;
+        if page > 5 {
+            return Err(CustomError {
+                error_code: CustomErrorType::BadRequest,
+                error_message: "out of range".to_string(),
+            });
+        }
+        Ok(PageData {
+            total: 10,
+            limit: 2,
+            page,
+            pages: 5,
+            docs: vec![page * 10, page * 10 + 1],
+        })
+    }
+
+    #[tokio::test]
+    async fn test_page_stream() {
+        let pages = page_stream(2, fetch)
+            .map(|page_data| page_data.unwrap().page)
+            .collect::<Vec<i32>>()
+            .await;
+        assert_eq!(pages, vec![2, 3, 4, 5]);
+
+        let results = page_stream(6, fetch).collect::<Vec<_>>().await;
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_all() {
+        let running = AtomicUsize::new(0);
+        let max_running = AtomicUsize::new(0);
+
+        let items = fetch_all(2, |page| {
+            let running = &running;
+            let max_running = &max_running;
+            async move {
+                let current = running.fetch_add(1, Ordering::SeqCst) + 1;
+                max_running.fetch_max(current, Ordering::SeqCst);
+                let result = fetch(page).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+                result
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(items, vec![10, 11, 20, 21, 30, 31, 40, 41, 50, 51]);
+        assert!(max_running.load(Ordering::SeqCst) <= 2);
+    }
+}
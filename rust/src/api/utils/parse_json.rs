@@ -26,6 +26,10 @@ where
                 })?;
                 Ok(result)
             }
+            Some(400) => Err(CustomError {
+                error_code: CustomErrorType::ValidationError,
+                error_message: json["message"].as_str().unwrap_or_default().to_string(),
+            }),
             _ => Err(CustomError {
                 error_code: CustomErrorType::ParseJsonError,
                 error_message: json["message"].clone().to_string(),
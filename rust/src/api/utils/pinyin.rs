@@ -0,0 +1,63 @@
+use crate::api::utils::chinese::to_simplified;
+use flutter_rust_bridge::frb;
+use std::{collections::HashMap, sync::LazyLock};
+
+/// 内置的拼音表，格式见文件开头的说明。
+const PINYIN_TABLE: &str = include_str!("pinyin.txt");
+
+static PINYIN: LazyLock<HashMap<char, &'static str>> = LazyLock::new(|| {
+    PINYIN_TABLE
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once(' '))
+        .flat_map(|(pinyin, chars)| chars.chars().map(move |c| (c, pinyin)))
+        .collect()
+});
+
+/// 获取汉字不带声调的拼音，繁体字按对应的简体字查找，不在拼音表中时返回 `None`。
+#[frb(ignore)]
+pub fn pinyin(c: char) -> Option<&'static str> {
+    PINYIN.get(&c).copied().or_else(|| {
+        to_simplified(&c.to_string())
+            .chars()
+            .next()
+            .and_then(|simplified| PINYIN.get(&simplified).copied())
+    })
+}
+
+/// 将文本中的汉字转换为以空格分隔的拼音，其他字符保持不变。
+#[frb(sync)]
+pub fn picacg_to_pinyin(text: String) -> String {
+    let mut result = String::new();
+    for c in text.chars() {
+        match pinyin(c) {
+            Some(pinyin) => {
+                if !result.is_empty() && !result.ends_with(' ') {
+                    result.push(' ');
+                }
+                result.push_str(pinyin);
+                result.push(' ');
+            }
+            None => result.push(c),
+        }
+    }
+    result.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::utils::pinyin::{picacg_to_pinyin, pinyin};
+
+    #[test]
+    fn test_pinyin() {
+        assert_eq!(pinyin('进'), Some("jin"));
+        assert_eq!(pinyin('擊'), Some("ji"));
+        assert_eq!(pinyin('绿'), Some("lv"));
+        assert_eq!(pinyin('a'), None);
+        assert_eq!(
+            picacg_to_pinyin("进击的巨人".to_string()),
+            "jin ji de ju ren"
+        );
+        assert_eq!(picacg_to_pinyin("第2季".to_string()), "di 2 ji");
+    }
+}
@@ -0,0 +1,44 @@
+use std::time::Duration;
+use tokio::time::{sleep_until, Instant};
+
+/// 限制请求频率，相邻两次请求至少间隔 `interval`。
+pub struct RateLimiter {
+    interval: Duration,
+    next: Option<Instant>,
+}
+
+impl RateLimiter {
+    pub fn new(interval: Duration) -> Self {
+        RateLimiter {
+            interval,
+            next: None,
+        }
+    }
+
+    /// 等待到允许发起下一次请求。
+    pub async fn wait(&mut self) {
+        if let Some(next) = self.next {
+            sleep_until(next).await;
+        }
+        self.next = Some(Instant::now() + self.interval);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::utils::rate_limiter::RateLimiter;
+    use std::time::Duration;
+    use tokio::time::Instant;
+
+    #[tokio::test]
+    async fn test_rate_limiter() {
+        let mut limiter = RateLimiter::new(Duration::from_millis(50));
+        let start = Instant::now();
+
+        limiter.wait().await;
+        assert!(start.elapsed() < Duration::from_millis(50));
+        limiter.wait().await;
+        limiter.wait().await;
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+}
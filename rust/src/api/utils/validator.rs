@@ -0,0 +1,70 @@
+/// 校验邮箱（账号）格式是否合法。
+///
+/// 仅做基础的格式检查：不能包含空白字符，必须且只能包含一个 `@`，
+/// 且域名部分至少包含一个 `.` 并且每一段都不为空。
+pub fn is_valid_email(email: &str) -> bool {
+    if email.chars().any(char::is_whitespace) {
+        return false;
+    }
+
+    let Some((local, domain)) = email.split_once('@') else {
+        return false;
+    };
+
+    if local.is_empty() || domain.contains('@') || !domain.contains('.') {
+        return false;
+    }
+
+    domain.split('.').all(|part| !part.is_empty())
+}
+
+/// 校验手机号格式是否合法：可选的 `+` 前缀，后接 10~15 位数字。
+pub fn is_valid_phone(phone: &str) -> bool {
+    let digits = phone.strip_prefix('+').unwrap_or(phone);
+    (10..=15).contains(&digits.len()) && digits.chars().all(|c| c.is_ascii_digit())
+}
+
+/// 校验账号格式是否合法：注册、找回密码和重置密码共用，邮箱或手机号均可。
+pub fn is_valid_account(account: &str) -> bool {
+    is_valid_email(account) || is_valid_phone(account)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::utils::validator::{is_valid_account, is_valid_email, is_valid_phone};
+
+    #[test]
+    fn test_is_valid_email() {
+        assert!(is_valid_email("user@example.com"));
+        assert!(is_valid_email("user.name+tag@mail.example.org"));
+
+        assert!(!is_valid_email(""));
+        assert!(!is_valid_email("email"));
+        assert!(!is_valid_email("@example.com"));
+        assert!(!is_valid_email("user@example"));
+        assert!(!is_valid_email("user@@example.com"));
+        assert!(!is_valid_email("user@example..com"));
+        assert!(!is_valid_email("user name@example.com"));
+    }
+
+    #[test]
+    fn test_is_valid_phone() {
+        assert!(is_valid_phone("13800138000"));
+        assert!(is_valid_phone("+8613800138000"));
+
+        assert!(!is_valid_phone(""));
+        assert!(!is_valid_phone("123456789"));
+        assert!(!is_valid_phone("1380013800a"));
+        assert!(!is_valid_phone("++8613800138000"));
+    }
+
+    #[test]
+    fn test_is_valid_account() {
+        assert!(is_valid_account("user@example.com"));
+        assert!(is_valid_account("13800138000"));
+
+        assert!(!is_valid_account(""));
+        assert!(!is_valid_account("email"));
+        assert!(!is_valid_account("123456789"));
+    }
+}
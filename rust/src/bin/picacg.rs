@@ -0,0 +1,473 @@
+//! PicACG 命令行客户端。
+//!
+//! 与应用共用存储目录中的 `config.json`、密钥库和数据库，适合在脚本中批量归档收藏、检查更新等。
+//! 所有命令都支持 `--json`，输出一行 JSON 便于管道处理。
+//!
+//! 登录令牌保存在密钥库中，除 `serve` 外的命令都需要先解锁：应用使用平台密钥时通过 `--key`
+//! 传入应用保存在系统安全存储中的密钥（Base64），应用使用口令时通过 `--passphrase` 传入口令。
+//! 只在命令行中使用时，第一次解锁会用传入的密钥或口令创建新的密钥库。
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use clap::{Parser, Subcommand, ValueEnum};
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
+#[cfg(feature = "opds")]
+use rust_lib_picacg::api::opds::{picacg_opds_start, picacg_opds_stop, OpdsSettings};
+use rust_lib_picacg::api::{
+    error::custom_error::{CustomError, CustomErrorType},
+    reqs::{
+        comic::{
+            picacg_comic_category, picacg_comic_eps_all, picacg_comic_favourite,
+            picacg_comic_favourite_all, picacg_comic_info, picacg_comic_page, picacg_comic_search,
+        },
+        download::{download_comic, DownloadProgress},
+        punch_in::picacg_punch_in_run,
+        search_query::picacg_comic_search_query,
+        user::{picacg_user_login, picacg_user_profile},
+    },
+    storage::{
+        config::{picacg_config_set_user_data, picacg_load_config},
+        database::picacg_open_database,
+        download_data::DownloadStatus,
+        root::picacg_init_storage,
+        secret_data::{
+            picacg_secrets_is_unlocked, picacg_secrets_unlock_with_key,
+            picacg_secrets_unlock_with_passphrase,
+        },
+        user_data::UserData,
+    },
+    types::{comic_query::ComicQuery, sort::Sort},
+};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, IsTerminal},
+    path::PathBuf,
+    process::ExitCode,
+    sync::Mutex,
+};
+
+#[derive(Parser)]
+#[command(name = "picacg", version, about = "PicACG command-line client")]
+struct Cli {
+    /// Storage directory shared with the app (contains config.json and the database)
+    #[arg(long, env = "PICACG_STORAGE")]
+    storage: PathBuf,
+
+    /// Base64 platform key that unlocks the app's secret store holding the login token
+    #[arg(
+        long,
+        global = true,
+        env = "PICACG_SECRET_KEY",
+        hide_env_values = true,
+        conflicts_with = "passphrase"
+    )]
+    key: Option<String>,
+
+    /// Passphrase that unlocks a secret store protected by a passphrase
+    #[arg(long, global = true, env = "PICACG_PASSPHRASE", hide_env_values = true)]
+    passphrase: Option<String>,
+
+    /// Print results as a single line of JSON
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Sign in and save the token to the secret store
+    Login {
+        username: String,
+        /// Read from stdin when omitted
+        #[arg(long, env = "PICACG_PASSWORD", hide_env_values = true)]
+        password: Option<String>,
+    },
+    /// Show the profile of the signed-in user
+    Profile,
+    /// Punch in for today
+    PunchIn,
+    /// Search comics by keyword, or by query language with --query
+    Search {
+        keyword: String,
+        /// Treat the keyword as a query such as `tag:全彩 -tag:NTR "exact phrase"`
+        #[arg(long)]
+        query: bool,
+        #[arg(long, value_enum, default_value_t = SortArg::Default)]
+        sort: SortArg,
+        #[arg(long, default_value_t = 1)]
+        page: i32,
+        /// Page size of --query results
+        #[arg(long, default_value_t = 20)]
+        limit: i32,
+        /// Restrict to categories, can be repeated
+        #[arg(long)]
+        category: Vec<String>,
+    },
+    /// Browse comics by category, tag, creator or Chinese team
+    Browse {
+        #[arg(long)]
+        category: Option<String>,
+        #[arg(long)]
+        tag: Option<String>,
+        #[arg(long)]
+        creator: Option<String>,
+        #[arg(long)]
+        team: Option<String>,
+        #[arg(long, value_enum, default_value_t = SortArg::Default)]
+        sort: SortArg,
+        #[arg(long, default_value_t = 1)]
+        page: i32,
+    },
+    /// Show comic details
+    Info { comic_id: String },
+    /// List all episodes of a comic
+    Eps { comic_id: String },
+    /// List favourite comics, all pages unless --page is given
+    Favourites {
+        #[arg(long, value_enum, default_value_t = SortArg::Default)]
+        sort: SortArg,
+        #[arg(long)]
+        page: Option<i32>,
+    },
+    /// Download episodes into the storage directory
+    Download {
+        comic_id: String,
+        /// Episode orders to download, can be repeated; all episodes when omitted
+        #[arg(long = "ep")]
+        eps: Vec<i32>,
+    },
+    /// List comic categories
+    Categories,
+    /// Serve downloaded comics as an OPDS catalog until interrupted
+    #[cfg(feature = "opds")]
+    Serve {
+        /// Address to listen on; non-loopback addresses require --username and --password
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+        /// Require basic auth with this username, together with --password
+        #[arg(long)]
+        username: Option<String>,
+        #[arg(long, env = "PICACG_OPDS_PASSWORD", hide_env_values = true)]
+        password: Option<String>,
+    },
+}
+
+impl Command {
+    /// 命令是否需要密钥库中的登录令牌。
+    fn requires_secrets(&self) -> bool {
+        match self {
+            #[cfg(feature = "opds")]
+            Command::Serve { .. } => false,
+            _ => true,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum SortArg {
+    Default,
+    Newest,
+    Oldest,
+    Likes,
+    Views,
+}
+
+impl From<SortArg> for Sort {
+    fn from(sort: SortArg) -> Self {
+        match sort {
+            SortArg::Default => Sort::Default,
+            SortArg::Newest => Sort::TimeNewest,
+            SortArg::Oldest => Sort::TimeOldest,
+            SortArg::Likes => Sort::LikeMost,
+            SortArg::Views => Sort::ViewMost,
+        }
+    }
+}
+
+/// `--json` 时输出一行 JSON，否则输出 `text` 生成的文本。
+fn print<T: Serialize>(json: bool, value: &T, text: impl FnOnce(&T) -> String) {
+    if json {
+        match serde_json::to_string(value) {
+            Ok(line) => println!("{}", line),
+            Err(e) => eprintln!("error: Failed to serialize output: {}", e),
+        }
+    } else {
+        println!("{}", text(value));
+    }
+}
+
+fn comic_line(id: &str, title: &str, author: &str) -> String {
+    format!("{}  {}  [{}]", id, title, author)
+}
+
+/// 从终端输入时不回显密码，从管道输入时读取一行。
+fn read_password() -> Result<String, CustomError> {
+    if io::stdin().is_terminal() {
+        return rpassword::prompt_password("Password: ").map_err(|e| CustomError {
+            error_code: CustomErrorType::FileReadError,
+            error_message: format!("Failed to read password: {}", e),
+        });
+    }
+
+    let mut password = String::new();
+    io::stdin()
+        .lock()
+        .read_line(&mut password)
+        .map_err(|e| CustomError {
+            error_code: CustomErrorType::FileReadError,
+            error_message: format!("Failed to read password: {}", e),
+        })?;
+    Ok(password.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// 每个章节一个进度条，`--json` 时不显示。
+fn download_progress(json: bool) -> impl Fn(DownloadProgress) {
+    let multi = MultiProgress::new();
+    if json {
+        multi.set_draw_target(ProgressDrawTarget::hidden());
+    }
+    let style = ProgressStyle::with_template("{msg:24!} [{bar:32}] {pos}/{len}")
+        .unwrap_or_else(|_| ProgressStyle::default_bar())
+        .progress_chars("=> ");
+    let bars = Mutex::new(HashMap::<i32, ProgressBar>::new());
+
+    move |progress: DownloadProgress| {
+        let Ok(mut bars) = bars.lock() else {
+            return;
+        };
+        let bar = bars.entry(progress.ep_order).or_insert_with(|| {
+            let bar = multi.add(ProgressBar::new(progress.total_pages as u64));
+            bar.set_style(style.clone());
+            bar.set_message(progress.ep_title.clone());
+            bar
+        });
+        bar.set_position(progress.downloaded_pages as u64);
+        match progress.status {
+            DownloadStatus::Completed => bar.finish(),
+            DownloadStatus::Failed => bar.abandon(),
+            DownloadStatus::Pending | DownloadStatus::Downloading => {}
+        }
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), CustomError> {
+    picacg_init_storage(cli.storage.to_string_lossy().to_string())?;
+    if let Some(key) = cli.key {
+        let key = STANDARD.decode(key.trim()).map_err(|e| CustomError {
+            error_code: CustomErrorType::ParameterError,
+            error_message: format!("Secret key must be base64: {}", e),
+        })?;
+        picacg_secrets_unlock_with_key(key)?;
+    } else if let Some(passphrase) = cli.passphrase {
+        picacg_secrets_unlock_with_passphrase(passphrase).await?;
+    }
+    if cli.command.requires_secrets() && !picacg_secrets_is_unlocked() {
+        return Err(CustomError {
+            error_code: CustomErrorType::SecretError,
+            error_message: "The secret store is locked, pass --key or --passphrase".to_string(),
+        });
+    }
+    // 打开数据库后配置以数据库中的为准，需要在加载配置前打开
+    picacg_open_database().await?;
+    picacg_load_config()?;
+
+    let json = cli.json;
+    match cli.command {
+        Command::Login { username, password } => {
+            let password = match password {
+                Some(password) => password,
+                None => read_password()?,
+            };
+            let login = picacg_user_login(username, password).await?;
+            picacg_config_set_user_data(UserData { token: login.token })?;
+
+            let profile = picacg_user_profile().await?;
+            print(json, &profile, |profile| {
+                format!("Signed in as {} ({})", profile.name, profile.id)
+            });
+        }
+        Command::Profile => {
+            let profile = picacg_user_profile().await?;
+            print(json, &profile, |profile| {
+                format!(
+                    "{} ({})\nLevel {}, exp {}\nPunched in: {}",
+                    profile.name, profile.id, profile.level, profile.exp, profile.is_punched
+                )
+            });
+        }
+        Command::PunchIn => {
+            let records = picacg_punch_in_run().await?;
+            print(json, &records, |records| {
+                records
+                    .iter()
+                    .map(|record| {
+                        format!(
+                            "{}: {:?}{}",
+                            record.user_name.as_deref().unwrap_or("-"),
+                            record.status,
+                            record
+                                .error_message
+                                .as_deref()
+                                .map(|message| format!(" ({})", message))
+                                .unwrap_or_default()
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            });
+        }
+        Command::Search {
+            keyword,
+            query,
+            sort,
+            page,
+            limit,
+            category,
+        } => {
+            let page_data = if query {
+                picacg_comic_search_query(keyword, sort.into(), page, limit).await?
+            } else {
+                picacg_comic_search(keyword, sort.into(), page, category).await?
+            };
+            print(json, &page_data, |page_data| {
+                let mut lines = page_data
+                    .docs
+                    .iter()
+                    .map(|comic| comic_line(&comic.id, &comic.title, &comic.author))
+                    .collect::<Vec<_>>();
+                lines.push(format!(
+                    "Page {}/{}, {} total, {} hidden",
+                    page_data.page, page_data.pages, page_data.total, page_data.hidden
+                ));
+                lines.join("\n")
+            });
+        }
+        Command::Browse {
+            category,
+            tag,
+            creator,
+            team,
+            sort,
+            page,
+        } => {
+            let query = ComicQuery {
+                category,
+                tag,
+                creator_id: creator,
+                chinese_team: team,
+                sort: sort.into(),
+                page,
+            };
+            let page_data = picacg_comic_page(query).await?;
+            print(json, &page_data, |page_data| {
+                let mut lines = page_data
+                    .docs
+                    .iter()
+                    .map(|comic| comic_line(&comic.id, &comic.title, &comic.author))
+                    .collect::<Vec<_>>();
+                lines.push(format!(
+                    "Page {}/{}, {} total, {} hidden",
+                    page_data.page, page_data.pages, page_data.total, page_data.hidden
+                ));
+                lines.join("\n")
+            });
+        }
+        Command::Info { comic_id } => {
+            let info = picacg_comic_info(comic_id).await?;
+            print(json, &info, |info| {
+                format!(
+                    "{}\nAuthor: {}\nChinese team: {}\nCategories: {}\nTags: {}\n\
+                     Episodes: {}, pages: {}, finished: {}\n\n{}",
+                    info.title,
+                    info.author,
+                    info.chinese_team,
+                    info.categories.join(", "),
+                    info.tags.join(", "),
+                    info.eps_count,
+                    info.pages_count,
+                    info.finished,
+                    info.description
+                )
+            });
+        }
+        Command::Eps { comic_id } => {
+            let eps = picacg_comic_eps_all(comic_id).await?;
+            print(json, &eps, |eps| {
+                eps.iter()
+                    .map(|ep| format!("{}  {}", ep.order, ep.title))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            });
+        }
+        Command::Favourites { sort, page } => {
+            let comics = match page {
+                Some(page) => picacg_comic_favourite(sort.into(), page).await?.docs,
+                None => picacg_comic_favourite_all(sort.into()).await?,
+            };
+            print(json, &comics, |comics| {
+                comics
+                    .iter()
+                    .map(|comic| comic_line(&comic.id, &comic.title, &comic.author))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            });
+        }
+        Command::Download { comic_id, eps } => {
+            let episodes = download_comic(comic_id, eps, download_progress(json)).await?;
+            print(json, &episodes, |episodes| {
+                format!("Downloaded {} episodes", episodes.len())
+            });
+        }
+        Command::Categories => {
+            let categories = picacg_comic_category().await?;
+            print(json, &categories, |categories| {
+                categories
+                    .iter()
+                    .filter(|category| !category.is_web)
+                    .map(|category| category.title.clone())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            });
+        }
+        #[cfg(feature = "opds")]
+        Command::Serve {
+            host,
+            port,
+            username,
+            password,
+        } => {
+            let settings = OpdsSettings {
+                host,
+                port,
+                username,
+                password,
+            };
+            let server = picacg_opds_start(settings).await?;
+            print(json, &server, |server| {
+                format!(
+                    "Serving OPDS catalog at http://{}{}, press Ctrl-C to stop",
+                    server.address, server.catalog_path
+                )
+            });
+            let _ = tokio::signal::ctrl_c().await;
+            picacg_opds_stop()?;
+        }
+    }
+
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run(Cli::parse()).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {:?}: {}", e.error_code, e.error_message);
+            ExitCode::FAILURE
+        }
+    }
+}